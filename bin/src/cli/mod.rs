@@ -2,13 +2,24 @@
 mod rpc;
 mod utils;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand};
 use color_eyre::Result;
 use probe_args::commands::utility;
+use probe_args::config;
 
 #[derive(Parser, Debug)]
 #[command(name = "probe", version, about, long_about = None)]
-pub enum Cli {
+pub struct Cli {
+    #[arg(long, global = true, env = "PROBE_PROFILE", value_name = "NAME")]
+    #[arg(help = "The profile of the configuration file to take the default values from.")]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
     #[command(flatten)]
     Utilities(utility::UtilityCommands),
 
@@ -18,11 +29,36 @@ pub enum Cli {
 }
 
 impl Cli {
+    /// Returns the profile selected on the command line or in the environment.
+    ///
+    /// The profile provides the default values of the arguments, so it has to be known before
+    /// the command line is parsed. It's found in a first pass that ignores the other errors.
+    pub fn selected_profile() -> Option<String> {
+        match Self::command().ignore_errors(true).try_get_matches() {
+            Ok(matches) => matches.get_one::<String>("profile").cloned(),
+            // `--help` and `--version` are still errors
+            Err(_) => std::env::var("PROBE_PROFILE").ok(),
+        }
+    }
+
     pub fn execute(self) -> Result<()> {
-        match self {
-            Cli::Utilities(cmd) => utils::execute(cmd)?,
+        match self.command {
+            Commands::Utilities(cmd @ utility::UtilityCommands::Config { .. }) => {
+                // the configuration file can still be fixed if it's invalid
+                if let Err(e) = config::config() {
+                    eprintln!("Warning: {e:#}, its profiles are ignored");
+                }
+                utils::execute(cmd)?
+            }
+            Commands::Utilities(cmd) => {
+                config::ensure_profile()?;
+                utils::execute(cmd)?
+            }
             #[cfg(feature = "rpc")]
-            Cli::Rpc(rpc) => rpc::execute(rpc)?,
+            Commands::Rpc(rpc) => {
+                config::ensure_profile()?;
+                rpc::execute(rpc)?
+            }
         }
         Ok(())
     }
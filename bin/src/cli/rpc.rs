@@ -1,25 +1,51 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+use color_eyre::eyre::eyre;
 use color_eyre::Result;
 pub use probe_args::commands::rpc::RpcCommands;
 use probe_ops as ops;
 
+use super::Cli;
+
 pub fn execute(command: RpcCommands) -> Result<()> {
     match command {
+        RpcCommands::ShellCompletions { shell } => completions(shell)?,
+        RpcCommands::Account(args) => ops::rpc::account::run(args)?,
+        RpcCommands::Age(args) => ops::rpc::block::age(args)?,
         RpcCommands::Balance(args) => ops::rpc::balance::get(args)?,
-        // RpcCommands::Call(args) => ops::rpc::call::call(args)?,
-        // RpcCommands::Tx(args) => ops::rpc::transaction::get(args)?,
-        // RpcCommands::TxCount(args) => ops::rpc::transaction::count(args)?,
-        // RpcCommands::TxStatus(args) => ops::rpc::transaction::status(args)?,
+        RpcCommands::Block(args) => ops::rpc::block::get(args)?,
+        RpcCommands::BlockNumber(args) => ops::rpc::block::number(args)?,
+        RpcCommands::Broadcast(args) => ops::rpc::send::broadcast(args)?,
+        RpcCommands::Call(args) => ops::rpc::call::call(args)?,
+        RpcCommands::ChainId(args) => ops::rpc::chain::id(args)?,
+        RpcCommands::Class(args) => ops::rpc::class::get(args)?,
+        RpcCommands::Code(args) => ops::rpc::class::code(args)?,
+        RpcCommands::ContractClass(args) => ops::rpc::contract::class_hash(args)?,
+        RpcCommands::Declare(args) => ops::rpc::send::declare(args)?,
+        RpcCommands::Deploy(args) => ops::rpc::send::deploy(args)?,
+        RpcCommands::Events(args) => ops::rpc::events::get(args)?,
+        RpcCommands::Invoke(args) => ops::rpc::send::invoke(args)?,
+        RpcCommands::LegacyDeclare(args) => ops::rpc::send::legacy_declare(args)?,
+        RpcCommands::Nonce(args) => ops::rpc::contract::nonce(args)?,
+        RpcCommands::Portfolio(args) => ops::rpc::balance::portfolio(args)?,
+        RpcCommands::Rpc(args) => ops::rpc::raw::send(args)?,
+        RpcCommands::SignTx(args) => ops::rpc::send::sign_tx(args)?,
+        RpcCommands::StateUpdate(args) => ops::rpc::state_update::get(args)?,
+        RpcCommands::Storage(args) => ops::rpc::contract::storage(args)?,
+        RpcCommands::Syncing(args) => ops::rpc::chain::syncing(args)?,
+        RpcCommands::Tx(args) => ops::rpc::transaction::get(args)?,
+        RpcCommands::TxCount(args) => ops::rpc::transaction::count(args)?,
+        RpcCommands::TxStatus(args) => ops::rpc::transaction::status(args)?,
         RpcCommands::Receipt(args) => ops::rpc::transaction::receipt(args)?,
-        // RpcCommands::Rpc(args) => ops::rpc::raw::send(args)?,
-        // RpcCommands::Block(args) => ops::rpc::block::get(args)?,
-        // RpcCommands::Age(args) => ops::rpc::block::age(args)?,
-        // RpcCommands::BlockNumber(args) => ops::rpc::block::number(args)?,
-        // RpcCommands::ChainId(args) => ops::rpc::chain::id(args)?,
-        // RpcCommands::Syncing(args) => ops::rpc::chain::syncing(args)?,
-        _ => {
-            unimplemented!("This command is not implemented yet")
-        }
     }
 
     Ok(())
 }
+
+fn completions(shell: Option<Shell>) -> Result<()> {
+    let shell = shell
+        .or_else(Shell::from_env)
+        .ok_or_else(|| eyre!("unable to identify shell from environment variable"))?;
+    clap_complete::generate(shell, &mut Cli::command(), "probe", &mut std::io::stdout());
+    Ok(())
+}
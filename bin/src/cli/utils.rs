@@ -1,11 +1,158 @@
+use color_eyre::eyre::bail;
 use color_eyre::Result;
+use probe_args::commands::rpc::StorageArgs;
 pub use probe_args::commands::utility::UtilityCommands;
+use probe_args::commands::utility::{EcdsaCommand, TxHashCommand};
+use probe_fmt::{Pretty, WithNames};
 use probe_ops as ops;
 
 pub fn execute(command: UtilityCommands) -> Result<()> {
     match command {
-        UtilityCommands::Index(args) => ops::utility::storage_address(args)?,
-        _ => unimplemented!("This command is not implemented yet"),
+        UtilityCommands::DecToHex { dec, pad } => println!("{}", ops::utility::to_hex(&dec, pad)),
+
+        UtilityCommands::HexToDec { hex } => println!("{}", ops::utility::to_dec(&hex)),
+
+        UtilityCommands::Ecdsa { commands } => match commands {
+            EcdsaCommand::Sign { message, private_key } => {
+                let signature = ops::utility::ecdsa_sign(&private_key, &message)?;
+                println!("{:#x} {:#x}", signature.r, signature.s);
+            }
+
+            EcdsaCommand::Verify { message, signature, verifying_key } => {
+                let (r, s) = (signature[0], signature[1]);
+                let is_valid = ops::utility::ecdsa_verify(&verifying_key, &message, r, s)?;
+                println!("{is_valid}");
+            }
+        },
+
+        UtilityCommands::FromAscii { ascii } => {
+            println!("{}", ops::utility::felt_to_short_string(&ascii)?)
+        }
+
+        UtilityCommands::ToAscii { short_str } => {
+            println!("{:#x}", ops::utility::short_string_to_felt(&short_str)?)
+        }
+
+        UtilityCommands::ToByteArray { string } => {
+            println!("{}", ops::utility::to_byte_array(string)?.prettify())
+        }
+
+        UtilityCommands::FromByteArray { felts } => {
+            println!("{}", ops::utility::from_byte_array(felts)?)
+        }
+
+        UtilityCommands::SplitU256 { value } => {
+            let (low, high) = ops::utility::split_u256(&value)?;
+            println!("{low:#x} {high:#x}");
+        }
+
+        UtilityCommands::Convert { commands, output } => {
+            println!("{}", ops::utility::convert(commands)?.display(output)?)
+        }
+
+        UtilityCommands::Keccak { data } => println!("{:#x}", ops::utility::keccak(&data)?),
+
+        UtilityCommands::Pedersen { elements } => {
+            println!("{:#x}", ops::utility::pedersen(&elements))
+        }
+
+        UtilityCommands::Poseidon { elements } => {
+            println!("{:#x}", ops::utility::poseidon(&elements))
+        }
+
+        UtilityCommands::Felt { expression, dec } => {
+            let result = ops::utility::eval_felt(&expression.join(" "))?;
+            if dec {
+                println!("{result}")
+            } else {
+                println!("{result:#x}")
+            }
+        }
+
+        UtilityCommands::Index(args) => {
+            let address = ops::utility::storage_address(&args)?;
+
+            match args.contract {
+                Some(contract_address) => {
+                    ops::rpc::contract::storage(StorageArgs {
+                        contract_address,
+                        index: format!("{address:#x}"),
+                        r#type: None,
+                        layout: None,
+                        block_id: args.block_id,
+                        starknet: args.starknet,
+                    })?;
+                }
+                None => println!("{address:#x}"),
+            }
+        }
+
+        UtilityCommands::ClassHash { contract } => {
+            println!("{:#x}", ops::utility::class_hash(contract)?)
+        }
+
+        UtilityCommands::CompiledClassHash { contract } => {
+            println!("{:#x}", ops::utility::compiled_class_hash(contract)?)
+        }
+
+        UtilityCommands::ComputeAddress { caller_address, salt, class_hash, calldata } => {
+            let address =
+                ops::utility::compute_address(caller_address, salt, class_hash, &calldata);
+            println!("{address:#x}");
+        }
+
+        UtilityCommands::TxHash { commands } => match commands {
+            TxHashCommand::Invoke(args) => println!("{:#x}", ops::utility::invoke_tx_hash(args)),
+
+            TxHashCommand::Declare(args) => {
+                println!("{:#x}", ops::utility::declare_tx_hash(args))
+            }
+
+            TxHashCommand::DeployAccount(args) => {
+                println!("{:#x}", ops::utility::deploy_account_tx_hash(args))
+            }
+
+            TxHashCommand::Verify { file, chain } => {
+                let (expected, computed) = ops::utility::verify_tx_hash(file, chain)?;
+                if expected != computed {
+                    bail!(
+                        "transaction hash mismatch: expected {expected:#x} but computed \
+                         {computed:#x}"
+                    );
+                }
+                println!("{computed:#x}");
+            }
+        },
+
+        UtilityCommands::DecodeCalldata { calldata } => {
+            let decoded = ops::utility::decode_calldata(calldata)?;
+            println!("{}", WithNames::new(&decoded, &ops::utility::names()?).prettify())
+        }
+
+        UtilityCommands::Selector(args) => {
+            if args.has_imports() {
+                let added = ops::utility::import_selectors(&args)?;
+                println!("Imported {added} new name(s) into the selector database.");
+            }
+
+            if let Some(value) = &args.value {
+                for line in ops::utility::selector(value)? {
+                    println!("{line}");
+                }
+            }
+        }
+
+        UtilityCommands::Config { commands } => {
+            if let Some(output) = ops::utility::config(commands)? {
+                println!("{output}");
+            }
+        }
+
+        UtilityCommands::Alias { commands } => {
+            for line in ops::utility::alias(commands)? {
+                println!("{line}");
+            }
+        }
     }
 
     Ok(())
@@ -8,6 +8,7 @@ use color_eyre::Result;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+    probe_args::config::select_profile(Cli::selected_profile());
     let cli = Cli::parse();
 
     match cli.execute() {
@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::get_contract_address;
+use starknet::macros::felt;
+
+/// Name of the directory, inside a chain's keystore directory, that holds the keystores of
+/// accounts which have been created but not yet deployed.
+pub const UNDEPLOYED_DIR: &str = "undeployed";
+
+/// The supported account contract implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum AccountFlavour {
+    /// OpenZeppelin account
+    Oz,
+    /// Argent account (without guardian)
+    Argent,
+    /// Braavos account
+    Braavos,
+}
+
+impl AccountFlavour {
+    /// OpenZeppelin account contract v0.8.1
+    pub const OZ_CLASS_HASH: FieldElement =
+        felt!("0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f");
+
+    /// Argent account contract v0.3.0
+    pub const ARGENT_CLASS_HASH: FieldElement =
+        felt!("0x01a736d6ed154502257f02b1ccdf4d9d1089f80811cd6acad48e6b6a9d1f2003");
+
+    /// Braavos account contract v1.0.0
+    pub const BRAAVOS_CLASS_HASH: FieldElement =
+        felt!("0x00816dd0297efc55dc1e7559020a3a825e81ef734b558f03c83325d4da7e6253");
+
+    /// Braavos accounts are always deployed from this base class, which then upgrades itself to
+    /// the actual account implementation given in the deployment signature.
+    pub const BRAAVOS_BASE_CLASS_HASH: FieldElement =
+        felt!("0x013bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6");
+
+    pub fn default_class_hash(&self) -> FieldElement {
+        match self {
+            Self::Oz => Self::OZ_CLASS_HASH,
+            Self::Argent => Self::ARGENT_CLASS_HASH,
+            Self::Braavos => Self::BRAAVOS_CLASS_HASH,
+        }
+    }
+}
+
+/// The parameters needed to deploy an account, stored alongside its (undeployed) keystore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDeployment {
+    pub flavour: AccountFlavour,
+    /// The class hash of the account implementation.
+    pub class_hash: FieldElement,
+    pub salt: FieldElement,
+    pub public_key: FieldElement,
+}
+
+impl AccountDeployment {
+    /// The class hash used in the `DEPLOY_ACCOUNT` transaction.
+    pub fn deployed_class_hash(&self) -> FieldElement {
+        match self.flavour {
+            AccountFlavour::Braavos => AccountFlavour::BRAAVOS_BASE_CLASS_HASH,
+            _ => self.class_hash,
+        }
+    }
+
+    pub fn constructor_calldata(&self) -> Vec<FieldElement> {
+        match self.flavour {
+            AccountFlavour::Oz | AccountFlavour::Braavos => vec![self.public_key],
+            // owner and guardian
+            AccountFlavour::Argent => vec![self.public_key, FieldElement::ZERO],
+        }
+    }
+
+    /// The counterfactual address of the account.
+    pub fn address(&self) -> FieldElement {
+        get_contract_address(
+            self.salt,
+            self.deployed_class_hash(),
+            &self.constructor_calldata(),
+            FieldElement::ZERO,
+        )
+    }
+
+    /// Path of the deployment file that belongs to the given keystore.
+    pub fn path_for(keystore: impl AsRef<Path>) -> PathBuf {
+        keystore.as_ref().with_extension("deployment.json")
+    }
+
+    pub fn read(keystore: impl AsRef<Path>) -> Result<Self> {
+        crate::opts::account::utils::read_json_file(Self::path_for(keystore))
+    }
+
+    pub fn write(&self, keystore: impl AsRef<Path>) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::path_for(keystore), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The expected addresses were computed with `calculate_contract_address` from starknet_api,
+    // with the class and the constructor calldata the accounts are deployed with, as in starkli:
+    // the public key for OZ, the owner and no guardian for Argent, and the public key on the base
+    // class for Braavos.
+    #[test]
+    fn counterfactual_addresses() {
+        let public_key = felt!("0x26da8d11938b76025862be14fdb8b28438827f73e75e86f7bfa38b196951fa7");
+        let deployment = |flavour: AccountFlavour| AccountDeployment {
+            flavour,
+            class_hash: flavour.default_class_hash(),
+            salt: felt!("0x1"),
+            public_key,
+        };
+
+        let argent = deployment(AccountFlavour::Argent);
+        assert_eq!(argent.deployed_class_hash(), AccountFlavour::ARGENT_CLASS_HASH);
+        assert_eq!(argent.constructor_calldata(), [public_key, FieldElement::ZERO]);
+        let braavos = deployment(AccountFlavour::Braavos);
+        assert_eq!(braavos.deployed_class_hash(), AccountFlavour::BRAAVOS_BASE_CLASS_HASH);
+        assert_eq!(braavos.constructor_calldata(), [public_key]);
+
+        assert_eq!(
+            deployment(AccountFlavour::Oz).address(),
+            felt!("0x248a5679921d798085809aaf7799000949c305015b1396e1ba6f509cbbc19d5")
+        );
+        assert_eq!(
+            deployment(AccountFlavour::Argent).address(),
+            felt!("0x52e18a204e354fd7d721d0c0275b7b85a8cb6cda9101e299f0624215f087fc0")
+        );
+        assert_eq!(
+            deployment(AccountFlavour::Braavos).address(),
+            felt!("0x6b7d28b42522fa16c05525432993e03f9c447a0bfb4ab32c59eb5deefee3cf")
+        );
+    }
+
+    #[test]
+    fn deployment_file_path() {
+        let path = AccountDeployment::path_for("/keystore/sepolia/undeployed/0x123-foo.json");
+        assert_eq!(path, PathBuf::from("/keystore/sepolia/undeployed/0x123-foo.deployment.json"));
+    }
+}
@@ -12,7 +12,8 @@ use starknet::providers::Provider;
 use starknet::signers::{LocalWallet, SigningKey};
 use starknet_keystore::Keystore;
 
-use crate::opts::account::utils::read_json_file;
+use super::deployment::UNDEPLOYED_DIR;
+use crate::opts::account::utils::{read_json_file, OTHER_CHAIN_DIR};
 use crate::opts::starknet::ChainId;
 
 #[derive(Debug)]
@@ -66,13 +67,38 @@ impl SimpleWallet {
         T: AsRef<Path>,
         U: AsRef<str>,
     {
-        let mut path = path.as_ref().to_path_buf();
+        self.write_keystore(self.keystore_dir(path), password, tag)
+    }
 
-        path = match &self.chain {
-            Some(chain) => path.join(chain.to_string()),
-            None => path.join("OTHER"),
-        };
+    /// Same as [`encrypt_keystore`](Self::encrypt_keystore), but the keystore is placed in the
+    /// chain's [`UNDEPLOYED_DIR`] to mark that the account has yet to be deployed.
+    pub fn encrypt_undeployed_keystore<T, U>(
+        &self,
+        path: T,
+        password: U,
+        tag: Option<String>,
+    ) -> Result<PathBuf>
+    where
+        T: AsRef<Path>,
+        U: AsRef<str>,
+    {
+        self.write_keystore(self.keystore_dir(path).join(UNDEPLOYED_DIR), password, tag)
+    }
 
+    /// Returns the directory of this wallet's chain inside the keystore directory `path`.
+    pub fn keystore_dir(&self, path: impl AsRef<Path>) -> PathBuf {
+        match &self.chain {
+            Some(chain) => path.as_ref().join(chain.to_string()),
+            None => path.as_ref().join(OTHER_CHAIN_DIR),
+        }
+    }
+
+    fn write_keystore<U: AsRef<str>>(
+        &self,
+        path: PathBuf,
+        password: U,
+        tag: Option<String>,
+    ) -> Result<PathBuf> {
         DirBuilder::new().recursive(true).create(&path)?;
 
         let mut filename = format!("{:#x}", self.account);
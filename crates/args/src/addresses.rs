@@ -0,0 +1,300 @@
+//! A local address book of named contracts and accounts, used to refer to them by name on the
+//! command line and to show the names behind the addresses found in the outputs.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use color_eyre::eyre::{bail, eyre};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+use starknet::macros::felt;
+
+use crate::config;
+use crate::opts::starknet::ChainId;
+use crate::tokens::TokenRegistry;
+
+/// The name of the address book file, in the configuration directory.
+pub const ADDRESS_BOOK_FILE: &str = "addresses.json";
+
+/// What a named address is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressTag {
+    Token,
+    Account,
+    Contract,
+}
+
+impl fmt::Display for AddressTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token => write!(f, "token"),
+            Self::Account => write!(f, "account"),
+            Self::Contract => write!(f, "contract"),
+        }
+    }
+}
+
+impl FromStr for AddressTag {
+    type Err = color_eyre::Report;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "token" => Ok(Self::Token),
+            "account" => Ok(Self::Account),
+            "contract" => Ok(Self::Contract),
+            _ => bail!("invalid tag `{s}`, expected one of: token, account, contract"),
+        }
+    }
+}
+
+/// A named address, either on a single chain or on all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressEntry {
+    pub name: String,
+    pub address: FieldElement,
+    /// The id of the chain the address is on, or `None` if it's the same on every chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<FieldElement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<AddressTag>,
+}
+
+impl AddressEntry {
+    /// Whether the address is on `chain`, or on any chain if it's unknown.
+    pub fn is_on(&self, chain: Option<FieldElement>) -> bool {
+        match (self.chain, chain) {
+            (Some(entry), Some(chain)) => entry == chain,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddressBook {
+    /// The entries added by the user, saved to the address book file.
+    entries: Vec<AddressEntry>,
+    /// The chain whose entries are looked up, on top of those of every chain. All the entries are
+    /// looked up if it's unknown.
+    chain: Option<FieldElement>,
+}
+
+impl AddressBook {
+    /// The ETH token, at the same address on mainnet and sepolia.
+    pub const ETH: FieldElement =
+        felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
+
+    /// The STRK token, at the same address on mainnet and sepolia.
+    pub const STRK: FieldElement =
+        felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
+
+    /// The USDC token, on mainnet.
+    pub const USDC: FieldElement =
+        felt!("0x053c91253bc9682c04929ca02ed00b3e423f6710d2ee7e0d5ebb06f3ecf368a8");
+
+    /// The names that are always known, unless the user overrides them, i.e. the symbols of the
+    /// built-in tokens.
+    fn builtin() -> Vec<AddressEntry> {
+        TokenRegistry::builtin()
+            .into_iter()
+            .map(|token| AddressEntry {
+                name: token.symbol,
+                address: token.address,
+                chain: token.chain,
+                tags: vec![AddressTag::Token],
+            })
+            .collect()
+    }
+
+    /// Opens the address book file at `path`. The file doesn't have to exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let entries = config::read(path.as_ref(), "address book", |s| serde_json::from_str(s))?;
+        Ok(Self { entries: entries.unwrap_or_default(), chain: None })
+    }
+
+    /// The default location of the address book file, i.e. `~/.config/probe/addresses.json`.
+    pub fn default_path() -> PathBuf {
+        config::path(ADDRESS_BOOK_FILE)
+    }
+
+    /// The address book at the default path, which is only read once, looking up the entries of
+    /// the chain of the active profile.
+    pub fn global() -> Result<&'static Self> {
+        static FILE: OnceLock<Result<AddressBook, String>> = OnceLock::new();
+        static ON_CHAIN: OnceLock<AddressBook> = OnceLock::new();
+
+        let file =
+            FILE.get_or_init(|| Self::open(Self::default_path()).map_err(|e| format!("{e:#}")));
+        let file = file.as_ref().map_err(|e| eyre!("{e}"))?;
+
+        // the chain isn't known while the command line is parsed for the profile
+        if !config::is_profile_selected() {
+            return Ok(file);
+        }
+
+        Ok(ON_CHAIN.get_or_init(|| {
+            file.clone().with_chain(config::profile().chain.as_ref().map(ChainId::id))
+        }))
+    }
+
+    /// Only looks up the entries of `chain`, on top of those of every chain.
+    pub fn with_chain(mut self, chain: Option<FieldElement>) -> Self {
+        self.chain = chain;
+        self
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        config::write(path.as_ref(), &serde_json::to_string_pretty(&self.entries)?)
+    }
+
+    /// Adds an entry, replacing the one with the same name on the same chain. Returns whether an
+    /// entry was replaced.
+    pub fn add(&mut self, entry: AddressEntry) -> Result<bool> {
+        let name = &entry.name;
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            bail!("invalid name `{name}`, names can't be empty or contain spaces");
+        }
+        if FieldElement::from_str(name).is_ok() {
+            bail!("invalid name `{name}`, names can't be numbers");
+        }
+
+        let existing = self
+            .entries
+            .iter()
+            .position(|e| e.name.eq_ignore_ascii_case(name) && e.chain == entry.chain);
+
+        match existing {
+            Some(index) => self.entries[index] = entry,
+            None => self.entries.push(entry),
+        }
+
+        Ok(existing.is_some())
+    }
+
+    /// Removes the entries named `name`, only on `chain` if it's given. Returns the number of
+    /// entries removed.
+    pub fn remove(&mut self, name: &str, chain: Option<FieldElement>) -> usize {
+        let len = self.entries.len();
+        self.entries.retain(|e| {
+            !(e.name.eq_ignore_ascii_case(name) && (chain.is_none() || e.chain == chain))
+        });
+        len - self.entries.len()
+    }
+
+    /// The entries added by the user, followed by the built-in ones that they don't override.
+    pub fn entries(&self) -> impl Iterator<Item = &AddressEntry> {
+        let builtin = builtin_entries().iter().filter(|builtin| {
+            !self.entries.iter().any(|e| e.name.eq_ignore_ascii_case(&builtin.name))
+        });
+        self.entries.iter().chain(builtin)
+    }
+
+    /// Returns the address named `name`, looking only at the entries with `tag` if it's given.
+    ///
+    /// The entries of the chain take priority over those of every chain, which fails if `name` is
+    /// on several chains with different addresses and the chain is unknown.
+    pub fn address(&self, name: &str, tag: Option<AddressTag>) -> Result<Option<FieldElement>> {
+        let found = self.lookup(|e| {
+            e.name.eq_ignore_ascii_case(name) && tag.map_or(true, |tag| e.tags.contains(&tag))
+        });
+
+        let Some(first) = found.first() else { return Ok(None) };
+        if found.iter().any(|e| e.address != first.address) {
+            bail!("`{name}` is on several chains, the chain of the profile must be set to use it");
+        }
+
+        Ok(Some(first.address))
+    }
+
+    /// Returns the name of `address`, if it's in the address book.
+    pub fn name(&self, address: FieldElement) -> Option<&str> {
+        let found = self.lookup(|e| e.address == address);
+        let name = found.first()?.name.as_str();
+
+        // a name is only shown if it's unambiguous
+        found.iter().all(|e| e.name.eq_ignore_ascii_case(name)).then_some(name)
+    }
+
+    /// The entries on the chain that match `filter`, keeping only the most specific ones: the
+    /// user's entries of the chain shadow those of every chain, which shadow the built-in ones.
+    fn lookup(&self, filter: impl Fn(&AddressEntry) -> bool) -> Vec<&AddressEntry> {
+        let user = self.entries.iter().filter(|e| e.is_on(self.chain)).map(|e| {
+            let rank = if self.chain.is_some() && e.chain.is_some() { 0 } else { 1 };
+            (rank, e)
+        });
+        let builtin = builtin_entries().iter().filter(|e| e.is_on(self.chain)).map(|e| (2, e));
+
+        let found = user.chain(builtin).filter(|(_, e)| filter(e)).collect::<Vec<_>>();
+        let Some(best) = found.iter().map(|(rank, _)| *rank).min() else { return Vec::new() };
+        found.into_iter().filter(|(rank, _)| *rank == best).map(|(_, e)| e).collect()
+    }
+}
+
+fn builtin_entries() -> &'static [AddressEntry] {
+    static BUILTIN: OnceLock<Vec<AddressEntry>> = OnceLock::new();
+    BUILTIN.get_or_init(AddressBook::builtin)
+}
+
+impl probe_fmt::Names for AddressBook {
+    fn address_name(&self, address: FieldElement) -> Option<&str> {
+        self.name(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, address: FieldElement, chain: Option<FieldElement>) -> AddressEntry {
+        AddressEntry { name: name.to_string(), address, chain, tags: vec![AddressTag::Account] }
+    }
+
+    #[test]
+    fn names_are_scoped_per_chain() {
+        let (mainnet, sepolia) = (felt!("0x1"), felt!("0x2"));
+
+        let mut book = AddressBook::default();
+        book.add(entry("deployer", felt!("0xa"), Some(mainnet))).unwrap();
+        book.add(entry("deployer", felt!("0xb"), Some(sepolia))).unwrap();
+        book.add(entry("treasury", felt!("0xc"), None)).unwrap();
+        assert!(book.add(entry("treasury", felt!("0xd"), None)).unwrap());
+        assert!(book.add(entry("0x123", felt!("0xd"), None)).is_err());
+
+        // the entries of the chain are picked, or those of every chain
+        let book = book.with_chain(Some(sepolia));
+        assert_eq!(book.address("deployer", None).unwrap(), Some(felt!("0xb")));
+        assert_eq!(book.address("TREASURY", None).unwrap(), Some(felt!("0xd")));
+        assert_eq!(book.address("deployer", Some(AddressTag::Token)).unwrap(), None);
+        assert_eq!(book.name(felt!("0xb")), Some("deployer"));
+        assert_eq!(book.name(felt!("0xa")), None);
+
+        // a name on several chains is ambiguous if the chain is unknown
+        let book = book.with_chain(None);
+        assert!(book.address("deployer", None).is_err());
+        assert_eq!(book.address("eth", Some(AddressTag::Token)).unwrap(), Some(AddressBook::ETH));
+        assert_eq!(book.name(AddressBook::STRK), Some("STRK"));
+    }
+
+    #[test]
+    fn save_and_open_entries() {
+        let path =
+            std::env::temp_dir().join(format!("probe-addresses-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut book = AddressBook::open(&path).unwrap();
+        book.add(entry("ETH", felt!("0x123"), Some(felt!("0x1")))).unwrap();
+        book.add(entry("deployer", felt!("0xa"), None)).unwrap();
+        book.save(&path).unwrap();
+
+        let mut book = AddressBook::open(&path).unwrap().with_chain(Some(felt!("0x1")));
+        assert_eq!(book.address("eth", None).unwrap(), Some(felt!("0x123")));
+        assert_eq!(book.entries().count(), 7);
+
+        assert_eq!(book.remove("eth", None), 1);
+        assert_eq!(book.address("eth", None).unwrap(), Some(AddressBook::ETH));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
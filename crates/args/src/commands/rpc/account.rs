@@ -5,5 +5,5 @@ use crate::account::WalletCommands;
 #[derive(Debug, Parser)]
 pub struct AccountArgs {
     #[command(subcommand)]
-    commands: WalletCommands,
+    pub commands: WalletCommands,
 }
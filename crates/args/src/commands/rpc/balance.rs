@@ -1,17 +1,18 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use starknet::core::types::{BlockId, FieldElement};
 
 use crate::opts::starknet::StarknetOptions;
-use crate::parser::{BlockIdParser, TokenAddressParser};
+use crate::parser::{address_parser, BlockIdParser, TokenAddressParser};
 
 #[derive(Debug, Parser)]
 pub struct BalanceArgs {
     /// The address whose balance you want to query.
     #[arg(value_name = "ADDRESS")]
+    #[arg(value_parser = address_parser)]
     pub address: FieldElement,
 
     /// The token you want to query the balance of.
-    #[arg(value_parser = TokenAddressParser)]
+    #[arg(value_parser = TokenAddressParser::default())]
     #[arg(default_value = "STRK")]
     pub token: FieldElement,
 
@@ -19,6 +20,83 @@ pub struct BalanceArgs {
     #[arg(long)]
     pub raw: bool,
 
+    /// Add the token to the token registry if it isn't there yet, so that its symbol and decimals
+    /// aren't fetched again.
+    #[arg(long)]
+    #[arg(conflicts_with = "raw")]
+    pub cache: bool,
+
+    /// The hash of the requested block, or number (height) of the requested block, or a block tag
+    /// (e.g. latest, pending).
+    #[arg(next_line_help = true)]
+    #[arg(short, long = "block")]
+    #[arg(default_value = "pending")]
+    #[arg(value_parser = BlockIdParser)]
+    pub block_id: BlockId,
+
+    /// Sample the balance over a range of blocks starting at this block, instead of reading it at
+    /// a single block.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    #[arg(conflicts_with = "block_id")]
+    #[arg(help_heading = "Range options")]
+    pub from_block: Option<u64>,
+
+    /// The last block of the range. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    #[arg(requires = "from_block")]
+    #[arg(help_heading = "Range options")]
+    pub to_block: Option<u64>,
+
+    /// The number of blocks between two samples. The last block of the range is always sampled.
+    #[arg(long, value_name = "BLOCKS")]
+    #[arg(default_value_t = 1)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(help_heading = "Range options")]
+    pub step: u64,
+
+    /// Flag the samples where the balance changed since the previous one.
+    #[arg(long)]
+    #[arg(requires = "from_block")]
+    #[arg(help_heading = "Range options")]
+    pub changes: bool,
+
+    /// Bisect the blocks between two samples whose balances differ, to also sample the exact
+    /// block where the balance changed.
+    #[arg(long)]
+    #[arg(requires = "from_block")]
+    #[arg(help_heading = "Range options")]
+    pub exact: bool,
+
+    /// The format of the samples.
+    #[arg(long, value_enum)]
+    #[arg(default_value_t = SeriesFormat::Table)]
+    #[arg(help_heading = "Range options")]
+    pub format: SeriesFormat,
+
+    #[command(flatten)]
+    pub starknet: StarknetOptions,
+}
+
+/// The output format of a balance sampled over a range of blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SeriesFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Parser)]
+pub struct PortfolioArgs {
+    /// The address whose balances you want to query.
+    #[arg(value_name = "ADDRESS")]
+    #[arg(value_parser = address_parser)]
+    pub address: FieldElement,
+
+    /// Also show the tokens with a zero balance.
+    #[arg(long)]
+    pub all: bool,
+
     /// The hash of the requested block, or number (height) of the requested block, or a block tag
     /// (e.g. latest, pending).
     #[arg(next_line_help = true)]
@@ -1,12 +1,15 @@
 use clap::Parser;
+use serde_json::Value;
 use starknet::core::types::{BlockId, FieldElement};
 
+use crate::opts::display::{DisplayOptions, JsonDisplay};
 use crate::opts::starknet::StarknetOptions;
-use crate::parser::BlockIdParser;
+use crate::parser::{address_parser, BlockIdParser};
 
 #[derive(Debug, Parser)]
 pub struct CallArgs {
     #[arg(display_order = 1)]
+    #[arg(value_parser = address_parser)]
     pub contract_address: FieldElement,
 
     #[arg(display_order = 2)]
@@ -16,7 +19,17 @@ pub struct CallArgs {
 
     #[arg(display_order = 3)]
     #[arg(help = "The input data for the function call")]
-    pub input: Vec<FieldElement>,
+    #[arg(long_help = "The input data for the function call. If the contract has a Cairo 1 ABI, \
+                       the inputs are given as typed values and encoded accordingly, e.g. a \
+                       `u256` as a single number, a `ByteArray` as a string, and arrays, \
+                       structs and enums JSON-style: '[1,2,3]', '{\"low\": 1, \"high\": 0}', \
+                       '{\"Some\": 5}'. Otherwise, they're passed as raw felts.")]
+    pub input: Vec<String>,
+
+    #[arg(long)]
+    #[arg(display_order = 4)]
+    #[arg(help = "Pass the input as raw felts instead of encoding it against the contract's ABI.")]
+    pub raw_calldata: bool,
 
     #[arg(next_line_help = true)]
     #[arg(display_order = 5)]
@@ -27,6 +40,16 @@ pub struct CallArgs {
                   or a block tag (e.g. latest, pending).")]
     pub block_id: BlockId,
 
+    #[arg(long)]
+    #[arg(display_order = 6)]
+    #[arg(help = "Print the return data as raw felts instead of decoding it against the \
+                  contract's ABI.")]
+    pub raw: bool,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Display options")]
+    pub display: DisplayOptions<JsonDisplay<Value>>,
+
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
     pub starknet: StarknetOptions,
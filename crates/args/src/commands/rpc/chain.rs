@@ -1,6 +1,7 @@
 use clap::Parser;
+use starknet::core::types::SyncStatusType;
 
-use crate::opts::display::DisplayOptions;
+use crate::opts::display::{DisplayOptions, JsonDisplay};
 use crate::opts::starknet::StarknetOptions;
 
 #[derive(Debug, Parser)]
@@ -12,9 +13,10 @@ pub struct ChainIdArgs {
 
 #[derive(Debug, Parser)]
 pub struct SyncingArgs {
-    // #[command(flatten)]
-    // #[command(next_help_heading = "Display options")]
-    // pub display: DisplayOptions,
+    #[command(flatten)]
+    #[command(next_help_heading = "Display options")]
+    pub display: DisplayOptions<JsonDisplay<SyncStatusType>>,
+
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
     pub starknet: StarknetOptions,
@@ -1,31 +1,37 @@
 use clap::Parser;
-use starknet::core::types::{BlockId, FieldElement};
+use starknet::core::types::{BlockId, ContractClass, FieldElement};
 
+use crate::opts::display::{DisplayOptions, JsonDisplay};
 use crate::opts::starknet::StarknetOptions;
-use crate::parser::BlockIdParser;
+use crate::parser::{address_parser, BlockIdParser};
 
 #[derive(Debug, Parser)]
 pub struct ClassArgs {
     #[arg(value_name = "CLASS_HASH")]
     #[arg(help = "The hash of the requested contract class")]
-    hash: FieldElement,
+    pub hash: FieldElement,
 
     #[arg(next_line_help = true)]
     #[arg(default_value = "latest")]
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. latest, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Display options")]
+    pub display: DisplayOptions<JsonDisplay<ContractClass>>,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
 
 #[derive(Debug, Parser)]
 pub struct CodeArgs {
+    #[arg(value_parser = address_parser)]
     #[arg(help = "The address of the contract whose class definition will be returned")]
-    contract_address: FieldElement,
+    pub contract_address: FieldElement,
 
     #[arg(next_line_help = true)]
     #[arg(short, long = "block")]
@@ -33,9 +39,13 @@ pub struct CodeArgs {
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. latest, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Display options")]
+    pub display: DisplayOptions<JsonDisplay<ContractClass>>,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
@@ -1,13 +1,16 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 use starknet::core::types::{BlockId, FieldElement};
 
 use crate::opts::starknet::StarknetOptions;
-use crate::parser::BlockIdParser;
+use crate::parser::{address_parser, BlockIdParser};
 
 #[derive(Debug, Parser)]
 pub struct ContractClassArgs {
+    #[arg(value_parser = address_parser)]
     #[arg(help = "The address of the contract whose class hash will be returned")]
-    contract_address: FieldElement,
+    pub contract_address: FieldElement,
 
     #[arg(next_line_help = true)]
     #[arg(short, long = "block")]
@@ -15,18 +18,33 @@ pub struct ContractClassArgs {
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. latest, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
 
 #[derive(Debug, Parser)]
 pub struct StorageArgs {
-    contract_address: FieldElement,
+    #[arg(value_parser = address_parser)]
+    pub contract_address: FieldElement,
+
+    #[arg(value_name = "INDEX")]
+    #[arg(help = "The storage address, or the path of a variable (e.g. `balances[0x123]`, \
+                  `config.owner`) when --layout is given.")]
+    pub index: String,
+
+    #[arg(long = "type", value_name = "TYPE")]
+    #[arg(help = "Read and decode a value of this Cairo type (e.g. `u256`, `ByteArray`, `(u8, \
+                  bool)`), from as many consecutive slots as it takes.")]
+    pub r#type: Option<String>,
 
-    index: FieldElement,
+    #[arg(long, value_name = "PATH")]
+    #[arg(help = "A JSON file with a `storage` object mapping the storage variables to their \
+                  types, e.g. a Sierra class artifact extended with it. The ABI of the artifact \
+                  resolves the struct and enum types.")]
+    pub layout: Option<PathBuf>,
 
     #[arg(next_line_help = true)]
     #[arg(short, long = "block")]
@@ -34,25 +52,26 @@ pub struct StorageArgs {
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. pending, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
 
 #[derive(Debug, Parser)]
 pub struct NonceArgs {
-    contract_address: FieldElement,
+    #[arg(value_parser = address_parser)]
+    pub contract_address: FieldElement,
 
     #[arg(next_line_help = true)]
     #[arg(default_value = "pending")]
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. pending, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
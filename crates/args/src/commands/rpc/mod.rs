@@ -32,6 +32,10 @@ pub enum RpcCommands {
     #[command(name = "completions", visible_alias = "com")]
     ShellCompletions { shell: Option<Shell> },
 
+    /// Account management utilities
+    #[command(visible_alias = "acc")]
+    Account(AccountArgs),
+
     /// Get the timestamp of a block.
     Age(AgeArgs),
 
@@ -47,6 +51,9 @@ pub enum RpcCommands {
     #[command(visible_alias = "bn")]
     BlockNumber(BlockNumberArgs),
 
+    /// Submit a transaction signed with `sign-tx`.
+    Broadcast(BroadcastArgs),
+
     /// Call a StarkNet function without creating a transaction.
     Call(CallArgs),
 
@@ -67,19 +74,43 @@ pub enum RpcCommands {
     #[command(visible_alias = "cc")]
     ContractClass(ContractClassArgs),
 
+    /// Declare a new Sierra contract class.
+    #[command(visible_alias = "dec")]
+    Declare(DeclareArgs),
+
+    /// Deploy a contract through the Universal Deployer Contract.
+    #[command(visible_alias = "dep")]
+    Deploy(DeployArgs),
+
     /// Returns all events matching the given filter
     ///
     /// Returns all event objects matching the conditions in the provided filter
     #[command(visible_alias = "ev")]
     Events(EventsArgs),
 
+    /// Submit a new transaction, executing one or more calls, to be added to the chain.
+    #[command(visible_alias = "inv")]
+    Invoke(InvokeArgs),
+
+    /// Declare a new legacy (Cairo 0) contract class.
+    #[command(name = "legacy-declare")]
+    LegacyDeclare(LegacyDeclareArgs),
+
     /// Get the latest nonce associated with the address.
     #[command(visible_alias = "n1")]
     Nonce(NonceArgs),
 
+    /// Get the balances of an address in all the known tokens.
+    #[command(visible_alias = "pf")]
+    Portfolio(PortfolioArgs),
+
     /// Perform a raw JSON-RPC request.
     Rpc(RawRpcArgs),
 
+    /// Sign an invoke transaction offline and output it as JSON, to be broadcasted later.
+    #[command(name = "sign-tx")]
+    SignTx(SignTxArgs),
+
     /// Get the information about the result of executing the requested block
     StateUpdate(StateUpdateArgs),
 
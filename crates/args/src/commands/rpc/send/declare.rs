@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::opts::account::WalletOptions;
+use crate::opts::starknet::StarknetOptions;
+use crate::opts::transaction::{TransactionOptions, WaitOptions};
+use crate::utils::fs::canonicalize_path;
+
+#[derive(Debug, Args)]
+pub struct DeclareArgs {
+    #[arg(value_name = "CONTRACT_PATH")]
+    #[arg(value_parser = canonicalize_path)]
+    #[arg(help = "Path to the Sierra contract class artifact.")]
+    pub contract: PathBuf,
+
+    #[arg(long)]
+    #[arg(value_name = "CASM_PATH")]
+    #[arg(value_parser = canonicalize_path)]
+    #[arg(help = "Path to the compiled (CASM) contract class artifact.")]
+    #[arg(long_help = "Path to the compiled (CASM) contract class artifact. If not provided, \
+                       the Sierra class will be compiled locally to compute the compiled class \
+                       hash.")]
+    pub casm: Option<PathBuf>,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Starknet options")]
+    pub starknet: StarknetOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Wallet options")]
+    pub wallet: WalletOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub transaction: TransactionOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub wait: WaitOptions,
+}
+
+#[derive(Debug, Args)]
+pub struct LegacyDeclareArgs {
+    #[arg(value_name = "CONTRACT_PATH")]
+    #[arg(value_parser = canonicalize_path)]
+    #[arg(help = "Path to the legacy (Cairo 0) contract artifact.")]
+    pub contract: PathBuf,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Starknet options")]
+    pub starknet: StarknetOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Wallet options")]
+    pub wallet: WalletOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub transaction: TransactionOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub wait: WaitOptions,
+}
@@ -0,0 +1,43 @@
+use clap::Args;
+use starknet::core::types::FieldElement;
+
+use crate::opts::account::WalletOptions;
+use crate::opts::starknet::StarknetOptions;
+use crate::opts::transaction::{TransactionOptions, WaitOptions};
+
+#[derive(Debug, Args)]
+pub struct DeployArgs {
+    #[arg(value_name = "CLASS_HASH")]
+    #[arg(help = "The hash of the class to deploy.")]
+    pub class_hash: FieldElement,
+
+    #[arg(value_delimiter = ',')]
+    #[arg(help = "Comma seperated constructor arguments e.g., 0x12345,0x69420,...")]
+    pub constructor_calldata: Vec<FieldElement>,
+
+    #[arg(long)]
+    #[arg(
+        help = "The salt used to compute the contract address. Randomly generated if not provided."
+    )]
+    pub salt: Option<FieldElement>,
+
+    #[arg(long)]
+    #[arg(help = "Make the contract address dependent on the deployer account address.")]
+    pub unique: bool,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Starknet options")]
+    pub starknet: StarknetOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Wallet options")]
+    pub wallet: WalletOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub transaction: TransactionOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub wait: WaitOptions,
+}
@@ -1,29 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
 use clap::Args;
-use color_eyre::eyre::bail;
+use color_eyre::eyre::{bail, eyre, Context};
 use color_eyre::Result;
-use starknet::accounts::{Account, Call};
-use starknet::core::types::{FieldElement, InvokeTransactionResult};
+use serde::Deserialize;
+use starknet::accounts::Call;
+use starknet::core::types::FieldElement;
 
 use crate::opts::account::WalletOptions;
 use crate::opts::starknet::StarknetOptions;
-use crate::opts::transaction::TransactionOptions;
-use crate::parser::selector_parser;
+use crate::opts::transaction::{TransactionOptions, WaitOptions};
+use crate::parser::{address_parser, selector_parser};
+
+/// Token used to separate the calls given on the command line.
+const CALL_SEPARATOR: &str = "-";
+
+/// The calls of a transaction, given either on the command line or in a file.
+#[derive(Debug, Clone, Args)]
+pub struct CallsArgs {
+    #[arg(value_name = "CALLS")]
+    #[arg(required_unless_present = "file")]
+    #[arg(help = "The calls to execute, in the form `CONTRACT_ADDRESS SELECTOR [CALLDATA...]`.")]
+    #[arg(long_help = "The calls to execute, in the form `CONTRACT_ADDRESS SELECTOR \
+                       [CALLDATA...]`. Multiple calls are separated by a `-`. The selector can \
+                       be the actual function name or the function selector, and the calldata \
+                       can be comma or space separated. If the target contract has a Cairo 1 \
+                       ABI, the calldata is given as typed values, one per argument (e.g. a \
+                       `u256` as a single number), and encoded accordingly. E.g., '0x123 \
+                       approve 0x456 100 - 0x456 swap 100 0'.")]
+    pub calls: Vec<String>,
+
+    #[arg(long, value_name = "PATH")]
+    #[arg(conflicts_with = "calls")]
+    #[arg(help = "Load the calls from a JSON or TOML file.")]
+    #[arg(long_help = "Load the calls from a JSON or TOML file. The file must contain a `calls` \
+                       array whose entries have a `to`, a `selector` and an optional `calldata` \
+                       field.")]
+    pub file: Option<PathBuf>,
+}
+
+impl CallsArgs {
+    /// Returns the calls to execute, either from the command line or from the calls file, with
+    /// their calldata parsed as raw felts.
+    pub fn to_calls(&self) -> Result<Vec<Call>> {
+        raw_to_calls(self.to_raw_calls()?)
+    }
+
+    /// Same as [`CallsArgs::to_calls`] but leaves the calldata unparsed, so that it can be encoded
+    /// against the target contract's ABI.
+    pub fn to_raw_calls(&self) -> Result<Vec<RawCall>> {
+        match &self.file {
+            Some(path) => read_calls_file(path),
+            None => parse_raw_calls(&self.calls),
+        }
+    }
+}
+
+/// A call whose calldata is kept as given by the user, either raw felts or typed values.
+#[derive(Debug, Clone)]
+pub struct RawCall {
+    pub to: FieldElement,
+    pub selector: FieldElement,
+    pub calldata: Vec<String>,
+}
+
+impl RawCall {
+    /// Parses the calldata as raw felts.
+    pub fn to_call(&self) -> Result<Call> {
+        let calldata = parse_raw_calldata(&self.calldata)?;
+        Ok(Call { to: self.to, selector: self.selector, calldata })
+    }
+}
 
 #[derive(Debug, Args)]
 pub struct InvokeArgs {
-    #[arg(value_name = "CONTRACT_ADDRESS")]
-    pub to: FieldElement,
+    #[command(flatten)]
+    pub calls: CallsArgs,
 
-    #[arg(value_name = "SELECTOR")]
-    #[arg(help = "The function of that contract that you want to call.")]
-    #[arg(long_help = "The function of that contract that you want to call. Can be the actual \
-                       function name or the function selector. E.g., 'foo' or '0x12345678'.")]
-    #[arg(value_parser(selector_parser))]
-    pub selector: FieldElement,
+    #[arg(long)]
+    #[arg(help = "Print the encoded `__execute__` calldata instead of sending the transaction.")]
+    #[arg(long_help = "Print the encoded `__execute__` calldata instead of sending the \
+                       transaction. Unless an RPC endpoint is configured, no node is queried \
+                       and the calldata is taken as raw felts.")]
+    pub dry_run: bool,
+
+    #[arg(long)]
+    #[arg(requires = "dry_run")]
+    #[arg(help = "Encode the calldata for legacy (Cairo 0) accounts.")]
+    pub legacy: bool,
 
-    #[arg(value_delimiter = ',')]
-    #[arg(help = "Comma seperated values e.g., 0x12345,0x69420,...")]
-    pub calldata: Vec<FieldElement>,
+    #[arg(long)]
+    #[arg(
+        help = "Pass the calldata as raw felts instead of encoding it against the contracts' ABI."
+    )]
+    pub raw_calldata: bool,
 
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
@@ -36,26 +107,173 @@ pub struct InvokeArgs {
     #[command(flatten)]
     #[command(next_help_heading = "Transaction options")]
     pub transaction: TransactionOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub wait: WaitOptions,
 }
 
-impl InvokeArgs {
-    pub async fn run(self) -> Result<InvokeTransactionResult> {
-        let InvokeArgs { to, selector, calldata, starknet, wallet, transaction } = self;
+/// Parses calls given as `CONTRACT_ADDRESS SELECTOR [CALLDATA...]` groups separated by `-`.
+pub fn parse_calls<S: AsRef<str>>(args: &[S]) -> Result<Vec<Call>> {
+    raw_to_calls(parse_raw_calls(args)?)
+}
 
-        let Some(wallet) = wallet.build_wallet()? else { bail!("missing wallet") };
+/// Parses raw felts, given either as separate values or comma separated.
+pub fn parse_raw_calldata<S: AsRef<str>>(values: &[S]) -> Result<Vec<FieldElement>> {
+    values
+        .iter()
+        .flat_map(|value| value.as_ref().split(','))
+        .filter(|value| !value.is_empty())
+        .map(|value| FieldElement::from_str(value).map_err(|e| eyre!("{e} in calldata")))
+        .collect()
+}
 
-        let account = wallet.account(starknet.provider()).await?;
+fn raw_to_calls(calls: Vec<RawCall>) -> Result<Vec<Call>> {
+    calls
+        .iter()
+        .enumerate()
+        .map(|(idx, call)| call.to_call().map_err(|e| eyre!("{e} for call {}", idx + 1)))
+        .collect()
+}
 
-        let mut tx = account.execute(vec![Call { to, selector, calldata }]);
+/// Same as [`parse_calls`] but leaves the calldata of each call unparsed.
+pub fn parse_raw_calls<S: AsRef<str>>(args: &[S]) -> Result<Vec<RawCall>> {
+    let args = args.iter().map(AsRef::as_ref).collect::<Vec<_>>();
 
-        if let Some(nonce) = transaction.nonce {
-            tx = tx.nonce(nonce);
-        }
+    let mut calls = Vec::new();
+    for (idx, group) in args.split(|arg| *arg == CALL_SEPARATOR).enumerate() {
+        let idx = idx + 1;
+        let mut values = group.iter();
+
+        let to = values.next().ok_or_else(|| eyre!("missing contract address for call {idx}"))?;
+        let to = address_parser(to).map_err(|e| eyre!("{e} for call {idx} contract address"))?;
+
+        let selector = values.next().ok_or_else(|| eyre!("missing selector for call {idx}"))?;
+        let selector =
+            selector_parser(selector).map_err(|e| eyre!("{e} for call {idx} selector"))?;
+
+        let calldata = values.map(|value| value.to_string()).collect();
+        calls.push(RawCall { to, selector, calldata });
+    }
+
+    Ok(calls)
+}
+
+#[derive(Debug, Deserialize)]
+struct CallsFile {
+    calls: Vec<CallEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CallEntry {
+    to: String,
+    selector: String,
+    #[serde(default)]
+    calldata: Vec<String>,
+}
+
+/// Reads the calls from a JSON or TOML file, based on its extension.
+fn read_calls_file(path: &Path) -> Result<Vec<RawCall>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read calls file {}", path.display()))?;
+
+    let file: CallsFile = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&content)?,
+        Some("toml") => toml::from_str(&content)?,
+        _ => bail!("unsupported calls file {}, expected a .json or .toml file", path.display()),
+    };
+
+    if file.calls.is_empty() {
+        bail!("no calls found in {}", path.display());
+    }
+
+    file.calls
+        .into_iter()
+        .enumerate()
+        .map(|(idx, CallEntry { to, selector, calldata })| {
+            let to = address_parser(&to)
+                .map_err(|e| eyre!("{e} for call {} contract address", idx + 1))?;
+            let selector = selector_parser(&selector)
+                .map_err(|e| eyre!("{e} for call {} selector", idx + 1))?;
+            Ok(RawCall { to, selector, calldata })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::core::utils::get_selector_from_name;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    #[test]
+    fn parse_multicall_args() {
+        let args = "0x123456789 balanceOf 0x987654321 - 0xabc298498723 get_the_owner_of_something \
+                    0x1abdf988,0x9872349 0x19831";
+        let args = args.split_whitespace().collect::<Vec<_>>();
+
+        let calls = parse_calls(&args).unwrap();
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].to, felt!("0x123456789"));
+        assert_eq!(calls[0].selector, get_selector_from_name("balanceOf").unwrap());
+        assert_eq!(calls[0].calldata, vec![felt!("0x987654321")]);
+        assert_eq!(calls[1].to, felt!("0xabc298498723"));
+        assert_eq!(
+            calls[1].selector,
+            get_selector_from_name("get_the_owner_of_something").unwrap()
+        );
+        assert_eq!(
+            calls[1].calldata,
+            vec![felt!("0x1abdf988"), felt!("0x9872349"), felt!("0x19831")]
+        );
+    }
+
+    #[test]
+    fn parse_call_without_selector() {
+        let err = parse_calls(&["0x1", "transfer", "-", "0x2"]).unwrap_err();
+        assert_eq!(err.to_string(), "missing selector for call 2");
+    }
+
+    #[test]
+    fn read_calls_from_files() {
+        let dir = std::env::temp_dir().join(format!("probe-invoke-calls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let json = dir.join("calls.json");
+        std::fs::write(
+            &json,
+            r#"{ "calls": [ { "to": "0x1", "selector": "approve", "calldata": ["0x2", "100"] },
+                           { "to": "0x2", "selector": "0x1234" } ] }"#,
+        )
+        .unwrap();
+
+        let toml = dir.join("calls.toml");
+        std::fs::write(
+            &toml,
+            r#"
+            [[calls]]
+            to = "0x1"
+            selector = "approve"
+            calldata = ["0x2", "100"]
+
+            [[calls]]
+            to = "0x2"
+            selector = "0x1234"
+            "#,
+        )
+        .unwrap();
 
-        if let Some(max_fee) = transaction.max_fee {
-            tx = tx.max_fee(max_fee);
+        for path in [json, toml] {
+            let calls = raw_to_calls(read_calls_file(&path).unwrap()).unwrap();
+            assert_eq!(calls.len(), 2);
+            assert_eq!(calls[0].selector, get_selector_from_name("approve").unwrap());
+            assert_eq!(calls[0].calldata, vec![felt!("0x2"), felt!("100")]);
+            assert_eq!(calls[1].selector, felt!("0x1234"));
+            assert!(calls[1].calldata.is_empty());
         }
 
-        Ok(tx.send().await?)
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
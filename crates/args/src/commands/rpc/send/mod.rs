@@ -1 +1,9 @@
+pub mod declare;
+pub mod deploy;
 pub mod invoke;
+pub mod offline;
+
+pub use declare::*;
+pub use deploy::*;
+pub use invoke::*;
+pub use offline::*;
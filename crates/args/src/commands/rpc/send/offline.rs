@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use starknet::core::types::FieldElement;
+
+use super::CallsArgs;
+use crate::opts::account::WalletOptions;
+use crate::opts::starknet::{ChainId, StarknetOptions};
+use crate::opts::transaction::{TransactionOptions, WaitOptions};
+use crate::parser::address_parser;
+
+#[derive(Debug, Args)]
+pub struct SignTxArgs {
+    #[command(flatten)]
+    pub calls: CallsArgs,
+
+    #[arg(long, value_name = "ADDRESS")]
+    #[arg(value_parser = address_parser)]
+    #[arg(help = "The account sending the transaction. Defaults to the wallet account.")]
+    pub sender: Option<FieldElement>,
+
+    #[arg(long)]
+    #[arg(help = "The chain the transaction is meant for. Defaults to the keystore chain.")]
+    pub chain: Option<ChainId>,
+
+    #[arg(long)]
+    #[arg(help = "Encode the calldata for legacy (Cairo 0) accounts.")]
+    pub legacy: bool,
+
+    #[arg(short, long, value_name = "PATH")]
+    #[arg(help = "Write the signed transaction to a file instead of stdout.")]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Wallet options")]
+    pub wallet: WalletOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Transaction options")]
+    pub transaction: TransactionOptions,
+}
+
+#[derive(Debug, Args)]
+pub struct BroadcastArgs {
+    #[arg(value_name = "PATH")]
+    #[arg(help = "The signed transaction, as emitted by `sign-tx`. Reads from stdin if omitted.")]
+    pub signed_tx: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub wait: WaitOptions,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Starknet options")]
+    pub starknet: StarknetOptions,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{Command, FromArgMatches};
+
+    use super::*;
+    use crate::config::{self, Profile};
+
+    fn parse<T: Args + FromArgMatches>(args: &[&str]) -> Result<T, clap::Error> {
+        let matches = T::augment_args(Command::new("probe")).try_get_matches_from(args)?;
+        T::from_arg_matches(&matches)
+    }
+
+    #[test]
+    fn sign_tx_ignores_the_wait_of_the_profile() {
+        let profile = Profile { wait: Some(true), ..Default::default() };
+
+        config::with_profile(profile, || {
+            assert!(parse::<BroadcastArgs>(&["probe", "tx.json"]).unwrap().wait.wait);
+
+            // a signed transaction isn't sent, so there's no waiting for it
+            let args = ["probe", "0x1", "transfer", "--nonce", "0x1"];
+            assert!(parse::<SignTxArgs>(&args).is_ok());
+            assert!(parse::<SignTxArgs>(&[&args[..], &["--wait"][..]].concat()).is_err());
+        });
+    }
+}
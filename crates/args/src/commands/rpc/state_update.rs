@@ -1,7 +1,7 @@
 use clap::Parser;
-use starknet::core::types::BlockId;
+use starknet::core::types::{BlockId, MaybePendingStateUpdate};
 
-use crate::opts::display::DisplayOptions;
+use crate::opts::display::{DisplayOptions, JsonDisplay};
 use crate::opts::starknet::StarknetOptions;
 use crate::parser::BlockIdParser;
 
@@ -12,12 +12,13 @@ pub struct StateUpdateArgs {
     #[arg(value_parser = BlockIdParser)]
     #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
                   or a block tag (e.g. latest, pending).")]
-    block_id: BlockId,
+    pub block_id: BlockId,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Display options")]
+    pub display: DisplayOptions<JsonDisplay<MaybePendingStateUpdate>>,
 
-    // #[command(flatten)]
-    // #[command(next_help_heading = "Display options")]
-    // display: DisplayOptions,
     #[command(flatten)]
     #[command(next_help_heading = "Starknet options")]
-    starknet: StarknetOptions,
+    pub starknet: StarknetOptions,
 }
@@ -1,7 +1,12 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
-use starknet::core::types::FieldElement;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use starknet::core::types::{BlockId, FieldElement};
+
+use crate::addresses::AddressTag;
+use crate::opts::starknet::StarknetOptions;
+use crate::opts::transaction::DaMode;
+use crate::parser::{address_parser, chain_id_parser, BlockIdParser};
 
 #[derive(Subcommand, Debug)]
 pub enum UtilityCommands {
@@ -48,11 +53,41 @@ pub enum UtilityCommands {
         short_str: String,
     },
 
+    #[command(visible_alias = "tba")]
+    #[command(name = "--to-byte-array")]
+    #[command(about = "Encode a UTF-8 string of any length as a Cairo ByteArray.")]
+    ToByteArray {
+        #[arg(value_name = "STRING")]
+        #[arg(help = "The string to encode. Reads from stdin if omitted.")]
+        string: Option<String>,
+    },
+
+    #[command(visible_alias = "fba")]
+    #[command(name = "--from-byte-array")]
+    #[command(about = "Decode a Cairo ByteArray into a UTF-8 string.")]
+    FromByteArray {
+        #[arg(value_name = "FELTS")]
+        #[arg(help = "The felts of the ByteArray, separated by spaces or commas (e.g. the \
+                      output of `call --raw`). Reads from stdin if omitted.")]
+        felts: Vec<String>,
+    },
+
     #[command(visible_alias = "su")]
     #[command(name = "--split-u256")]
     #[command(about = "Split a uint256 into its low and high components.")]
     SplitU256 { value: String },
 
+    #[command(visible_alias = "conv")]
+    #[command(about = "Convert between integer types and their Cairo encodings.")]
+    Convert {
+        #[command(subcommand)]
+        commands: ConvertCommand,
+
+        #[arg(long, value_enum, global = true, default_value_t)]
+        #[arg(help = "The format of the converted values.")]
+        output: NumberFormat,
+    },
+
     #[command(visible_alias = "kck")]
     #[command(about = "Hash abritrary data using Starknet keccak.")]
     Keccak {
@@ -74,8 +109,25 @@ pub enum UtilityCommands {
         elements: Vec<FieldElement>,
     },
 
+    #[command(visible_alias = "calc")]
+    #[command(about = "Evaluate an arithmetic expression modulo the Stark prime.")]
+    Felt {
+        #[arg(required = true, allow_hyphen_values = true)]
+        #[arg(help = "The expression, e.g. \"'transfer' * 2 + pow(3, 0x10) >> 1\". Operands can \
+                      be hex, decimal or 'short string' literals. Supports + - * / (modular \
+                      inverse), pow(x, e), sqrt(x), comparisons, and the << >> & | ^ operators, \
+                      which apply to the integer value of the felts.")]
+        expression: Vec<String>,
+
+        #[arg(long)]
+        #[arg(help = "Display the result in decimal instead of hex.")]
+        dec: bool,
+    },
+
     #[command(visible_alias = "idx")]
-    #[command(about = "Compute the address of a storage variable.")]
+    #[command(
+        about = "Compute the address of a storage variable, or read its value with --contract."
+    )]
     Index(IndexArgs),
 
     #[command(visible_alias = "ch")]
@@ -107,6 +159,112 @@ pub enum UtilityCommands {
         #[arg(help = "The inputs passed to the constructor")]
         calldata: Vec<FieldElement>,
     },
+
+    #[command(visible_alias = "txh")]
+    #[command(name = "tx-hash")]
+    #[command(about = "Compute the hash of a transaction without a node.")]
+    TxHash {
+        #[command(subcommand)]
+        commands: TxHashCommand,
+    },
+
+    #[command(visible_alias = "dcd")]
+    #[command(about = "Decode the calldata of an account's `__execute__` into the calls it makes.")]
+    DecodeCalldata {
+        #[arg(value_name = "CALLDATA")]
+        #[arg(help = "The calldata, separated by spaces or commas, or as a JSON array (e.g. \
+                      from `tx --json`). Reads from stdin if omitted.")]
+        calldata: Vec<String>,
+    },
+
+    #[command(visible_alias = "sel")]
+    #[command(about = "Compute the selector of a name, or look up the names behind a selector.")]
+    Selector(SelectorArgs),
+
+    #[command(visible_alias = "cfg")]
+    #[command(about = "Show or edit the profiles of the configuration file.")]
+    Config {
+        #[command(subcommand)]
+        commands: ConfigCommand,
+    },
+
+    #[command(about = "Manage the address book, whose names can be used in place of addresses.")]
+    Alias {
+        #[command(subcommand)]
+        commands: AliasCommand,
+    },
+}
+
+/// The subcommands of `config`, which apply to the profile selected with `--profile`.
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Show the values of the profile.")]
+    Show {
+        #[arg(long)]
+        #[arg(help = "Show the whole configuration file instead.")]
+        all: bool,
+    },
+
+    #[command(about = "Set a value of the profile, creating the profile if needed.")]
+    Set {
+        #[arg(
+            help = "One of: rpc-url, chain, keystore, account, fee-token, wait, receipt, output."
+        )]
+        key: String,
+        value: String,
+    },
+
+    #[command(about = "Remove a value from the profile.")]
+    Unset { key: String },
+
+    #[command(about = "Remove the profile.")]
+    Remove,
+
+    #[command(about = "Print the path of the configuration file.")]
+    Path,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommand {
+    #[command(about = "Name an address, replacing the address of the name on the same chain.")]
+    Add {
+        name: String,
+        address: FieldElement,
+
+        #[arg(long, value_name = "CHAIN_ID")]
+        #[arg(value_parser = chain_id_parser)]
+        #[arg(help = "The chain the address is on (e.g. mainnet, sepolia, SN_MAIN). The name \
+                      applies to every chain if omitted.")]
+        chain: Option<FieldElement>,
+
+        #[arg(long = "tag", value_delimiter = ',')]
+        #[arg(help = "What the address is, one or more of: token, account, contract.")]
+        tags: Vec<AddressTag>,
+    },
+
+    #[command(visible_alias = "remove")]
+    #[command(about = "Remove a name from the address book.")]
+    Rm {
+        name: String,
+
+        #[arg(long, value_name = "CHAIN_ID")]
+        #[arg(value_parser = chain_id_parser)]
+        #[arg(help = "Only remove the name on this chain.")]
+        chain: Option<FieldElement>,
+    },
+
+    #[command(visible_alias = "list")]
+    #[command(about = "List the names of the address book.")]
+    Ls {
+        #[arg(long, value_name = "CHAIN_ID")]
+        #[arg(value_parser = chain_id_parser)]
+        #[arg(help = "Only list the names available on this chain.")]
+        chain: Option<FieldElement>,
+
+        #[arg(long)]
+        #[arg(help = "Only list the names with this tag.")]
+        tag: Option<AddressTag>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -144,12 +302,355 @@ pub enum EcdsaCommand {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ConvertCommand {
+    #[command(about = "Join the low and high 128-bit limbs of a u256.")]
+    JoinU256 {
+        #[arg(help = "The low 128 bits, in hex or decimal.")]
+        low: FieldElement,
+
+        #[arg(help = "The high 128 bits, in hex or decimal.")]
+        high: FieldElement,
+    },
+
+    #[command(
+        about = "Interpret a felt as a signed integer, where the values above P/2 are negative."
+    )]
+    ToSigned {
+        #[arg(help = "The felt, in hex or decimal.")]
+        felt: FieldElement,
+    },
+
+    #[command(
+        about = "Encode a signed integer as a felt, the way Cairo's signed integer types do."
+    )]
+    FromSigned {
+        #[arg(allow_hyphen_values = true)]
+        #[arg(help = "The integer, in hex or decimal, e.g. -5 or -0x5.")]
+        value: String,
+
+        #[arg(long)]
+        #[arg(help = "Check that the value fits in the signed integer type with this many bits, \
+                      e.g. 32 for `i32`.")]
+        bits: Option<u32>,
+    },
+
+    #[command(
+        about = "Scale a decimal number (e.g. 1.5) into its fixed-point integer representation."
+    )]
+    ToFixed {
+        #[arg(allow_hyphen_values = true)]
+        #[arg(help = "The decimal number, e.g. 1.5 or -0.25.")]
+        value: String,
+
+        #[arg(long, default_value_t = 18)]
+        #[arg(help = "The number of decimals of the fixed-point representation.")]
+        decimals: u8,
+    },
+
+    #[command(
+        about = "Scale a fixed-point integer, in hex or decimal, back into a decimal number."
+    )]
+    FromFixed {
+        #[arg(allow_hyphen_values = true)]
+        #[arg(help = "The fixed-point integer, in hex or decimal, e.g. 0x19 or -0x19.")]
+        value: String,
+
+        #[arg(long, default_value_t = 18)]
+        #[arg(help = "The number of decimals of the fixed-point representation.")]
+        decimals: u8,
+    },
+}
+
+/// The format of the values output by the conversion utilities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum NumberFormat {
+    #[default]
+    Hex,
+    Dec,
+    /// An object with the values as decimal strings.
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TxHashCommand {
+    #[command(about = "Compute the hash of an INVOKE transaction.")]
+    Invoke(InvokeHashArgs),
+
+    #[command(about = "Compute the hash of a DECLARE transaction.")]
+    Declare(DeclareHashArgs),
+
+    #[command(about = "Compute the hash of a DEPLOY_ACCOUNT transaction.")]
+    DeployAccount(DeployAccountHashArgs),
+
+    #[command(about = "Recompute the hash of a transaction JSON, as printed by `tx --json`, and \
+                       check it against its `transaction_hash`.")]
+    Verify {
+        #[arg(value_name = "PATH")]
+        #[arg(help = "Path to the transaction JSON. Reads from stdin if omitted.")]
+        file: Option<PathBuf>,
+
+        #[arg(long, value_name = "CHAIN_ID")]
+        #[arg(value_parser = chain_id_parser)]
+        #[arg(
+            help = "The chain the transaction was sent to (e.g. sepolia, SN_MAIN or a hex value)."
+        )]
+        chain: FieldElement,
+    },
+}
+
+#[derive(Debug, Parser)]
+pub struct InvokeHashArgs {
+    #[arg(value_name = "SENDER")]
+    #[arg(help = "The address of the account sending the transaction.")]
+    pub sender_address: FieldElement,
+
+    #[arg(help = "The calldata passed to the account's `__execute__`.")]
+    pub calldata: Vec<FieldElement>,
+
+    #[arg(long, value_delimiter = ',')]
+    #[arg(help = "The account deployment data (V3 only).")]
+    pub account_deployment_data: Vec<FieldElement>,
+
+    #[command(flatten)]
+    pub fields: TxHashFields,
+}
+
+#[derive(Debug, Parser)]
+pub struct DeclareHashArgs {
+    #[arg(value_name = "SENDER")]
+    #[arg(help = "The address of the account sending the transaction.")]
+    pub sender_address: FieldElement,
+
+    #[arg(help = "The hash of the Sierra class being declared.")]
+    pub class_hash: FieldElement,
+
+    #[arg(help = "The hash of the compiled (CASM) class.")]
+    pub compiled_class_hash: FieldElement,
+
+    #[arg(long, value_delimiter = ',')]
+    #[arg(help = "The account deployment data (V3 only).")]
+    pub account_deployment_data: Vec<FieldElement>,
+
+    #[command(flatten)]
+    pub fields: TxHashFields,
+}
+
+#[derive(Debug, Parser)]
+pub struct DeployAccountHashArgs {
+    #[arg(help = "The class hash of the account contract.")]
+    pub class_hash: FieldElement,
+
+    #[arg(help = "The salt used to compute the account address.")]
+    pub salt: FieldElement,
+
+    #[arg(help = "The inputs passed to the account constructor.")]
+    pub constructor_calldata: Vec<FieldElement>,
+
+    #[command(flatten)]
+    pub fields: TxHashFields,
+}
+
+/// The fields shared by all transaction types.
+///
+/// A V1 (V2 for `DECLARE`) transaction is hashed when `--max-fee` is given, a V3 one otherwise.
+#[derive(Debug, Args)]
+pub struct TxHashFields {
+    #[arg(long, value_name = "CHAIN_ID")]
+    #[arg(value_parser = chain_id_parser)]
+    #[arg(help = "The chain the transaction is meant for (e.g. sepolia, SN_MAIN or a hex value).")]
+    pub chain: FieldElement,
+
+    #[arg(long, default_value = "0")]
+    #[arg(help = "The transaction nonce.")]
+    pub nonce: FieldElement,
+
+    #[arg(long)]
+    #[arg(conflicts_with_all = [
+        "l1_gas", "l1_gas_price", "tip", "paymaster_data", "nonce_da_mode", "fee_da_mode",
+    ])]
+    #[arg(help = "The max fee of a V1 (or V2 for declare) transaction.")]
+    pub max_fee: Option<FieldElement>,
+
+    #[arg(long, default_value_t = 0)]
+    #[arg(help = "The maximum amount of L1 gas (V3 only).")]
+    pub l1_gas: u64,
+
+    #[arg(long, default_value_t = 0)]
+    #[arg(help = "The maximum price per unit of L1 gas, in FRI (V3 only).")]
+    pub l1_gas_price: u128,
+
+    #[arg(long, default_value_t = 0)]
+    #[arg(help = "The transaction tip (V3 only).")]
+    pub tip: u64,
+
+    #[arg(long, value_delimiter = ',')]
+    #[arg(help = "The paymaster data (V3 only).")]
+    pub paymaster_data: Vec<FieldElement>,
+
+    #[arg(long, value_enum, default_value_t)]
+    #[arg(help = "The data availability mode of the account nonce (V3 only).")]
+    pub nonce_da_mode: DaMode,
+
+    #[arg(long, value_enum, default_value_t)]
+    #[arg(help = "The data availability mode of the fee (V3 only).")]
+    pub fee_da_mode: DaMode,
+
+    #[arg(long)]
+    #[arg(help = "Compute the hash of a query-only version of the transaction (e.g. for fee \
+                  estimation).")]
+    pub query: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct SelectorArgs {
+    #[arg(value_name = "NAME_OR_SELECTOR")]
+    #[arg(required_unless_present_any = ["import", "import_class", "import_contract"])]
+    #[arg(help = "A function or event name to compute the selector of, or a selector to look up \
+                  in the local selector database.")]
+    pub value: Option<String>,
+
+    #[arg(long, value_name = "PATH")]
+    #[arg(help = "Import the function and event names found in the ABI of a contract artifact.")]
+    pub import: Option<PathBuf>,
+
+    #[arg(long, value_name = "CLASS_HASH")]
+    #[arg(help = "Import the function and event names found in the ABI of a declared class.")]
+    pub import_class: Option<FieldElement>,
+
+    #[arg(long, value_name = "ADDRESS")]
+    #[arg(value_parser = address_parser)]
+    #[arg(help = "Import the function and event names found in the ABI of a deployed contract.")]
+    pub import_contract: Option<FieldElement>,
+
+    #[command(flatten)]
+    pub starknet: StarknetOptions,
+}
+
+impl SelectorArgs {
+    /// Whether any ABI should be imported into the selector database.
+    pub fn has_imports(&self) -> bool {
+        self.import.is_some() || self.import_class.is_some() || self.import_contract.is_some()
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct IndexArgs {
     #[arg(value_name = "VARIABLE_NAME")]
     #[arg(help = "The storage variable name.")]
     pub var_name: String,
 
-    #[arg(help = "The storage keys")]
-    pub keys: Vec<FieldElement>,
+    #[arg(allow_negative_numbers = true)]
+    #[arg(help = "The keys of the (nested) maps, or the indexes of a `Vec`, one per level. A \
+                  key is a felt or a \"short string\" unless its type is given with \
+                  `--key-type`, e.g. a `u256` as a single number or a tuple as `(a, b)`.")]
+    pub keys: Vec<String>,
+
+    #[arg(long = "key-type", value_name = "TYPE")]
+    #[arg(help = "The Cairo type of a key, e.g. `u256` or `(ContractAddress, u8)`. Given once \
+                  per key, in order; the keys without a type are `felt252`.")]
+    pub key_types: Vec<String>,
+
+    #[arg(long = "component", value_name = "NAME")]
+    #[arg(help = "The storage nodes containing the variable, outermost first, e.g. the name of \
+                  a component's `#[substorage(v1)]` field. Components embedded with \
+                  `#[substorage(v0)]` share the storage of the contract and need no prefix.")]
+    pub components: Vec<String>,
+
+    #[arg(long = "list", value_name = "INDEX")]
+    #[arg(help = "The index of an item of the alexandria `List` at the computed address.")]
+    pub list_index: Option<u32>,
+
+    #[arg(long, default_value_t = 1, requires = "list_index")]
+    #[arg(help = "The number of slots taken by each item of the `List`.")]
+    pub item_size: u8,
+
+    #[arg(long, default_value_t = 0)]
+    #[arg(help = "The offset to add to the computed address, e.g. to get to a member of a \
+                  struct, whose members are stored in consecutive slots.")]
+    pub offset: u64,
+
+    #[arg(long)]
+    #[arg(value_parser = address_parser)]
+    #[arg(help = "Read the value at the computed address in the storage of this contract.")]
+    pub contract: Option<FieldElement>,
+
+    #[arg(next_line_help = true)]
+    #[arg(short, long = "block")]
+    #[arg(default_value = "pending")]
+    #[arg(value_parser = BlockIdParser)]
+    #[arg(help = "The hash of the requested block, or number (height) of the requested block, \
+                  or a block tag (e.g. latest, pending).")]
+    pub block_id: BlockId,
+
+    #[command(flatten)]
+    #[command(next_help_heading = "Starknet options")]
+    pub starknet: StarknetOptions,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use starknet::macros::short_string;
+
+    use super::*;
+
+    #[test]
+    fn parse_invoke_hash_args() {
+        let args = InvokeHashArgs::parse_from([
+            "invoke",
+            "0x1234",
+            "1",
+            "2",
+            "3",
+            "--chain",
+            "sepolia",
+            "--nonce",
+            "5",
+            "--l1-gas",
+            "100000",
+            "--fee-da-mode",
+            "l2",
+        ]);
+
+        assert_eq!(args.sender_address, FieldElement::from(0x1234u32));
+        assert_eq!(args.calldata.len(), 3);
+        assert_eq!(args.fields.chain, short_string!("SN_SEPOLIA"));
+        assert_eq!(args.fields.max_fee, None);
+        assert_eq!(args.fields.l1_gas, 100000);
+        assert_eq!(args.fields.nonce_da_mode, DaMode::L1);
+        assert_eq!(args.fields.fee_da_mode, DaMode::L2);
+    }
+
+    #[test]
+    fn max_fee_conflicts_with_v3_fields() {
+        let res = InvokeHashArgs::try_parse_from([
+            "invoke",
+            "0x1234",
+            "--chain",
+            "SN_MAIN",
+            "--max-fee",
+            "0x100",
+            "--tip",
+            "1",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn convert_negative_values() {
+        #[derive(Debug, Parser)]
+        struct Convert {
+            #[command(subcommand)]
+            command: ConvertCommand,
+        }
+
+        let args = Convert::parse_from(["convert", "to-fixed", "-0.25", "--decimals", "2"]);
+        assert!(matches!(args.command, ConvertCommand::ToFixed { value, .. } if value == "-0.25"));
+
+        let args = Convert::parse_from(["convert", "from-fixed", "-0x19"]);
+        assert!(
+            matches!(args.command, ConvertCommand::FromFixed { value, .. } if value == "-0x19")
+        );
+    }
 }
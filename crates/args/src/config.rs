@@ -0,0 +1,431 @@
+//! The configuration file, `~/.config/probe/config.toml`, whose named profiles hold the default
+//! values of the command line options:
+//!
+//! ```toml
+//! [profiles.default]
+//! rpc-url = "http://localhost:5050/"
+//!
+//! [profiles.sepolia]
+//! rpc-url = "https://free-rpc.nethermind.io/sepolia-juno/v0_7"
+//! chain = "sepolia"
+//! keystore = "~/.starknet/keystore/sepolia/deployer.json"
+//! account = "0x4b3f4ba8c00a02b66142a4b1dd41a4dfab4f92650922a3280977b0f03c75ee1"
+//! fee-token = "strk"
+//! wait = true
+//! output = "json"
+//! ```
+//!
+//! The profile is selected with `--profile` or `PROBE_PROFILE`, and is otherwise the `default`
+//! one. An option is taken from the command line first, then from its environment variable, then
+//! from the profile, and finally falls back to its default value.
+//!
+//! The files probe keeps its data in, i.e. the address book, the selector database and the token
+//! registry, are stored next to the configuration file.
+
+#[cfg(test)]
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use color_eyre::eyre::{bail, ensure, eyre, Context};
+use color_eyre::Result;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+
+use crate::opts::starknet::ChainId;
+use crate::opts::transaction::FeeToken;
+
+/// The directory of the configuration file and of the data files, relative to the home directory.
+pub const CONFIG_DIR: &str = ".config/probe";
+
+/// The name of the configuration file, in the configuration directory.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// The profile used when none is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// The default values of a profile. Unset values fall back to the defaults of the options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    /// The Starknet JSON-RPC endpoint.
+    #[serde(default, with = "from_str", skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<Url>,
+    /// The chain of the accounts, used to find their keystores.
+    #[serde(default, with = "from_str", skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainId>,
+    /// The keystore file, or the folder of keystores, to sign transactions with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<PathBuf>,
+    /// The account contract to send transactions from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<FieldElement>,
+    /// The token used to pay for the transaction fees.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_token: Option<FeeToken>,
+    /// Whether to wait for the transactions to be executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<bool>,
+    /// Whether to output the receipt of the transactions that were waited for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<bool>,
+    /// The format of the outputs that can be displayed as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl Config {
+    /// Opens the configuration file at `path`. The file doesn't have to exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let config = read(path.as_ref(), "config file", |s| toml::from_str(s))?;
+        Ok(config.unwrap_or_default())
+    }
+
+    /// The default location of the configuration file, i.e. `~/.config/probe/config.toml`.
+    pub fn default_path() -> PathBuf {
+        path(CONFIG_FILE)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        write(path.as_ref(), &toml::to_string_pretty(self)?)
+    }
+
+    /// Edits the profiles of the configuration file at `path` as a TOML table. The file isn't
+    /// validated as a whole, so that an invalid one can still be fixed.
+    pub fn edit(
+        path: impl AsRef<Path>,
+        edit: impl FnOnce(&mut toml::Table) -> Result<()>,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let mut config =
+            read(path, "config file", |s| s.parse::<toml::Table>())?.unwrap_or_default();
+
+        let profiles = config.entry("profiles").or_insert_with(|| toml::Table::new().into());
+        let Some(profiles) = profiles.as_table_mut() else {
+            bail!("invalid config file {}: `profiles` must be a table", path.display())
+        };
+        edit(profiles)?;
+
+        write(path, &toml::to_string_pretty(&config)?)
+    }
+}
+
+/// The default location of `file` in the configuration directory, i.e. `~/.config/probe/<file>`.
+pub fn path(file: &str) -> PathBuf {
+    home::home_dir().unwrap_or_default().join(CONFIG_DIR).join(file)
+}
+
+/// Reads the file at `path` and parses its content with `parse`, or returns `None` if the file
+/// doesn't exist yet. `what` names the file in the error if it's invalid.
+pub(crate) fn read<T, E>(
+    path: &Path,
+    what: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<Option<T>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse(&content).map(Some).with_context(|| format!("invalid {what} {}", path.display()))
+}
+
+/// Writes `content` to the file at `path`, creating its directory if needed.
+pub(crate) fn write(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create directory {}", dir.display()))?;
+    }
+
+    fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+}
+
+impl Profile {
+    /// The keys of the profile values, as written in the configuration file.
+    pub const KEYS: &'static [&'static str] =
+        &["rpc-url", "chain", "keystore", "account", "fee-token", "wait", "receipt", "output"];
+
+    /// Sets the value of `key` in a profile of the configuration file, checking it the same way as
+    /// when the file is loaded.
+    pub fn set(profile: &mut toml::Table, key: &str, value: &str) -> Result<()> {
+        Self::check_key(key)?;
+
+        let value = match value.parse::<bool>() {
+            Ok(value) => toml::Value::Boolean(value),
+            Err(_) => toml::Value::String(value.to_string()),
+        };
+
+        let checked = toml::Table::from_iter([(key.to_string(), value.clone())]);
+        toml::Value::Table(checked)
+            .try_into::<Profile>()
+            .with_context(|| format!("invalid value for `{key}`"))?;
+
+        profile.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes the value of `key` from a profile of the configuration file, so that the option
+    /// falls back to its default. Unknown keys can be removed too, to fix an invalid file.
+    pub fn unset(profile: &mut toml::Table, key: &str) -> Result<()> {
+        if profile.remove(key).is_none() {
+            Self::check_key(key)?;
+        }
+        Ok(())
+    }
+
+    /// The keystore path, with the leading `~` expanded to the home directory.
+    pub fn keystore_path(&self) -> Option<PathBuf> {
+        let path = self.keystore.as_ref()?.to_str()?;
+        Some(PathBuf::from(shellexpand::tilde(path).as_ref()))
+    }
+
+    fn check_key(key: &str) -> Result<()> {
+        if !Self::KEYS.contains(&key) {
+            bail!("unknown key `{key}`, expected one of: {}", Self::KEYS.join(", "));
+        }
+        Ok(())
+    }
+}
+
+/// The profile selected with `--profile` or `PROBE_PROFILE`, if any.
+static SELECTED: OnceLock<Option<String>> = OnceLock::new();
+
+/// Selects the profile that provides the defaults of the options, and the chain of the address
+/// book. The configuration file is only loaded when the profile is first used.
+pub fn select_profile(name: Option<String>) {
+    let selected = SELECTED.set(name);
+    debug_assert!(selected.is_ok(), "the profile can only be selected once");
+}
+
+/// Whether the profile was selected, i.e. whether the command line was parsed for it.
+pub fn is_profile_selected() -> bool {
+    SELECTED.get().is_some()
+}
+
+/// The configuration file at the default path, which is only read once.
+pub fn config() -> Result<&'static Config> {
+    static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+    let config =
+        CONFIG.get_or_init(|| Config::open(Config::default_path()).map_err(|e| format!("{e:#}")));
+    config.as_ref().map_err(|e| eyre!("{e}"))
+}
+
+/// The name of the active profile.
+pub fn profile_name() -> &'static str {
+    SELECTED.get().and_then(Option::as_deref).unwrap_or(DEFAULT_PROFILE)
+}
+
+/// The active profile, which is empty until the profile is selected, or if it doesn't exist or
+/// the configuration file is invalid.
+pub fn profile() -> &'static Profile {
+    static EMPTY: Profile = Profile {
+        rpc_url: None,
+        chain: None,
+        keystore: None,
+        account: None,
+        fee_token: None,
+        wait: None,
+        receipt: None,
+        output: None,
+    };
+
+    if let Some(profile) = test_profile() {
+        return profile;
+    }
+
+    if !is_profile_selected() {
+        return &EMPTY;
+    }
+
+    let active = config().ok().and_then(|config| config.profiles.get(profile_name()));
+    active.unwrap_or(&EMPTY)
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_PROFILE: Cell<Option<&'static Profile>> = const { Cell::new(None) };
+}
+
+/// The profile set with [`with_profile`] in the tests.
+#[cfg(test)]
+fn test_profile() -> Option<&'static Profile> {
+    TEST_PROFILE.with(Cell::get)
+}
+
+#[cfg(not(test))]
+fn test_profile() -> Option<&'static Profile> {
+    None
+}
+
+/// Runs `f` with `profile` as the active profile of the current thread.
+#[cfg(test)]
+pub(crate) fn with_profile<T>(profile: Profile, f: impl FnOnce() -> T) -> T {
+    let profile: &'static Profile = Box::leak(Box::new(profile));
+    let previous = TEST_PROFILE.with(|active| active.replace(Some(profile)));
+    let result = f();
+    TEST_PROFILE.with(|active| active.set(previous));
+    result
+}
+
+/// Fails if the configuration file is invalid, or if the profile that was explicitly selected
+/// doesn't exist.
+pub fn ensure_profile() -> Result<()> {
+    let config = config()?;
+    if let Some(Some(name)) = SELECTED.get() {
+        ensure!(
+            config.profiles.contains_key(name),
+            "profile `{name}` not found in {}",
+            Config::default_path().display()
+        );
+    }
+    Ok(())
+}
+
+/// (De)serializes optional values through their `FromStr` and `Display` implementations.
+mod from_str {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: serde::Serializer,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map(Some).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    #[test]
+    fn parse_config_file() {
+        let config: Config = toml::from_str(
+            r#"
+            [profiles.default]
+            rpc-url = "http://localhost:5050/"
+
+            [profiles.sepolia]
+            rpc-url = "https://free-rpc.nethermind.io/sepolia-juno/v0_7"
+            chain = "sepolia"
+            account = "0x123"
+            fee-token = "strk"
+            wait = true
+            output = "json"
+            "#,
+        )
+        .unwrap();
+
+        let sepolia = &config.profiles["sepolia"];
+        assert_eq!(sepolia.chain, Some(ChainId::Sepolia));
+        assert_eq!(sepolia.account, Some(felt!("0x123")));
+        assert_eq!(sepolia.fee_token, Some(FeeToken::Strk));
+        assert_eq!(sepolia.wait, Some(true));
+        assert_eq!(sepolia.receipt, None);
+        assert_eq!(sepolia.output, Some(OutputFormat::Json));
+
+        // the file is written back the same way
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&content).unwrap(), config);
+
+        assert!(toml::from_str::<Config>("[profiles.default]\nrpc = \"x\"").is_err());
+    }
+
+    #[test]
+    fn edit_profile() {
+        let mut table = toml::Table::new();
+        let parse = |table: &toml::Table| toml::Value::Table(table.clone()).try_into::<Profile>();
+
+        Profile::set(&mut table, "rpc-url", "http://localhost:5050").unwrap();
+        Profile::set(&mut table, "fee-token", "strk").unwrap();
+        Profile::set(&mut table, "wait", "true").unwrap();
+        let profile = parse(&table).unwrap();
+        assert_eq!(profile.rpc_url, Some(Url::parse("http://localhost:5050/").unwrap()));
+        assert_eq!(profile.fee_token, Some(FeeToken::Strk));
+        assert_eq!(profile.wait, Some(true));
+
+        Profile::unset(&mut table, "wait").unwrap();
+        assert_eq!(parse(&table).unwrap().wait, None);
+
+        // invalid values leave the profile untouched
+        assert!(Profile::set(&mut table, "rpc-url", "not a url").is_err());
+        assert!(Profile::set(&mut table, "wait", "maybe").is_err());
+        assert!(Profile::set(&mut table, "unknown", "1").is_err());
+        assert_eq!(parse(&table).unwrap(), Profile { wait: None, ..profile });
+
+        // a key that isn't known can still be removed from an invalid profile
+        table.insert("rpc".to_string(), "x".into());
+        assert!(parse(&table).is_err());
+        Profile::unset(&mut table, "rpc").unwrap();
+        assert!(Profile::unset(&mut table, "rpc").is_err());
+        assert!(parse(&table).is_ok());
+    }
+
+    #[test]
+    fn fix_invalid_config_file() {
+        let path = std::env::temp_dir().join(format!("probe-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "[profiles.default]\nrpc = \"x\"\n\n[profiles.other]\nwait = 1\n")
+            .unwrap();
+        assert!(Config::open(&path).is_err());
+
+        Config::edit(&path, |profiles| {
+            let default = profiles.get_mut("default").and_then(toml::Value::as_table_mut).unwrap();
+            Profile::unset(default, "rpc")?;
+            Profile::set(default, "wait", "true")
+        })
+        .unwrap();
+
+        // the other profiles are kept as they are
+        assert!(Config::open(&path).is_err());
+        Config::edit(&path, |profiles| {
+            let other = profiles.get_mut("other").and_then(toml::Value::as_table_mut).unwrap();
+            Profile::unset(other, "wait")
+        })
+        .unwrap();
+
+        let config = Config::open(&path).unwrap();
+        assert_eq!(config.profiles["default"].wait, Some(true));
+        assert_eq!(config.profiles["other"], Profile::default());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
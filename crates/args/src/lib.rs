@@ -1,8 +1,11 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 pub mod account;
+pub mod addresses;
 pub mod commands;
-// pub mod fmt;
+pub mod config;
 pub mod opts;
 pub mod parser;
-mod utils;
+pub mod selectors;
+pub mod tokens;
+pub mod utils;
@@ -10,9 +10,11 @@ use inquire::{CustomType, Password, Select};
 use starknet::core::types::FieldElement;
 use walkdir::WalkDir;
 
-use self::utils::get_from_keystore;
-use super::starknet::ChainId;
+use self::utils::{get_from_keystore, get_main_keystore_dir, keystore_chains, OTHER_CHAIN_DIR};
+use crate::account::deployment::UNDEPLOYED_DIR;
 use crate::account::simple_account::SimpleWallet;
+use crate::config;
+use crate::parser::address_parser;
 
 #[derive(Debug, Clone, Args, Default)]
 #[command(group(ArgGroup::new("wallet-method").args(["private_key", "keystore_path"])))]
@@ -33,6 +35,7 @@ pub struct WalletOptions {
 
     #[arg(long)]
     #[arg(value_name = "FROM")]
+    #[arg(value_parser = address_parser)]
     #[arg(requires = "wallet-method")]
     #[arg(help_heading = "Wallet options - RAW")]
     #[arg(help = "Account contract to initiate the transaction from.")]
@@ -84,19 +87,27 @@ impl WalletOptions {
 
             Some(SimpleWallet::new(account, private_key, None))
         } else {
-            let chain = Select::new("Select chain id", [ChainId::options(), &["OTHER"]].concat())
-                .prompt()?;
+            let chain = match &config::profile().chain {
+                Some(chain) => chain.to_string(),
+                None => {
+                    let chains = keystore_chains(get_main_keystore_dir());
+                    let mut options = chains.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    options.push(OTHER_CHAIN_DIR.to_string());
+                    Select::new("Select chain id", options).prompt()?
+                }
+            };
 
             let mut keystores_path: Vec<String> = Vec::new();
 
-            let path_str = format!("~/.starknet/keystore/{chain}");
-            let path = PathBuf::from(shellexpand::tilde(&path_str).as_ref());
+            let path = get_main_keystore_dir().join(&chain);
 
             if !path.exists() {
                 return Err(eyre!("Keystore directory for chain {chain} doesn't exist."));
             }
 
-            for entry in WalkDir::new(path) {
+            for entry in
+                WalkDir::new(path).into_iter().filter_entry(|e| e.file_name() != UNDEPLOYED_DIR)
+            {
                 let file = entry?;
                 if file.file_type().is_file() {
                     keystores_path.push(file.into_path().to_str().unwrap().to_string());
@@ -120,12 +131,21 @@ impl WalletOptions {
 
     pub fn keystore(&self) -> Result<Option<SimpleWallet>> {
         get_from_keystore(
-            self.account,
-            self.keystore_path.as_ref(),
+            self.account.or(config::profile().account),
+            self.keystore_path().as_ref(),
             self.keystore_password.as_ref(),
             self.keystore_password_file.as_ref(),
         )
     }
+
+    /// The keystore from the command line or the environment, then from the profile unless the
+    /// wallet is given another way.
+    pub fn keystore_path(&self) -> Option<PathBuf> {
+        if self.keystore_path.is_some() || self.private_key.is_some() || self.interactive {
+            return self.keystore_path.clone();
+        }
+        config::profile().keystore_path()
+    }
 }
 
 #[cfg(test)]
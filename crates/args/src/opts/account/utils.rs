@@ -8,10 +8,15 @@ use inquire::Password;
 use starknet::core::types::FieldElement;
 use starknet_keystore::Keystore;
 
+use crate::account::deployment::UNDEPLOYED_DIR;
 use crate::account::simple_account::SimpleWallet;
+use crate::opts::starknet::ChainId;
 
 pub const KEYSTORE_DIR: &str = ".starknet/keystore";
 
+/// The directory of the keystores whose chain is unknown, inside the keystore directory.
+pub const OTHER_CHAIN_DIR: &str = "OTHER";
+
 pub fn read_json_file<T>(path: impl AsRef<Path>) -> Result<T>
 where
     T: serde::de::DeserializeOwned,
@@ -25,6 +30,29 @@ pub fn get_main_keystore_dir() -> PathBuf {
     home::home_dir().unwrap().join(KEYSTORE_DIR)
 }
 
+/// Returns the known chains, followed by the other chains that have a directory of keystores in
+/// the keystore directory `dir`.
+pub fn keystore_chains(dir: impl AsRef<Path>) -> Vec<ChainId> {
+    let mut chains = ChainId::known().to_vec();
+    let Ok(entries) = fs::read_dir(dir) else { return chains };
+
+    let mut custom = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let chain = name.parse::<ChainId>().ok()?;
+            // only the directories named after their chain hold its keystores
+            (name != OTHER_CHAIN_DIR && chain.to_string() == name).then_some(chain)
+        })
+        .filter(|chain| !chains.contains(chain))
+        .collect::<Vec<_>>();
+
+    custom.sort_by_key(ToString::to_string);
+    chains.append(&mut custom);
+    chains
+}
+
 /// Attempts to find the actual path of the keystore file.
 ///
 /// If the path is a directory then we try to find the first keystore file with the correct
@@ -46,6 +74,8 @@ pub fn find_keystore_file(
         let (_, file) = walkdir::WalkDir::new(path)
             .max_depth(2)
             .into_iter()
+            // accounts that are not yet deployed can't be used to send transactions
+            .filter_entry(|e| e.file_name() != UNDEPLOYED_DIR)
             .filter_map(Result::ok)
             .filter(|e| e.file_type().is_file())
             .filter_map(|e| {
@@ -1,16 +1,19 @@
 use std::marker::PhantomData;
 
-use clap::Args;
+use clap::{ArgAction, Args};
 use color_eyre::eyre::{ensure, eyre};
 use color_eyre::Result;
 use serde::Serialize;
 use serde_json::Value;
 
 use super::RawDisplay;
+use crate::config::{self, OutputFormat};
 
 #[derive(Debug, Args)]
 pub struct JsonDisplay<T: Serialize> {
-    #[arg(short, long)]
+    #[arg(short, long, action = ArgAction::Set, num_args = 0..=1, require_equals = true)]
+    #[arg(default_missing_value = "true")]
+    #[arg(default_value_t = config::profile().output == Some(OutputFormat::Json))]
     #[arg(help = "Display the output in its raw JSON format")]
     json: bool,
 
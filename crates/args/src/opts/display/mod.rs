@@ -37,6 +37,22 @@ where
         }
         Ok(())
     }
+
+    /// Similar to [`print`](Self::print), but the pretty output is produced by `pretty` instead of
+    /// the value itself. Useful when the raw value is an intermediate representation (e.g. JSON)
+    /// of the type that we actually want to prettify.
+    pub fn print_with<F, D>(&self, value: <T as RawDisplay>::Value, pretty: F) -> Result<()>
+    where
+        F: FnOnce() -> D,
+        D: std::fmt::Display,
+    {
+        if self.raw_format.is_raw() {
+            println!("{}", self.raw_format.display_raw(value)?);
+        } else {
+            println!("{}", pretty());
+        }
+        Ok(())
+    }
 }
 
 /// Trait for displaying a value in its intended raw format.
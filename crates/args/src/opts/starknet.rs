@@ -1,31 +1,49 @@
+use std::fmt;
 use std::str::FromStr;
 
 use clap::Args;
 use reqwest::Url;
 use starknet::core::types::FieldElement;
+use starknet::core::utils::{cairo_short_string_to_felt, parse_cairo_short_string};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 
+use crate::config;
+
 #[derive(Debug, Clone, Args)]
 #[command(next_help_heading = "Starknet options")]
 pub struct StarknetOptions {
-    /// The Starknet JSON-RPC endpoint.
+    /// The Starknet JSON-RPC endpoint. Defaults to the one of the profile, or to
+    /// http://localhost:5050/.
     #[arg(long = "rpc", value_name = "URL", env = "STARKNET_RPC_URL")]
-    #[arg(default_value = "http://localhost:5050/")]
-    pub rpc_url: Url,
+    pub rpc_url: Option<Url>,
 }
 
 impl StarknetOptions {
+    pub const DEFAULT_RPC_URL: &'static str = "http://localhost:5050/";
+
+    /// The endpoint from the command line or the environment, then from the profile.
+    pub fn rpc_url(&self) -> Url {
+        self.configured_rpc_url()
+            .unwrap_or_else(|| Url::parse(Self::DEFAULT_RPC_URL).expect("valid url"))
+    }
+
+    /// Same as [`StarknetOptions::rpc_url`], but `None` if no endpoint was given at all.
+    pub fn configured_rpc_url(&self) -> Option<Url> {
+        self.rpc_url.clone().or_else(|| config::profile().rpc_url.clone())
+    }
+
     pub fn provider(&self) -> JsonRpcClient<HttpTransport> {
-        JsonRpcClient::new(HttpTransport::new(self.rpc_url.clone()))
+        JsonRpcClient::new(HttpTransport::new(self.rpc_url()))
     }
 }
 
-#[derive(Debug, Clone, strum::Display, PartialEq, Eq)]
-#[strum(serialize_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ChainId {
     Mainnet,
     Sepolia,
+    /// Any other chain, e.g. a Katana or devnet instance, or an appchain.
+    Custom(FieldElement),
 }
 
 impl ChainId {
@@ -49,38 +67,123 @@ impl ChainId {
         match self {
             Self::Mainnet => Self::SN_MAIN,
             Self::Sepolia => Self::SN_SEPOLIA,
+            Self::Custom(id) => *id,
         }
     }
 
-    pub fn options<'a>() -> &'a [&'static str] {
-        &["Mainnet", "Sepolia"]
+    /// The chains that are known without having to ask the node.
+    pub fn known() -> [ChainId; 2] {
+        [Self::Mainnet, Self::Sepolia]
+    }
+
+    /// The short string of a custom chain id, if it can be used as its name, i.e. if it is made
+    /// of characters that can be used in a directory name and parses back to the same chain.
+    fn short_name(id: FieldElement) -> Option<String> {
+        let name = parse_cairo_short_string(&id).ok()?;
+        let valid = !name.is_empty()
+            && !name.eq_ignore_ascii_case("other")
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        (valid && name.parse::<Self>().ok() == Some(Self::Custom(id))).then_some(name)
     }
 }
 
-impl TryFrom<FieldElement> for ChainId {
-    type Error = InvalidChain;
-    fn try_from(value: FieldElement) -> Result<Self, Self::Error> {
+impl From<FieldElement> for ChainId {
+    fn from(value: FieldElement) -> Self {
         if value == Self::SN_MAIN {
-            Ok(Self::Mainnet)
+            Self::Mainnet
         } else if value == Self::SN_SEPOLIA {
-            Ok(Self::Sepolia)
+            Self::Sepolia
         } else {
-            Err(InvalidChain(format!("{value:#x}")))
+            Self::Custom(value)
         }
     }
 }
 
+/// The chains are displayed as they are named in the keystores, i.e. `mainnet`, `sepolia`, and the
+/// short string or the hex id of the other chains (e.g. `KATANA`).
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "mainnet"),
+            Self::Sepolia => write!(f, "sepolia"),
+            Self::Custom(id) => match Self::short_name(*id) {
+                Some(name) => write!(f, "{name}"),
+                None => write!(f, "{id:#x}"),
+            },
+        }
+    }
+}
+
+/// Parses a known network name, or a chain id either in hex or as a Cairo short string (e.g.
+/// `SN_SEPOLIA` or `KATANA`).
 impl FromStr for ChainId {
     type Err = InvalidChain;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "mainnet" => Ok(Self::Mainnet),
-            "sepolia" => Ok(Self::Sepolia),
-            _ => Err(InvalidChain(s.to_string())),
+            "mainnet" => return Ok(Self::Mainnet),
+            "sepolia" => return Ok(Self::Sepolia),
+            "" => return Err(InvalidChain(s.to_string())),
+            _ => {}
         }
+
+        let id = if s.starts_with("0x") {
+            FieldElement::from_hex_be(s).ok()
+        } else {
+            cairo_short_string_to_felt(s).ok()
+        };
+
+        id.map(Self::from).ok_or_else(|| InvalidChain(s.to_string()))
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 #[error("invalid chain: {0}")]
 pub struct InvalidChain(String);
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::{felt, short_string};
+
+    use super::*;
+
+    #[test]
+    fn parse_chain_ids() {
+        assert_eq!("Mainnet".parse::<ChainId>().unwrap(), ChainId::Mainnet);
+        assert_eq!("SN_SEPOLIA".parse::<ChainId>().unwrap(), ChainId::Sepolia);
+        assert_eq!(
+            format!("{:#x}", ChainId::SN_MAIN).parse::<ChainId>().unwrap(),
+            ChainId::Mainnet
+        );
+
+        let katana = ChainId::Custom(short_string!("KATANA"));
+        assert_eq!("KATANA".parse::<ChainId>().unwrap(), katana);
+        assert_eq!("0x4b4154414e41".parse::<ChainId>().unwrap(), katana);
+
+        assert!("".parse::<ChainId>().is_err());
+        assert!("0xnope".parse::<ChainId>().is_err());
+        assert!("a chain id that is too long to fit in a felt".parse::<ChainId>().is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let chains = [
+            ChainId::Mainnet,
+            ChainId::Sepolia,
+            ChainId::Custom(short_string!("KATANA")),
+            ChainId::Custom(short_string!("SN_GOERLI")),
+            // names that wouldn't parse back, or can't be directory names, are shown in hex
+            ChainId::Custom(short_string!("0x1")),
+            ChainId::Custom(short_string!("a/b")),
+            ChainId::Custom(short_string!("other")),
+            ChainId::Custom(felt!("0x1")),
+        ];
+
+        for chain in chains {
+            assert_eq!(chain.to_string().parse::<ChainId>().unwrap(), chain);
+        }
+
+        assert_eq!(ChainId::Custom(short_string!("KATANA")).to_string(), "KATANA");
+        assert_eq!(ChainId::Custom(short_string!("a/b")).to_string(), "0x612f62");
+    }
+}
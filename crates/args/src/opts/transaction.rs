@@ -1,4 +1,49 @@
-use starknet::core::types::FieldElement;
+use clap::{ArgAction, ValueEnum};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{DataAvailabilityMode, FieldElement};
+
+use crate::config;
+use crate::parser::TokenAddressParser;
+
+/// The token used to pay for the transaction fee.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, strum::Display, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "UPPERCASE")]
+#[serde(rename_all = "lowercase")]
+pub enum FeeToken {
+    /// Pay in ETH, using a V1 transaction
+    #[default]
+    Eth,
+    /// Pay in STRK, using a V3 transaction
+    Strk,
+}
+
+impl FeeToken {
+    pub fn address(&self) -> FieldElement {
+        match self {
+            Self::Eth => TokenAddressParser::ETH,
+            Self::Strk => TokenAddressParser::STRK,
+        }
+    }
+}
+
+/// Data availability mode of the nonce and fee of a V3 transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DaMode {
+    #[default]
+    L1,
+    L2,
+}
+
+impl From<DaMode> for DataAvailabilityMode {
+    fn from(mode: DaMode) -> Self {
+        match mode {
+            DaMode::L1 => DataAvailabilityMode::L1,
+            DaMode::L2 => DataAvailabilityMode::L2,
+        }
+    }
+}
 
 #[cfg_attr(test, derive(clap::Parser))]
 #[cfg_attr(not(test), derive(clap::Args))]
@@ -9,32 +54,114 @@ pub struct TransactionOptions {
     pub nonce: Option<FieldElement>,
 
     #[arg(long)]
+    #[arg(conflicts_with_all = ["l1_gas", "l1_gas_price"])]
     #[arg(help = "The maximal fee that can be charged for including the transaction")]
     pub max_fee: Option<FieldElement>,
 
+    #[arg(long, value_enum, default_value_t = config::profile().fee_token.unwrap_or_default())]
+    #[arg(help = "The token used to pay for the transaction fee")]
+    #[arg(long_help = "The token used to pay for the transaction fee. Paying in STRK sends a V3 \
+                       transaction, whose fee is bounded by the L1 gas options instead of the \
+                       max fee.")]
+    pub fee_token: FeeToken,
+
+    #[arg(long, value_name = "AMOUNT")]
+    #[arg(help = "The maximum amount of L1 gas the transaction can consume (V3 only)")]
+    pub l1_gas: Option<u64>,
+
+    #[arg(long, value_name = "PRICE")]
+    #[arg(help = "The maximum price per unit of L1 gas, in FRI (V3 only)")]
+    pub l1_gas_price: Option<u128>,
+
+    #[arg(long)]
+    #[arg(help = "The tip paid to the sequencer (V3 only)")]
+    pub tip: Option<u64>,
+
+    #[arg(long, value_enum)]
+    #[arg(help = "The data availability mode of the account nonce (V3 only)")]
+    pub nonce_da_mode: Option<DaMode>,
+
+    #[arg(long, value_enum)]
+    #[arg(help = "The data availability mode of the fee (V3 only)")]
+    pub fee_da_mode: Option<DaMode>,
+
     #[arg(long)]
     #[arg(value_delimiter = ',')]
     #[arg(help = "The transaction signature")]
     pub signature: Option<Vec<FieldElement>>,
 
-    #[arg(long)]
+    #[arg(long, value_name = "MULTIPLIER")]
+    #[arg(value_parser = parse_fee_multiplier)]
     #[arg(help = "Specify the fee multiplier of the actual max fee based on the estimated fee")]
-    pub fee_multiplier: Option<FieldElement>,
+    #[arg(long_help = "Specify the fee multiplier of the actual max fee based on the estimated \
+                       fee. For V3 transactions, the L1 gas amount is scaled. Defaults to 1.1 \
+                       for V1 invokes and declarations, as in starknet-rs, and to 1.5 for V3 \
+                       transactions and account deployments.")]
+    pub fee_multiplier: Option<f64>,
 
     #[arg(long)]
     #[arg(help = "Estimate the transaction fee without submitting it to the network")]
     pub estimate: bool,
+}
 
-    #[arg(long)]
+/// Whether to wait for a transaction once it's sent. They are kept apart from the
+/// [`TransactionOptions`] since a transaction signed offline isn't sent.
+#[cfg_attr(test, derive(clap::Parser))]
+#[cfg_attr(not(test), derive(clap::Args))]
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true)]
+    #[arg(default_missing_value = "true")]
+    #[arg(default_value_t = config::profile().wait.unwrap_or_default())]
     #[arg(help = "Wait for the transaction until it gets executed and return the receipt")]
     pub wait: bool,
 
-    #[arg(long)]
+    #[arg(long, action = ArgAction::Set, num_args = 0..=1, require_equals = true)]
+    #[arg(default_missing_value = "true")]
+    #[arg(default_value_t = config::profile().receipt.unwrap_or_default())]
     #[arg(requires = "wait")]
     #[arg(help = "Output the transaction receipt upon waiting for its completion")]
     pub receipt: bool,
 }
 
+impl TransactionOptions {
+    /// The multiplier applied to the estimated fee of the transactions built by probe itself (V3
+    /// transactions and account deployments) if none is specified. The V1 transactions built by
+    /// starknet-rs keep its own default.
+    pub const DEFAULT_FEE_MULTIPLIER: f64 = 1.5;
+
+    /// The fee multiplier of the transactions built by probe itself.
+    pub fn fee_multiplier(&self) -> f64 {
+        self.fee_multiplier.unwrap_or(Self::DEFAULT_FEE_MULTIPLIER)
+    }
+
+    /// Whether the transaction should be sent as a V3 transaction.
+    pub fn is_v3(&self) -> bool {
+        self.fee_token == FeeToken::Strk
+    }
+
+    /// Returns the name of the first V3-only option that has been set.
+    pub fn v3_only_option(&self) -> Option<&'static str> {
+        [
+            ("--l1-gas", self.l1_gas.is_some()),
+            ("--l1-gas-price", self.l1_gas_price.is_some()),
+            ("--tip", self.tip.is_some()),
+            ("--nonce-da-mode", self.nonce_da_mode.is_some()),
+            ("--fee-da-mode", self.fee_da_mode.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(name, set)| set.then_some(name))
+    }
+}
+
+fn parse_fee_multiplier(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(multiplier) if multiplier.is_finite() && multiplier > 0.0 => Ok(multiplier),
+        Ok(_) => Err("the fee multiplier must be a positive number".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -42,7 +169,7 @@ mod tests {
     use clap::CommandFactory;
     use starknet::core::types::FieldElement;
 
-    use super::TransactionOptions;
+    use super::{DaMode, FeeToken, TransactionOptions, WaitOptions};
 
     #[test]
     fn parse_tx_options() {
@@ -78,4 +205,78 @@ mod tests {
             FieldElement::from_str("0x256").unwrap(),
         );
     }
+
+    #[test]
+    fn parse_v3_tx_options() {
+        let opts = <TransactionOptions as clap::Parser>::parse_from([
+            "transaction_options",
+            "--fee-token",
+            "strk",
+            "--l1-gas",
+            "100000",
+            "--l1-gas-price",
+            "100000000000000",
+            "--tip",
+            "10",
+            "--fee-da-mode",
+            "l2",
+        ]);
+
+        assert!(opts.is_v3());
+        assert_eq!(opts.fee_token, FeeToken::Strk);
+        assert_eq!(opts.l1_gas, Some(100000));
+        assert_eq!(opts.l1_gas_price, Some(100000000000000));
+        assert_eq!(opts.tip, Some(10));
+        assert_eq!(opts.nonce_da_mode, None);
+        assert_eq!(opts.fee_da_mode, Some(DaMode::L2));
+        assert_eq!(opts.v3_only_option(), Some("--l1-gas"));
+    }
+
+    #[test]
+    fn wait_can_be_turned_off() {
+        let parse = |args: &[&str]| {
+            let args = ["wait_options"].iter().chain(args);
+            <WaitOptions as clap::Parser>::try_parse_from(args)
+        };
+
+        assert!(!parse(&[]).unwrap().wait);
+        assert!(parse(&["--wait"]).unwrap().wait);
+        assert!(!parse(&["--wait=false"]).unwrap().wait);
+
+        let opts = parse(&["--wait", "--receipt"]).unwrap();
+        assert!(opts.wait && opts.receipt);
+        assert!(parse(&["--receipt"]).is_err());
+    }
+
+    #[test]
+    fn max_fee_conflicts_with_l1_gas() {
+        let res = <TransactionOptions as clap::Parser>::try_parse_from([
+            "transaction_options",
+            "--max-fee",
+            "0x256",
+            "--l1-gas",
+            "100000",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn fee_multiplier_option() {
+        let opts = <TransactionOptions as clap::Parser>::parse_from(["transaction_options"]);
+        assert_eq!(opts.fee_multiplier(), TransactionOptions::DEFAULT_FEE_MULTIPLIER);
+
+        let opts = <TransactionOptions as clap::Parser>::parse_from([
+            "transaction_options",
+            "--fee-multiplier",
+            "2",
+        ]);
+        assert_eq!(opts.fee_multiplier(), 2.0);
+
+        let res = <TransactionOptions as clap::Parser>::try_parse_from([
+            "transaction_options",
+            "--fee-multiplier",
+            "0",
+        ]);
+        assert!(res.is_err());
+    }
 }
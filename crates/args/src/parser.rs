@@ -1,10 +1,15 @@
 use std::str::FromStr;
+use std::sync::Arc;
 
 use clap::builder::{PossibleValue, TypedValueParser};
 use clap::error::{Error, ErrorKind};
+use color_eyre::eyre::bail;
 use starknet::core::types::{BlockId, BlockTag, FieldElement, FromStrError};
 use starknet::core::utils::get_selector_from_name;
 
+use crate::addresses::{AddressBook, AddressTag};
+use crate::opts::starknet::ChainId;
+
 #[derive(Debug, Clone)]
 pub struct BlockIdParser;
 
@@ -39,34 +44,29 @@ impl TypedValueParser for BlockIdParser {
     }
 }
 
-/// Parser for known ERC20 token name to its contract address.
-#[derive(Debug, Clone)]
-pub struct TokenAddressParser;
+/// Parser for a token contract address, or the name of a token in the address book.
+#[derive(Debug, Clone, Default)]
+pub struct TokenAddressParser {
+    /// The address book the names are looked up in, instead of the global one.
+    book: Option<Arc<AddressBook>>,
+}
 
 impl TokenAddressParser {
-    // 0x049D36570D4e46f48e99674bd3fcc84644DdD6b96F7C741B1562B82f9e004dC7
-    pub const ETH: FieldElement = FieldElement::from_mont([
-        4380532846569209554,
-        17839402928228694863,
-        17240401758547432026,
-        418961398025637529,
-    ]);
-
-    // 0x04718f5a0Fc34cC1AF16A1cdee98fFB20C31f5cD61D6Ab07201858f4287c938D
-    pub const STRK: FieldElement = FieldElement::from_mont([
-        16432072983745651214,
-        1325769094487018516,
-        5134018303144032807,
-        468300854463065062,
-    ]);
-
-    // 0x053C91253BC9682c04929cA02ED00b3E423f6710D2ee7e0D5EBB06F3eCF368A8
-    pub const USDC: FieldElement = FieldElement::from_mont([
-        5808361013446951402,
-        13558485962494585092,
-        9528015766451344574,
-        198270530439797869,
-    ]);
+    pub const ETH: FieldElement = AddressBook::ETH;
+    pub const STRK: FieldElement = AddressBook::STRK;
+    pub const USDC: FieldElement = AddressBook::USDC;
+
+    /// A parser that looks the names up in `book` instead of the global address book.
+    pub fn with_book(book: AddressBook) -> Self {
+        Self { book: Some(Arc::new(book)) }
+    }
+
+    fn book(&self) -> color_eyre::Result<&AddressBook> {
+        match &self.book {
+            Some(book) => Ok(book),
+            None => AddressBook::global(),
+        }
+    }
 }
 
 impl TypedValueParser for TokenAddressParser {
@@ -80,34 +80,50 @@ impl TypedValueParser for TokenAddressParser {
     ) -> Result<Self::Value, Error> {
         let value = value.to_str().ok_or(Error::raw(ErrorKind::InvalidUtf8, "invalid utf-8"))?;
 
-        let address = match value.to_lowercase().as_str() {
-            "eth" => Self::ETH,
-            "usdc" => Self::USDC,
-            "strk" => Self::STRK,
-
-            value => FieldElement::from_str(value).map_err(|e| match e {
-                FromStrError::InvalidCharacter => Error::raw(
-                    ErrorKind::InvalidValue,
-                    "value must be an address or one of the known tokens".to_string(),
-                ),
-                FromStrError::OutOfRange => {
-                    Error::raw(ErrorKind::InvalidValue, format!("unknown token address '{e}'"))
-                }
-            })?,
-        };
-
-        Ok(address)
+        let token = self
+            .book()
+            .and_then(|book| book.address(value, Some(AddressTag::Token)))
+            .map_err(|e| Error::raw(ErrorKind::InvalidValue, e))?;
+        if let Some(address) = token {
+            return Ok(address);
+        }
+
+        FieldElement::from_str(value).map_err(|e| match e {
+            FromStrError::InvalidCharacter => Error::raw(
+                ErrorKind::InvalidValue,
+                "value must be an address or one of the known tokens".to_string(),
+            ),
+            FromStrError::OutOfRange => {
+                Error::raw(ErrorKind::InvalidValue, format!("unknown token address '{e}'"))
+            }
+        })
     }
 
     fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
-        Some(Box::new(
-            [PossibleValue::new("ETH"), PossibleValue::new("USDC"), PossibleValue::new("STRK")]
-                .into_iter(),
-        ))
+        // the names must outlive the parser, which only those of the global address book do
+        if self.book.is_some() {
+            return None;
+        }
+
+        let tokens = AddressBook::global().ok()?.entries();
+        let tokens = tokens.filter(|e| e.tags.contains(&AddressTag::Token));
+        Some(Box::new(tokens.map(|e| PossibleValue::new(e.name.as_str()))))
+    }
+}
+
+/// Parses an address from either a hex/decimal value or a name in the address book.
+pub fn address_parser(value: &str) -> color_eyre::Result<FieldElement> {
+    if let Ok(address) = FieldElement::from_str(value) {
+        return Ok(address);
+    }
+
+    match AddressBook::global()?.address(value, None)? {
+        Some(address) => Ok(address),
+        None => bail!("value must be an address or a name in the address book"),
     }
 }
 
-/// Used as clap's value parser for `selector` field in `InvokeArgs`.
+/// Parses a selector from either a function name or a hex/decimal value.
 pub fn selector_parser(selector: &str) -> color_eyre::Result<FieldElement> {
     let value = FieldElement::from_str(selector);
     match value {
@@ -116,6 +132,12 @@ pub fn selector_parser(selector: &str) -> color_eyre::Result<FieldElement> {
     }
 }
 
+/// Parses a chain id from either a known network name (e.g. `sepolia`), a hex value or a Cairo
+/// short string (e.g. `SN_SEPOLIA`).
+pub fn chain_id_parser(value: &str) -> color_eyre::Result<FieldElement> {
+    Ok(ChainId::from_str(value)?.id())
+}
+
 // Expected format for keys : 0x124123,0x14123,0x1342
 // where each array is a key
 pub fn parse_event_keys(value: &str) -> std::result::Result<Vec<FieldElement>, FromStrError> {
@@ -127,11 +149,12 @@ mod tests {
     use clap::builder::TypedValueParser;
     use starknet::core::types::FieldElement;
 
+    use crate::addresses::AddressBook;
     use crate::parser::TokenAddressParser;
 
     #[test]
     fn test_parse_token_name() -> color_eyre::Result<()> {
-        let parser = super::TokenAddressParser;
+        let parser = TokenAddressParser::with_book(AddressBook::default());
         let eth = parser.parse_ref(&clap::Command::new("test"), None, "ETH".as_ref())?;
         let usdc = parser.parse_ref(&clap::Command::new("test"), None, "USDC".as_ref())?;
         let strk = parser.parse_ref(&clap::Command::new("test"), None, "STRK".as_ref())?;
@@ -142,12 +165,10 @@ mod tests {
         assert_eq!(usdc, TokenAddressParser::USDC);
         assert_eq!(strk, TokenAddressParser::STRK);
         assert_eq!(address, FieldElement::from(0x123u16));
-        assert!(
-            random
-                .unwrap_err()
-                .to_string()
-                .contains("value must be an address or one of the known tokens")
-        );
+        assert!(random
+            .unwrap_err()
+            .to_string()
+            .contains("value must be an address or one of the known tokens"));
 
         Ok(())
     }
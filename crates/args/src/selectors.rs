@@ -0,0 +1,252 @@
+//! A local database of function and event names, used to show the names behind the selectors
+//! found in transactions and receipts.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{get_selector_from_name, starknet_keccak};
+
+use crate::config;
+
+/// The name of the database file, in the configuration directory.
+pub const SELECTORS_FILE: &str = "selectors.json";
+
+/// The functions of the common interfaces: ERC20, ERC721, SRC5, accounts and the Universal
+/// Deployer Contract.
+const BUILTIN_FUNCTIONS: &[&str] = &[
+    // ERC20
+    "name",
+    "symbol",
+    "decimals",
+    "total_supply",
+    "totalSupply",
+    "balance_of",
+    "balanceOf",
+    "allowance",
+    "transfer",
+    "transfer_from",
+    "transferFrom",
+    "approve",
+    "increase_allowance",
+    "increaseAllowance",
+    "decrease_allowance",
+    "decreaseAllowance",
+    // ERC721
+    "owner_of",
+    "ownerOf",
+    "get_approved",
+    "getApproved",
+    "is_approved_for_all",
+    "isApprovedForAll",
+    "set_approval_for_all",
+    "setApprovalForAll",
+    "safe_transfer_from",
+    "safeTransferFrom",
+    "token_uri",
+    "tokenURI",
+    // SRC5
+    "supports_interface",
+    "supportsInterface",
+    // accounts
+    "__execute__",
+    "__validate__",
+    "__validate_declare__",
+    "__validate_deploy__",
+    "is_valid_signature",
+    "isValidSignature",
+    "get_public_key",
+    "getPublicKey",
+    "set_public_key",
+    "setPublicKey",
+    // UDC
+    "deploy_contract",
+    "deployContract",
+    // contracts in general
+    "constructor",
+    "upgrade",
+];
+
+/// The events of the common interfaces.
+const BUILTIN_EVENTS: &[&str] = &[
+    // ERC20 and ERC721
+    "Transfer",
+    "Approval",
+    "ApprovalForAll",
+    // accounts
+    "TransactionExecuted",
+    "OwnerAdded",
+    "OwnerRemoved",
+    // UDC
+    "ContractDeployed",
+    // contracts in general
+    "Upgraded",
+];
+
+/// The names added to the database, as stored in its file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Names {
+    #[serde(default)]
+    functions: BTreeSet<String>,
+    #[serde(default)]
+    events: BTreeSet<String>,
+}
+
+/// Maps `sn_keccak` selectors back to the names of functions and events.
+#[derive(Debug, Default)]
+pub struct SelectorDb {
+    /// The names that aren't built in, which are the ones saved to the database file.
+    imported: Names,
+    functions: HashMap<FieldElement, String>,
+    events: HashMap<FieldElement, String>,
+}
+
+impl SelectorDb {
+    /// Creates a database with only the built-in names.
+    pub fn builtin() -> Self {
+        let mut db = Self::default();
+        for name in BUILTIN_FUNCTIONS {
+            db.insert_function(name);
+        }
+        for name in BUILTIN_EVENTS {
+            db.insert_event(name);
+        }
+        db
+    }
+
+    /// Opens the database file at `path`, on top of the built-in names. The file doesn't have to
+    /// exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let mut db = Self::builtin();
+
+        let names =
+            config::read(path.as_ref(), "selector database", |s| serde_json::from_str::<Names>(s))?;
+
+        if let Some(names) = names {
+            for name in &names.functions {
+                db.add_function(name);
+            }
+            for name in &names.events {
+                db.add_event(name);
+            }
+        }
+
+        Ok(db)
+    }
+
+    /// The default location of the database file, i.e. `~/.config/probe/selectors.json`.
+    pub fn default_path() -> PathBuf {
+        config::path(SELECTORS_FILE)
+    }
+
+    /// The database at the default path, which is only read once.
+    pub fn global() -> Result<&'static Self> {
+        static DB: OnceLock<Result<SelectorDb, String>> = OnceLock::new();
+        let db = DB.get_or_init(|| Self::open(Self::default_path()).map_err(|e| format!("{e:#}")));
+        db.as_ref().map_err(|e| eyre!("{e}"))
+    }
+
+    /// Saves the names that were added to the database to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        config::write(path.as_ref(), &serde_json::to_string_pretty(&self.imported)?)
+    }
+
+    /// Adds a function name, returning whether its selector wasn't known yet.
+    pub fn add_function(&mut self, name: &str) -> bool {
+        let added = self.insert_function(name);
+        if added {
+            self.imported.functions.insert(name.to_string());
+        }
+        added
+    }
+
+    /// Adds an event name, returning whether its selector wasn't known yet.
+    pub fn add_event(&mut self, name: &str) -> bool {
+        let added = self.insert_event(name);
+        if added {
+            self.imported.events.insert(name.to_string());
+        }
+        added
+    }
+
+    /// Returns the name of the function with the given selector.
+    pub fn function(&self, selector: FieldElement) -> Option<&str> {
+        self.functions.get(&selector).map(String::as_str)
+    }
+
+    /// Returns the name of the event with the given selector.
+    pub fn event(&self, selector: FieldElement) -> Option<&str> {
+        self.events.get(&selector).map(String::as_str)
+    }
+
+    fn insert_function(&mut self, name: &str) -> bool {
+        // names that aren't ASCII can't be entrypoint names
+        let Ok(selector) = get_selector_from_name(name) else { return false };
+        insert(&mut self.functions, selector, name)
+    }
+
+    fn insert_event(&mut self, name: &str) -> bool {
+        insert(&mut self.events, starknet_keccak(name.as_bytes()), name)
+    }
+}
+
+impl probe_fmt::Names for SelectorDb {
+    fn function_name(&self, selector: FieldElement) -> Option<&str> {
+        self.function(selector)
+    }
+
+    fn event_name(&self, selector: FieldElement) -> Option<&str> {
+        self.event(selector)
+    }
+}
+
+/// Inserts a name unless its selector is already known, in which case the first name is kept.
+fn insert(names: &mut HashMap<FieldElement, String>, selector: FieldElement, name: &str) -> bool {
+    if names.contains_key(&selector) {
+        return false;
+    }
+    names.insert(selector, name.to_string());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    #[test]
+    fn lookup_builtin_names() {
+        let db = SelectorDb::builtin();
+
+        let transfer = felt!("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e");
+        assert_eq!(db.function(transfer), Some("transfer"));
+        let event = felt!("0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9");
+        assert_eq!(db.event(event), Some("Transfer"));
+        assert_eq!(db.function(felt!("0x1234")), None);
+    }
+
+    #[test]
+    fn save_and_open_added_names() {
+        let path =
+            std::env::temp_dir().join(format!("probe-selectors-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut db = SelectorDb::open(&path).unwrap();
+        assert!(db.add_function("mint"));
+        assert!(!db.add_function("transfer"));
+        assert!(db.add_event("Minted"));
+        db.save(&path).unwrap();
+
+        let db = SelectorDb::open(&path).unwrap();
+        assert_eq!(db.function(get_selector_from_name("mint").unwrap()), Some("mint"));
+        assert_eq!(db.event(starknet_keccak(b"Minted")), Some("Minted"));
+        assert_eq!(db.imported.functions.len(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
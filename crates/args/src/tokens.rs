@@ -0,0 +1,131 @@
+//! A registry of ERC20 tokens and their metadata, so that the symbol and decimals of a token only
+//! have to be fetched from the chain once.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use probe_fmt::TokenInfo;
+use starknet::core::types::FieldElement;
+use starknet::macros::felt;
+
+use crate::addresses::AddressBook;
+use crate::config;
+
+/// The name of the token registry file, in the configuration directory.
+pub const TOKENS_FILE: &str = "tokens.json";
+
+/// `SN_MAIN` in ASCII.
+const MAINNET: FieldElement = felt!("0x534e5f4d41494e");
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    /// The tokens whose metadata was fetched from the chain, saved to the registry file.
+    tokens: Vec<TokenInfo>,
+}
+
+impl TokenRegistry {
+    /// The tokens that are always known.
+    pub fn builtin() -> Vec<TokenInfo> {
+        let token = |symbol: &str, address, decimals, chain| TokenInfo {
+            address,
+            symbol: symbol.to_string(),
+            decimals,
+            chain,
+        };
+
+        vec![
+            token("ETH", AddressBook::ETH, 18, None),
+            token("STRK", AddressBook::STRK, 18, None),
+            token("USDC", AddressBook::USDC, 6, Some(MAINNET)),
+            token(
+                "USDT",
+                felt!("0x068f5c6a61780768455de69077e07e89787839bf8166decfbf92b645209c0fb8"),
+                6,
+                Some(MAINNET),
+            ),
+            token(
+                "DAI",
+                felt!("0x05574eb6b8789a91466f902c380d978e472db68170ff82a5b650b95a58ddf4ad"),
+                18,
+                Some(MAINNET),
+            ),
+            token(
+                "WBTC",
+                felt!("0x03fe2b97c1fd336e750087d68b9b867997fd64a2661ff3ca5a7c771641e8e7ac"),
+                8,
+                Some(MAINNET),
+            ),
+        ]
+    }
+
+    /// Opens the token registry file at `path`. The file doesn't have to exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let tokens = config::read(path.as_ref(), "token registry", |s| serde_json::from_str(s))?;
+        Ok(Self { tokens: tokens.unwrap_or_default() })
+    }
+
+    /// The default location of the token registry file, i.e. `~/.config/probe/tokens.json`.
+    pub fn default_path() -> PathBuf {
+        config::path(TOKENS_FILE)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        config::write(path.as_ref(), &serde_json::to_string_pretty(&self.tokens)?)
+    }
+
+    /// Adds a token, replacing the one at the same address on the same chain.
+    pub fn add(&mut self, token: TokenInfo) {
+        let existing =
+            self.tokens.iter_mut().find(|t| t.address == token.address && t.chain == token.chain);
+
+        match existing {
+            Some(existing) => *existing = token,
+            None => self.tokens.push(token),
+        }
+    }
+
+    /// Returns the token at `address` on `chain`, if it's known.
+    pub fn get(&self, chain: FieldElement, address: FieldElement) -> Option<TokenInfo> {
+        self.tokens(chain).into_iter().find(|t| t.address == address)
+    }
+
+    /// The tokens on `chain`, the ones fetched from the chain first.
+    pub fn tokens(&self, chain: FieldElement) -> Vec<TokenInfo> {
+        let fetched = self.tokens.iter().filter(|t| t.is_on(chain));
+        let builtin = Self::builtin().into_iter().filter(|t| {
+            t.is_on(chain) && !fetched.clone().any(|fetched| fetched.address == t.address)
+        });
+
+        fetched.cloned().chain(builtin).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetched_tokens_shadow_the_builtin_ones() {
+        let sepolia = felt!("0x534e5f5345504f4c4941");
+        let usdt = felt!("0x068f5c6a61780768455de69077e07e89787839bf8166decfbf92b645209c0fb8");
+
+        let mut registry = TokenRegistry::default();
+        assert_eq!(registry.get(MAINNET, usdt).unwrap().decimals, 6);
+        assert_eq!(registry.get(sepolia, usdt), None);
+        assert_eq!(registry.get(sepolia, AddressBook::USDC), None);
+        assert_eq!(registry.tokens(sepolia).len(), 2);
+
+        let token = TokenInfo {
+            address: AddressBook::ETH,
+            symbol: "WETH".to_string(),
+            decimals: 18,
+            chain: Some(sepolia),
+        };
+        registry.add(token.clone());
+        registry.add(token.clone());
+
+        assert_eq!(registry.get(sepolia, AddressBook::ETH), Some(token));
+        assert_eq!(registry.get(MAINNET, AddressBook::ETH).unwrap().symbol, "ETH");
+        assert_eq!(registry.tokens(sepolia).len(), 2);
+    }
+}
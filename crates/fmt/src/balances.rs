@@ -0,0 +1,155 @@
+//! The balance of an address sampled over a range of blocks.
+
+use alloy_primitives::U256;
+use prettytable::format::Alignment;
+use prettytable::{Cell, Row, Table};
+use serde_json::{json, Value};
+
+use crate::rpc::format_timestamp;
+use crate::utils::format_erc20_balance;
+use crate::{Tabular, TokenInfo};
+
+/// The balance at a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceSample {
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub balance: U256,
+    /// Whether the balance is different from the one of the previous sample.
+    pub changed: bool,
+}
+
+/// The samples of a balance, ordered by block.
+#[derive(Debug, Clone)]
+pub struct BalanceSeries {
+    pub samples: Vec<BalanceSample>,
+    /// The token, to format the balances with, or `None` to only show the raw balances.
+    pub token: Option<TokenInfo>,
+    /// Whether to flag the samples where the balance changed.
+    pub show_changes: bool,
+}
+
+impl BalanceSeries {
+    /// Builds the series from samples ordered by block, flagging the ones where the balance
+    /// changed.
+    pub fn new(mut samples: Vec<BalanceSample>, token: Option<TokenInfo>) -> Self {
+        let mut previous = None;
+        for sample in &mut samples {
+            sample.changed = previous.is_some_and(|previous| previous != sample.balance);
+            previous = Some(sample.balance);
+        }
+
+        Self { samples, token, show_changes: false }
+    }
+
+    pub fn with_changes(mut self, show_changes: bool) -> Self {
+        self.show_changes = show_changes;
+        self
+    }
+
+    /// The series as CSV, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec!["block_number", "timestamp", "balance"];
+        if self.token.is_some() {
+            header.push("formatted");
+        }
+        if self.show_changes {
+            header.push("changed");
+        }
+
+        let mut lines = vec![header.join(",")];
+        for sample in &self.samples {
+            let mut fields = vec![
+                sample.block_number.to_string(),
+                sample.timestamp.to_string(),
+                format!("{:#x}", sample.balance),
+            ];
+            if let Some(formatted) = self.formatted(sample) {
+                fields.push(formatted);
+            }
+            if self.show_changes {
+                fields.push(sample.changed.to_string());
+            }
+            lines.push(fields.join(","));
+        }
+
+        lines.join("\n")
+    }
+
+    /// The series as a JSON array of samples.
+    pub fn to_json(&self) -> Value {
+        let samples = self.samples.iter().map(|sample| {
+            let mut value = json!({
+                "block_number": sample.block_number,
+                "timestamp": sample.timestamp,
+                "balance": format!("{:#x}", sample.balance),
+            });
+            if let Some(formatted) = self.formatted(sample) {
+                value["formatted"] = json!(formatted);
+            }
+            if self.show_changes {
+                value["changed"] = json!(sample.changed);
+            }
+            value
+        });
+
+        Value::Array(samples.collect())
+    }
+
+    fn formatted(&self, sample: &BalanceSample) -> Option<String> {
+        let token = self.token.as_ref()?;
+        Some(format_erc20_balance(sample.balance, &token.symbol, token.decimals))
+    }
+}
+
+impl Tabular for BalanceSeries {
+    fn build_table(&self, table: &mut Table) {
+        let mut titles = vec!["BLOCK", "TIMESTAMP", "BALANCE"];
+        if self.show_changes {
+            titles.push("CHANGED");
+        }
+        table.set_titles(Row::from(titles));
+
+        for sample in &self.samples {
+            let balance =
+                self.formatted(sample).unwrap_or_else(|| format!("{:#x}", sample.balance));
+
+            let mut cells = vec![
+                Cell::new(&sample.block_number.to_string()),
+                Cell::new(&format_timestamp(sample.timestamp)),
+                Cell::new_align(&balance, Alignment::RIGHT),
+            ];
+            if self.show_changes {
+                cells.push(Cell::new(if sample.changed { "*" } else { "" }));
+            }
+            table.add_row(Row::new(cells));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(block_number: u64, balance: u64) -> BalanceSample {
+        BalanceSample { block_number, timestamp: 0, balance: U256::from(balance), changed: false }
+    }
+
+    #[test]
+    fn flag_changes_and_export() {
+        let samples = vec![sample(10, 5), sample(20, 5), sample(30, 7)];
+        let series = BalanceSeries::new(samples, None).with_changes(true);
+
+        let changed = series.samples.iter().map(|s| s.changed).collect::<Vec<_>>();
+        assert_eq!(changed, [false, false, true]);
+
+        assert_eq!(
+            series.to_csv(),
+            "block_number,timestamp,balance,changed\n10,0,0x5,false\n20,0,0x5,false\n30,0,0x7,true"
+        );
+        assert_eq!(
+            series.to_json()[2],
+            json!({ "block_number": 30, "timestamp": 0, "balance": "0x7", "changed": true })
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Decoding of the calldata of an account's `__execute__` entrypoint into the calls it makes.
+
+use prettytable::{Row, Table};
+use starknet::core::types::FieldElement;
+
+use crate::names::{pretty_address, pretty_function_selector};
+use crate::{Names, NoNames, Pretty, Tabular, WithNames};
+
+/// The layouts used by accounts to serialize the calls of a multicall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalldataLayout {
+    /// `[calls_len, (to, selector, data_offset, data_len)..., calldata_len, calldata...]`, as used
+    /// by Cairo 0 accounts.
+    Legacy,
+    /// The serialization of `Array<Call>`, i.e. `[calls_len, (to, selector, calldata_len,
+    /// calldata...)...]`.
+    Cairo1,
+}
+
+/// A call made by an account through its `__execute__` entrypoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecuteCall {
+    pub to: FieldElement,
+    pub selector: FieldElement,
+    pub calldata: Vec<FieldElement>,
+}
+
+/// The calls encoded in the calldata of an account's `__execute__` entrypoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecuteCalldata {
+    pub layout: CalldataLayout,
+    pub calls: Vec<ExecuteCall>,
+}
+
+impl ExecuteCalldata {
+    /// Decodes `__execute__` calldata in either layout, or returns `None` if the calldata doesn't
+    /// exactly fit any of them.
+    pub fn decode(calldata: &[FieldElement]) -> Option<Self> {
+        if let Some(calls) = decode_legacy(calldata) {
+            return Some(Self { layout: CalldataLayout::Legacy, calls });
+        }
+
+        decode_cairo1(calldata).map(|calls| Self { layout: CalldataLayout::Cairo1, calls })
+    }
+}
+
+fn decode_legacy(calldata: &[FieldElement]) -> Option<Vec<ExecuteCall>> {
+    let (len, rest) = calldata.split_first()?;
+    let headers_len = to_usize(*len)?.checked_mul(4)?;
+    if rest.len() <= headers_len {
+        return None;
+    }
+
+    let (headers, rest) = rest.split_at(headers_len);
+    let (data_len, data) = rest.split_first()?;
+    if to_usize(*data_len)? != data.len() {
+        return None;
+    }
+
+    // the calldata of each call directly follows the one of the previous call
+    let mut offset = 0;
+    let calls = headers
+        .chunks(4)
+        .map(|header| {
+            if to_usize(header[2])? != offset {
+                return None;
+            }
+
+            let end = offset.checked_add(to_usize(header[3])?)?;
+            let calldata = data.get(offset..end)?.to_vec();
+            offset = end;
+
+            Some(ExecuteCall { to: header[0], selector: header[1], calldata })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (offset == data.len()).then_some(calls)
+}
+
+fn decode_cairo1(calldata: &[FieldElement]) -> Option<Vec<ExecuteCall>> {
+    let (len, mut rest) = calldata.split_first()?;
+    let len = to_usize(*len)?;
+    // each call takes at least 3 felts
+    if len.checked_mul(3)? > rest.len() {
+        return None;
+    }
+
+    let mut calls = Vec::with_capacity(len);
+    for _ in 0..len {
+        let [to, selector, data_len, tail @ ..] = rest else { return None };
+        let data_len = to_usize(*data_len)?;
+        if data_len > tail.len() {
+            return None;
+        }
+
+        let (data, tail) = tail.split_at(data_len);
+        calls.push(ExecuteCall { to: *to, selector: *selector, calldata: data.to_vec() });
+        rest = tail;
+    }
+
+    rest.is_empty().then_some(calls)
+}
+
+fn to_usize(value: FieldElement) -> Option<usize> {
+    u64::try_from(value).ok().and_then(|value| usize::try_from(value).ok())
+}
+
+fn add_call_rows(call: &ExecuteCall, table: &mut Table, names: &dyn Names) {
+    table.add_row(Row::from(["To".to_string(), pretty_address(call.to, names)]));
+    table.add_row(Row::from([
+        "Selector".to_string(),
+        pretty_function_selector(call.selector, names),
+    ]));
+    table.add_row(Row::from(["Calldata".to_string(), call.calldata.prettify()]));
+}
+
+fn add_calldata_rows(calldata: &ExecuteCalldata, table: &mut Table, names: &dyn Names) {
+    let layout = match calldata.layout {
+        CalldataLayout::Legacy => "LEGACY",
+        CalldataLayout::Cairo1 => "CAIRO 1",
+    };
+
+    table.add_row(Row::from(["LAYOUT", layout]));
+    table.add_row(Row::from(["CALLS".to_string(), pretty_calls(&calldata.calls, names)]));
+}
+
+impl Tabular for ExecuteCall {
+    fn build_table(&self, table: &mut Table) {
+        add_call_rows(self, table, &NoNames);
+    }
+}
+
+impl Tabular for ExecuteCalldata {
+    fn build_table(&self, table: &mut Table) {
+        add_calldata_rows(self, table, &NoNames);
+    }
+}
+
+impl<N: Names> Tabular for WithNames<'_, ExecuteCalldata, N> {
+    fn build_table(&self, table: &mut Table) {
+        add_calldata_rows(self.value, table, self.names);
+    }
+}
+
+/// Displays the calls as a list of tables.
+pub(crate) fn pretty_calls(calls: &[ExecuteCall], names: &dyn Names) -> String {
+    calls
+        .iter()
+        .map(|call| {
+            let mut table = Table::new();
+            table.set_format(ExecuteCall::format());
+            add_call_rows(call, &mut table, names);
+            table.to_string()
+        })
+        .collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    fn calls() -> Vec<ExecuteCall> {
+        vec![
+            ExecuteCall {
+                to: felt!("0x111"),
+                selector: felt!("0xaaa"),
+                calldata: vec![felt!("1"), felt!("2")],
+            },
+            ExecuteCall { to: felt!("0x222"), selector: felt!("0xbbb"), calldata: vec![] },
+            ExecuteCall {
+                to: felt!("0x333"),
+                selector: felt!("0xccc"),
+                calldata: vec![felt!("3")],
+            },
+        ]
+    }
+
+    #[test]
+    fn decode_legacy_calldata() {
+        let calldata = [
+            "3", "0x111", "0xaaa", "0", "2", "0x222", "0xbbb", "2", "0", "0x333", "0xccc", "2",
+            "1", "3", "1", "2", "3",
+        ]
+        .map(|felt| FieldElement::from_hex_be(felt).unwrap());
+
+        let decoded = ExecuteCalldata::decode(&calldata).unwrap();
+        assert_eq!(decoded.layout, CalldataLayout::Legacy);
+        assert_eq!(decoded.calls, calls());
+    }
+
+    #[test]
+    fn decode_cairo1_calldata() {
+        let calldata = [
+            "3", "0x111", "0xaaa", "2", "1", "2", "0x222", "0xbbb", "0", "0x333", "0xccc", "1", "3",
+        ]
+        .map(|felt| FieldElement::from_hex_be(felt).unwrap());
+
+        let decoded = ExecuteCalldata::decode(&calldata).unwrap();
+        assert_eq!(decoded.layout, CalldataLayout::Cairo1);
+        assert_eq!(decoded.calls, calls());
+    }
+
+    #[test]
+    fn reject_calldata_in_no_layout() {
+        assert!(ExecuteCalldata::decode(&[]).is_none());
+        // one felt missing from the last call
+        let calldata = [felt!("1"), felt!("0x111"), felt!("0xaaa"), felt!("2"), felt!("1")];
+        assert!(ExecuteCalldata::decode(&calldata).is_none());
+        // too many calls for the amount of data
+        assert!(ExecuteCalldata::decode(&[felt!("0xffffffffffff"), felt!("1")]).is_none());
+    }
+}
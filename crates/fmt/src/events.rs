@@ -0,0 +1,67 @@
+use starknet::core::types::FieldElement;
+
+use crate::Names;
+
+/// An event decoded against the ABI of the contract that emitted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    /// The name of the event. For nested events, this is the path of variants that lead to it,
+    /// e.g. `ERC20Event::Transfer`.
+    pub name: String,
+    pub members: Vec<DecodedEventMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEventMember {
+    pub name: String,
+    /// The type of the member, without its module path.
+    pub ty: String,
+    /// Whether the member is serialized in the event keys (i.e. marked with `#[key]`).
+    pub key: bool,
+    pub value: String,
+}
+
+/// Decodes the events emitted by contracts so that they can be displayed by name.
+pub trait EventDecoder {
+    /// Decodes an event emitted by the contract at `from_address` in block `block_number` (`None`
+    /// if pending), or returns `None` if it can't be decoded, in which case its raw keys and data
+    /// are displayed instead.
+    fn decode_event(
+        &self,
+        from_address: FieldElement,
+        block_number: Option<u64>,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Option<DecodedEvent>;
+}
+
+/// An [EventDecoder] that doesn't decode anything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawEvents;
+
+impl EventDecoder for RawEvents {
+    fn decode_event(
+        &self,
+        _: FieldElement,
+        _: Option<u64>,
+        _: &[FieldElement],
+        _: &[FieldElement],
+    ) -> Option<DecodedEvent> {
+        None
+    }
+}
+
+/// Wraps a value whose events should be displayed decoded by `decoder`, and whose addresses and
+/// selectors should be displayed along with their names in `names`.
+#[derive(Debug)]
+pub struct WithEvents<'a, T, D, N> {
+    pub value: &'a T,
+    pub decoder: &'a D,
+    pub names: &'a N,
+}
+
+impl<'a, T, D: EventDecoder, N: Names> WithEvents<'a, T, D, N> {
+    pub fn new(value: &'a T, decoder: &'a D, names: &'a N) -> Self {
+        Self { value, decoder, names }
+    }
+}
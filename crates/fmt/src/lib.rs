@@ -1,10 +1,22 @@
+mod balances;
+mod calldata;
+mod events;
+mod names;
 mod rpc;
+mod tokens;
 pub mod utils;
 
 use prettytable::format::TableFormat;
 use prettytable::Table;
 use starknet::core::types::FieldElement;
 
+pub use self::balances::{BalanceSample, BalanceSeries};
+pub use self::calldata::{CalldataLayout, ExecuteCall, ExecuteCalldata};
+pub use self::events::{DecodedEvent, DecodedEventMember, EventDecoder, RawEvents, WithEvents};
+pub use self::names::{Names, NoNames, WithNames};
+pub use self::rpc::pretty_block_without_txs;
+pub use self::tokens::{Portfolio, TokenInfo};
+
 /// Display trait for pretty printing
 pub trait Pretty {
     fn prettify(&self) -> String;
@@ -0,0 +1,121 @@
+use starknet::core::types::FieldElement;
+
+/// Looks up the names behind the addresses and selectors found in the outputs, e.g. in an address
+/// book or a selector database. Every lookup returns `None` by default.
+pub trait Names {
+    /// Returns the name of the contract or account at `address`.
+    fn address_name(&self, _address: FieldElement) -> Option<&str> {
+        None
+    }
+
+    /// Returns the name of the function with the given selector.
+    fn function_name(&self, _selector: FieldElement) -> Option<&str> {
+        None
+    }
+
+    /// Returns the name of the event with the given selector.
+    fn event_name(&self, _selector: FieldElement) -> Option<&str> {
+        None
+    }
+}
+
+/// A [Names] that doesn't know any name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoNames;
+
+impl Names for NoNames {}
+
+impl<T: Names + ?Sized> Names for &T {
+    fn address_name(&self, address: FieldElement) -> Option<&str> {
+        (**self).address_name(address)
+    }
+
+    fn function_name(&self, selector: FieldElement) -> Option<&str> {
+        (**self).function_name(selector)
+    }
+
+    fn event_name(&self, selector: FieldElement) -> Option<&str> {
+        (**self).event_name(selector)
+    }
+}
+
+/// Looks the names up in both, the first one taking priority.
+impl<A: Names, B: Names> Names for (A, B) {
+    fn address_name(&self, address: FieldElement) -> Option<&str> {
+        self.0.address_name(address).or_else(|| self.1.address_name(address))
+    }
+
+    fn function_name(&self, selector: FieldElement) -> Option<&str> {
+        self.0.function_name(selector).or_else(|| self.1.function_name(selector))
+    }
+
+    fn event_name(&self, selector: FieldElement) -> Option<&str> {
+        self.0.event_name(selector).or_else(|| self.1.event_name(selector))
+    }
+}
+
+/// Wraps a value whose addresses and selectors should be displayed along with their names in
+/// `names`.
+#[derive(Debug)]
+pub struct WithNames<'a, T, N> {
+    pub value: &'a T,
+    pub names: &'a N,
+}
+
+impl<'a, T, N: Names> WithNames<'a, T, N> {
+    pub fn new(value: &'a T, names: &'a N) -> Self {
+        Self { value, names }
+    }
+}
+
+/// Displays an address along with its name, if it has one.
+pub(crate) fn pretty_address(address: FieldElement, names: &dyn Names) -> String {
+    match names.address_name(address) {
+        Some(name) => format!("{address:#x} ({name})"),
+        None => format!("{address:#x}"),
+    }
+}
+
+/// Displays a function selector along with its name, if it has one.
+pub(crate) fn pretty_function_selector(selector: FieldElement, names: &dyn Names) -> String {
+    match names.function_name(selector) {
+        Some(name) => format!("{selector:#x} ({name})"),
+        None => format!("{selector:#x}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    struct Book;
+
+    impl Names for Book {
+        fn address_name(&self, address: FieldElement) -> Option<&str> {
+            (address == felt!("0x1")).then_some("treasury")
+        }
+    }
+
+    struct Db;
+
+    impl Names for Db {
+        fn address_name(&self, _: FieldElement) -> Option<&str> {
+            Some("other")
+        }
+
+        fn function_name(&self, _: FieldElement) -> Option<&str> {
+            Some("transfer")
+        }
+    }
+
+    #[test]
+    fn first_names_take_priority() {
+        let names = (&Book, &Db);
+        assert_eq!(pretty_address(felt!("0x1"), &names), "0x1 (treasury)");
+        assert_eq!(pretty_address(felt!("0x2"), &names), "0x2 (other)");
+        assert_eq!(pretty_function_selector(felt!("0xa"), &names), "0xa (transfer)");
+        assert_eq!(pretty_address(felt!("0x1"), &NoNames), "0x1");
+    }
+}
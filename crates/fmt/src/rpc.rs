@@ -1,83 +1,234 @@
 use std::vec;
 
 use alloy_primitives::U256;
+use chrono::{Local, TimeZone};
 use prettytable::format::Alignment;
 use prettytable::{Cell, Row, Table};
 use starknet::core::types::{
-    Event, ExecutionResources, ExecutionResult, FeePayment, MaybePendingTransactionReceipt,
-    MsgToL1, PendingTransactionReceipt, PriceUnit, TransactionFinalityStatus, TransactionReceipt,
+    BlockStatus, ContractClass, DataAvailabilityMode, DeclareTransaction, DeployAccountTransaction,
+    EmittedEvent, Event, EventsPage, ExecutionResources, ExecutionResult, FeeEstimate, FeePayment,
+    FieldElement, InvokeTransaction, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
+    MaybePendingStateUpdate, MaybePendingTransactionReceipt, MsgToL1, PendingTransactionReceipt,
+    PriceUnit, ResourceBoundsMapping, StateDiff, SyncStatusType, Transaction,
+    TransactionExecutionStatus, TransactionFinalityStatus, TransactionReceipt, TransactionStatus,
 };
+use starknet::core::utils::get_selector_from_name;
 
-use crate::{utils, Pretty, Tabular};
+use crate::calldata::{pretty_calls, ExecuteCalldata};
+use crate::events::{EventDecoder, RawEvents, WithEvents};
+use crate::names::{pretty_address, pretty_function_selector};
+use crate::{utils, Names, NoNames, Pretty, Tabular, WithNames};
 
-impl Tabular for TransactionReceipt {
-    fn build_table(&self, table: &mut Table) {
-        match self {
-            TransactionReceipt::Invoke(receipt) => {
-                table.add_row(Row::from([
-                    "TX HASH".to_string(),
-                    receipt.transaction_hash.prettify(),
-                ]));
-                table.add_row(Row::from(["BLOCK HASH".to_string(), receipt.block_hash.prettify()]));
-                table.add_row(Row::from([
-                    "BLOCK NUMBER".to_string(),
-                    receipt.block_number.to_string(),
-                ]));
-                table.add_row(Row::from(["ACTUAL FEE".to_string(), receipt.actual_fee.prettify()]));
-                table.add_row(Row::from([
-                    "FINALITY STATUS".to_string(),
-                    receipt.finality_status.prettify(),
-                ]));
-                table.add_row(Row::from([
-                    "EXECUTION RESULT".to_string(),
-                    receipt.execution_result.prettify(),
-                ]));
-                table.add_row(Row::from([
-                    "EXECUTION RESOURCES".to_string(),
-                    receipt.execution_resources.tablify().to_string(),
-                ]));
-                table.add_row(Row::from([
-                    "EVENTS".to_string(),
-                    receipt.events.iter().map(|e| e.tablify().to_string()).collect(),
-                ]));
-                table.add_row(Row::from([
-                    "MESSAGES SENT".to_string(),
-                    receipt.messages_sent.iter().map(|e| e.tablify().to_string()).collect(),
-                ]));
+/// Builds a two-column `NAME | VALUE` row.
+fn kv_row(name: &str, value: impl Into<String>) -> Row {
+    Row::from([name.to_string(), value.into()])
+}
+
+/// Adds the rows that are shared by all the (non-pending) transaction receipt types.
+macro_rules! add_receipt_rows {
+    ($table:expr, $receipt:expr, $decoder:expr, $names:expr) => {{
+        let receipt = $receipt;
+        $table.add_row(kv_row("TX HASH", receipt.transaction_hash.prettify()));
+        $table.add_row(kv_row("BLOCK HASH", receipt.block_hash.prettify()));
+        $table.add_row(kv_row("BLOCK NUMBER", receipt.block_number.to_string()));
+        $table.add_row(kv_row("ACTUAL FEE", receipt.actual_fee.prettify()));
+        $table.add_row(kv_row("FINALITY STATUS", receipt.finality_status.prettify()));
+        $table.add_row(kv_row("EXECUTION RESULT", receipt.execution_result.prettify()));
+        $table.add_row(kv_row(
+            "EXECUTION RESOURCES",
+            receipt.execution_resources.tablify().to_string(),
+        ));
+        $table.add_row(kv_row(
+            "EVENTS",
+            receipt
+                .events
+                .iter()
+                .map(|e| event_table(e, Some(receipt.block_number), $decoder, $names).to_string())
+                .collect::<String>(),
+        ));
+        $table.add_row(kv_row(
+            "MESSAGES SENT",
+            receipt
+                .messages_sent
+                .iter()
+                .map(|e| msg_to_l1_table(e, $names).to_string())
+                .collect::<String>(),
+        ));
+    }};
+}
+
+/// Adds the rows that are shared by all the pending transaction receipt types.
+macro_rules! add_pending_receipt_rows {
+    ($table:expr, $receipt:expr, $decoder:expr, $names:expr) => {{
+        let receipt = $receipt;
+        $table.add_row(kv_row("TX HASH", receipt.transaction_hash.prettify()));
+        $table.add_row(kv_row("ACTUAL FEE", receipt.actual_fee.prettify()));
+        $table.add_row(kv_row("EXECUTION RESULT", receipt.execution_result.prettify()));
+        $table.add_row(kv_row(
+            "EXECUTION RESOURCES",
+            receipt.execution_resources.tablify().to_string(),
+        ));
+        $table.add_row(kv_row(
+            "EVENTS",
+            receipt
+                .events
+                .iter()
+                .map(|e| event_table(e, None, $decoder, $names).to_string())
+                .collect::<String>(),
+        ));
+        $table.add_row(kv_row(
+            "MESSAGES SENT",
+            receipt
+                .messages_sent
+                .iter()
+                .map(|e| msg_to_l1_table(e, $names).to_string())
+                .collect::<String>(),
+        ));
+    }};
+}
+
+/// Builds the table of an event emitted in `block_number`, decoded by `decoder` if possible.
+fn event_table(
+    event: &Event,
+    block_number: Option<u64>,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) -> Table {
+    let mut table = Table::new();
+    table.set_format(Event::format());
+    add_event_with_emitter_rows(&mut table, event, block_number, decoder, names);
+    table
+}
+
+fn add_event_with_emitter_rows(
+    table: &mut Table,
+    event: &Event,
+    block_number: Option<u64>,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    table.add_row(Row::from(["From".to_string(), pretty_address(event.from_address, names)]));
+    let (keys, data) = (&event.keys, &event.data);
+    add_event_rows(table, event.from_address, block_number, keys, data, decoder, names);
+}
+
+/// Adds either the decoded fields of an event, or its raw keys and data.
+fn add_event_rows(
+    table: &mut Table,
+    from_address: FieldElement,
+    block_number: Option<u64>,
+    keys: &[FieldElement],
+    data: &[FieldElement],
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    match decoder.decode_event(from_address, block_number, keys, data) {
+        Some(event) => {
+            table.add_row(Row::from(["Event".to_string(), event.name]));
+            for member in event.members {
+                let key = if member.key { " [key]" } else { "" };
+                let name = format!("{} ({}){key}", member.name, member.ty);
+                table.add_row(Row::from([name, member.value]));
             }
+        }
 
-            _ => {
-                todo!()
+        None => {
+            // without an ABI, the first key may still be a known event selector
+            if let Some(name) = keys.first().and_then(|key| names.event_name(*key)) {
+                table.add_row(Row::from(["Event".to_string(), format!("{name} (guessed)")]));
             }
+            table.add_row(Row::from(["Keys".to_string(), keys.to_vec().prettify()]));
+            table.add_row(Row::from(["Data".to_string(), data.to_vec().prettify()]));
         }
     }
 }
 
-impl Tabular for MaybePendingTransactionReceipt {
-    fn build_table(&self, table: &mut Table) {
-        let type_row = match self {
-            Self::Receipt(receipt) => {
-                receipt.build_table(table);
-                match receipt {
-                    TransactionReceipt::Invoke(_) => {
-                        Row::new(vec![Cell::new("TYPE"), Cell::new("INVOKE")])
-                    }
-                    TransactionReceipt::Declare(_) => {
-                        Row::new(vec![Cell::new("TYPE"), Cell::new("DECLARE")])
-                    }
-                    TransactionReceipt::Deploy(_) => {
-                        Row::new(vec![Cell::new("TYPE"), Cell::new("DEPLOY")])
-                    }
-                    TransactionReceipt::DeployAccount(_) => {
-                        Row::new(vec![Cell::new("TYPE"), Cell::new("DEPLOY ACCOUNT")])
-                    }
-                    TransactionReceipt::L1Handler(_) => {
-                        Row::new(vec![Cell::new("TYPE"), Cell::new("L1 HANDLER")])
-                    }
+fn add_transaction_receipt_rows(
+    receipt: &TransactionReceipt,
+    table: &mut Table,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    match receipt {
+        TransactionReceipt::Invoke(receipt) => add_receipt_rows!(table, receipt, decoder, names),
+        TransactionReceipt::Declare(receipt) => add_receipt_rows!(table, receipt, decoder, names),
+        TransactionReceipt::L1Handler(receipt) => add_receipt_rows!(table, receipt, decoder, names),
+
+        TransactionReceipt::Deploy(receipt) => {
+            add_receipt_rows!(table, receipt, decoder, names);
+            table.add_row(kv_row(
+                "CONTRACT ADDRESS",
+                pretty_address(receipt.contract_address, names),
+            ));
+        }
+
+        TransactionReceipt::DeployAccount(receipt) => {
+            add_receipt_rows!(table, receipt, decoder, names);
+            table.add_row(kv_row(
+                "CONTRACT ADDRESS",
+                pretty_address(receipt.contract_address, names),
+            ));
+        }
+    }
+}
+
+fn add_pending_transaction_receipt_rows(
+    receipt: &PendingTransactionReceipt,
+    table: &mut Table,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    match receipt {
+        PendingTransactionReceipt::Invoke(receipt) => {
+            add_pending_receipt_rows!(table, receipt, decoder, names)
+        }
+        PendingTransactionReceipt::Declare(receipt) => {
+            add_pending_receipt_rows!(table, receipt, decoder, names)
+        }
+        PendingTransactionReceipt::L1Handler(receipt) => {
+            add_pending_receipt_rows!(table, receipt, decoder, names)
+        }
+        PendingTransactionReceipt::DeployAccount(receipt) => {
+            add_pending_receipt_rows!(table, receipt, decoder, names);
+            table.add_row(kv_row(
+                "CONTRACT ADDRESS",
+                pretty_address(receipt.contract_address, names),
+            ));
+        }
+    }
+}
+
+fn add_maybe_pending_receipt_rows(
+    receipt: &MaybePendingTransactionReceipt,
+    table: &mut Table,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    let type_row = match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => {
+            add_transaction_receipt_rows(receipt, table, decoder, names);
+            match receipt {
+                TransactionReceipt::Invoke(_) => {
+                    Row::new(vec![Cell::new("TYPE"), Cell::new("INVOKE")])
+                }
+                TransactionReceipt::Declare(_) => {
+                    Row::new(vec![Cell::new("TYPE"), Cell::new("DECLARE")])
+                }
+                TransactionReceipt::Deploy(_) => {
+                    Row::new(vec![Cell::new("TYPE"), Cell::new("DEPLOY")])
+                }
+                TransactionReceipt::DeployAccount(_) => {
+                    Row::new(vec![Cell::new("TYPE"), Cell::new("DEPLOY ACCOUNT")])
+                }
+                TransactionReceipt::L1Handler(_) => {
+                    Row::new(vec![Cell::new("TYPE"), Cell::new("L1 HANDLER")])
                 }
             }
+        }
 
-            Self::PendingReceipt(pending) => match pending {
+        MaybePendingTransactionReceipt::PendingReceipt(pending) => {
+            add_pending_transaction_receipt_rows(pending, table, decoder, names);
+            match pending {
                 PendingTransactionReceipt::Invoke(_) => {
                     Row::new(vec![Cell::new("TYPE"), Cell::new("INVOKE")])
                 }
@@ -90,26 +241,592 @@ impl Tabular for MaybePendingTransactionReceipt {
                 PendingTransactionReceipt::L1Handler(_) => {
                     Row::new(vec![Cell::new("TYPE"), Cell::new("L1 HANDLER")])
                 }
-            },
-        };
+            }
+        }
+    };
+
+    table.insert_row(0, type_row);
+}
 
-        table.insert_row(0, type_row);
+impl Tabular for TransactionReceipt {
+    fn build_table(&self, table: &mut Table) {
+        add_transaction_receipt_rows(self, table, &RawEvents, &NoNames);
+    }
+}
+
+impl Tabular for PendingTransactionReceipt {
+    fn build_table(&self, table: &mut Table) {
+        add_pending_transaction_receipt_rows(self, table, &RawEvents, &NoNames);
+    }
+}
+
+impl Tabular for MaybePendingTransactionReceipt {
+    fn build_table(&self, table: &mut Table) {
+        add_maybe_pending_receipt_rows(self, table, &RawEvents, &NoNames);
+    }
+}
+
+impl<D: EventDecoder, N: Names> Tabular for WithEvents<'_, MaybePendingTransactionReceipt, D, N> {
+    fn build_table(&self, table: &mut Table) {
+        add_maybe_pending_receipt_rows(self.value, table, self.decoder, self.names);
     }
 }
 
 impl Tabular for Event {
     fn build_table(&self, table: &mut Table) {
-        table.add_row(Row::from(["From".to_string(), self.from_address.prettify()]));
-        table.add_row(Row::from(["Keys".to_string(), self.keys.prettify()]));
-        table.add_row(Row::from(["Data".to_string(), self.data.prettify()]));
+        add_event_with_emitter_rows(table, self, None, &RawEvents, &NoNames);
     }
 }
 
+fn msg_to_l1_table(msg: &MsgToL1, names: &dyn Names) -> Table {
+    let mut table = Table::new();
+    table.set_format(MsgToL1::format());
+    add_msg_to_l1_rows(msg, &mut table, names);
+    table
+}
+
+fn add_msg_to_l1_rows(msg: &MsgToL1, table: &mut Table, names: &dyn Names) {
+    table.add_row(Row::from(["From".to_string(), pretty_address(msg.from_address, names)]));
+    table.add_row(Row::from(["To".to_string(), msg.to_address.prettify()]));
+    table.add_row(Row::from(["Payload".to_string(), msg.payload.prettify()]));
+}
+
 impl Tabular for MsgToL1 {
     fn build_table(&self, table: &mut Table) {
-        table.add_row(Row::from(["From".to_string(), self.from_address.prettify()]));
-        table.add_row(Row::from(["To".to_string(), self.to_address.prettify()]));
-        table.add_row(Row::from(["Payload".to_string(), self.payload.prettify()]));
+        add_msg_to_l1_rows(self, table, &NoNames);
+    }
+}
+
+fn execute_selector() -> FieldElement {
+    get_selector_from_name("__execute__").expect("valid entrypoint name")
+}
+
+/// Builds the row of the calldata of an account's `__execute__`, which lists the calls made by the
+/// account if the calldata can be decoded.
+fn execute_calldata_row(calldata: &[FieldElement], names: &dyn Names) -> Row {
+    match ExecuteCalldata::decode(calldata) {
+        Some(decoded) => kv_row("CALLS", pretty_calls(&decoded.calls, names)),
+        None => kv_row("CALLDATA", calldata.to_vec().prettify()),
+    }
+}
+
+fn add_transaction_rows(tx: &Transaction, table: &mut Table, names: &dyn Names) {
+    match tx {
+        Transaction::Invoke(tx) => {
+            table.add_row(kv_row("TYPE", "INVOKE"));
+
+            match tx {
+                InvokeTransaction::V0(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "CONTRACT ADDRESS",
+                        pretty_address(tx.contract_address, names),
+                    ));
+                    table.add_row(kv_row(
+                        "ENTRY POINT SELECTOR",
+                        pretty_function_selector(tx.entry_point_selector, names),
+                    ));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    if tx.entry_point_selector == execute_selector() {
+                        table.add_row(execute_calldata_row(&tx.calldata, names));
+                    } else {
+                        table.add_row(kv_row("CALLDATA", tx.calldata.prettify()));
+                    }
+                    table.add_row(kv_row("VERSION", "0"));
+                }
+
+                InvokeTransaction::V1(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    table.add_row(execute_calldata_row(&tx.calldata, names));
+                    table.add_row(kv_row("VERSION", "1"));
+                }
+
+                InvokeTransaction::V3(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(execute_calldata_row(&tx.calldata, names));
+                    table.add_row(kv_row("RESOURCE BOUNDS", tx.resource_bounds.prettify()));
+                    table.add_row(kv_row("TIP", tx.tip.prettify()));
+                    table.add_row(kv_row("PAYMASTER DATA", tx.paymaster_data.prettify()));
+                    table.add_row(kv_row(
+                        "ACCOUNT DEPLOYMENT DATA",
+                        tx.account_deployment_data.prettify(),
+                    ));
+                    table.add_row(kv_row(
+                        "NONCE DA MODE",
+                        tx.nonce_data_availability_mode.prettify(),
+                    ));
+                    table.add_row(kv_row("FEE DA MODE", tx.fee_data_availability_mode.prettify()));
+                    table.add_row(kv_row("VERSION", "3"));
+                }
+            }
+        }
+
+        Transaction::Declare(tx) => {
+            table.add_row(kv_row("TYPE", "DECLARE"));
+
+            match tx {
+                DeclareTransaction::V0(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    table.add_row(kv_row("VERSION", "0"));
+                }
+
+                DeclareTransaction::V1(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    table.add_row(kv_row("VERSION", "1"));
+                }
+
+                DeclareTransaction::V2(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row("COMPILED CLASS HASH", tx.compiled_class_hash.prettify()));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    table.add_row(kv_row("VERSION", "2"));
+                }
+
+                DeclareTransaction::V3(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row(
+                        "SENDER ADDRESS",
+                        pretty_address(tx.sender_address, names),
+                    ));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row("COMPILED CLASS HASH", tx.compiled_class_hash.prettify()));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("RESOURCE BOUNDS", tx.resource_bounds.prettify()));
+                    table.add_row(kv_row("TIP", tx.tip.prettify()));
+                    table.add_row(kv_row("PAYMASTER DATA", tx.paymaster_data.prettify()));
+                    table.add_row(kv_row(
+                        "ACCOUNT DEPLOYMENT DATA",
+                        tx.account_deployment_data.prettify(),
+                    ));
+                    table.add_row(kv_row(
+                        "NONCE DA MODE",
+                        tx.nonce_data_availability_mode.prettify(),
+                    ));
+                    table.add_row(kv_row("FEE DA MODE", tx.fee_data_availability_mode.prettify()));
+                    table.add_row(kv_row("VERSION", "3"));
+                }
+            }
+        }
+
+        Transaction::Deploy(tx) => {
+            table.add_row(kv_row("TYPE", "DEPLOY"));
+            table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+            table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+            table.add_row(kv_row("CONTRACT ADDRESS SALT", tx.contract_address_salt.prettify()));
+            table.add_row(kv_row("CONSTRUCTOR CALLDATA", tx.constructor_calldata.prettify()));
+            table.add_row(kv_row("VERSION", tx.version.to_string()));
+        }
+
+        Transaction::DeployAccount(tx) => {
+            table.add_row(kv_row("TYPE", "DEPLOY ACCOUNT"));
+
+            match tx {
+                DeployAccountTransaction::V1(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row(
+                        "CONTRACT ADDRESS SALT",
+                        tx.contract_address_salt.prettify(),
+                    ));
+                    table.add_row(kv_row(
+                        "CONSTRUCTOR CALLDATA",
+                        tx.constructor_calldata.prettify(),
+                    ));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("MAX FEE", tx.max_fee.prettify()));
+                    table.add_row(kv_row("VERSION", "1"));
+                }
+
+                DeployAccountTransaction::V3(tx) => {
+                    table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+                    table.add_row(kv_row("CLASS HASH", tx.class_hash.prettify()));
+                    table.add_row(kv_row(
+                        "CONTRACT ADDRESS SALT",
+                        tx.contract_address_salt.prettify(),
+                    ));
+                    table.add_row(kv_row(
+                        "CONSTRUCTOR CALLDATA",
+                        tx.constructor_calldata.prettify(),
+                    ));
+                    table.add_row(kv_row("SIGNATURE", tx.signature.prettify()));
+                    table.add_row(kv_row("NONCE", tx.nonce.prettify()));
+                    table.add_row(kv_row("RESOURCE BOUNDS", tx.resource_bounds.prettify()));
+                    table.add_row(kv_row("TIP", tx.tip.prettify()));
+                    table.add_row(kv_row("PAYMASTER DATA", tx.paymaster_data.prettify()));
+                    table.add_row(kv_row(
+                        "NONCE DA MODE",
+                        tx.nonce_data_availability_mode.prettify(),
+                    ));
+                    table.add_row(kv_row("FEE DA MODE", tx.fee_data_availability_mode.prettify()));
+                    table.add_row(kv_row("VERSION", "3"));
+                }
+            }
+        }
+
+        Transaction::L1Handler(tx) => {
+            table.add_row(kv_row("TYPE", "L1 HANDLER"));
+            table.add_row(kv_row("TX HASH", tx.transaction_hash.prettify()));
+            table.add_row(kv_row("CONTRACT ADDRESS", pretty_address(tx.contract_address, names)));
+            table.add_row(kv_row(
+                "ENTRY POINT SELECTOR",
+                pretty_function_selector(tx.entry_point_selector, names),
+            ));
+            table.add_row(kv_row("CALLDATA", tx.calldata.prettify()));
+            table.add_row(kv_row("NONCE", tx.nonce.to_string()));
+            table.add_row(kv_row("VERSION", tx.version.to_string()));
+        }
+    }
+}
+
+impl Tabular for Transaction {
+    fn build_table(&self, table: &mut Table) {
+        add_transaction_rows(self, table, &NoNames);
+    }
+}
+
+impl<N: Names> Tabular for WithNames<'_, Transaction, N> {
+    fn build_table(&self, table: &mut Table) {
+        add_transaction_rows(self.value, table, self.names);
+    }
+}
+
+/// Adds the header rows (ie everything other than the transactions) of a block.
+macro_rules! add_block_header_rows {
+    ($table:expr, $block:expr) => {{
+        let block = $block;
+        $table.add_row(kv_row("BLOCK HASH", block.block_hash.prettify()));
+        $table.add_row(kv_row("PARENT HASH", block.parent_hash.prettify()));
+        $table.add_row(kv_row("BLOCK NUMBER", block.block_number.to_string()));
+        $table.add_row(kv_row("NEW ROOT", block.new_root.prettify()));
+        $table.add_row(kv_row("TIMESTAMP", format_timestamp(block.timestamp)));
+        $table.add_row(kv_row("SEQUENCER ADDRESS", block.sequencer_address.prettify()));
+        $table.add_row(kv_row("STATUS", block.status.prettify()));
+    }};
+}
+
+/// Adds the header rows of a pending block.
+macro_rules! add_pending_block_header_rows {
+    ($table:expr, $block:expr) => {{
+        let block = $block;
+        $table.add_row(kv_row("PARENT HASH", block.parent_hash.prettify()));
+        $table.add_row(kv_row("TIMESTAMP", format_timestamp(block.timestamp)));
+        $table.add_row(kv_row("SEQUENCER ADDRESS", block.sequencer_address.prettify()));
+        $table.add_row(kv_row("STATUS", "Pending"));
+    }};
+}
+
+fn add_block_with_txs_rows(block: &MaybePendingBlockWithTxs, table: &mut Table, names: &dyn Names) {
+    let txs = match block {
+        MaybePendingBlockWithTxs::Block(block) => {
+            add_block_header_rows!(table, block);
+            &block.transactions
+        }
+        MaybePendingBlockWithTxs::PendingBlock(block) => {
+            add_pending_block_header_rows!(table, block);
+            &block.transactions
+        }
+    };
+
+    let txs = txs.iter().map(|tx| {
+        let mut table = Table::new();
+        table.set_format(Transaction::format());
+        add_transaction_rows(tx, &mut table, names);
+        table.to_string()
+    });
+    table.add_row(kv_row("TRANSACTIONS", txs.collect::<String>()));
+}
+
+impl Tabular for MaybePendingBlockWithTxs {
+    fn build_table(&self, table: &mut Table) {
+        add_block_with_txs_rows(self, table, &NoNames);
+    }
+}
+
+impl<N: Names> Tabular for WithNames<'_, MaybePendingBlockWithTxs, N> {
+    fn build_table(&self, table: &mut Table) {
+        add_block_with_txs_rows(self.value, table, self.names);
+    }
+}
+
+impl Tabular for MaybePendingBlockWithTxHashes {
+    fn build_table(&self, table: &mut Table) {
+        let txs = match self {
+            Self::Block(block) => {
+                add_block_header_rows!(table, block);
+                &block.transactions
+            }
+            Self::PendingBlock(block) => {
+                add_pending_block_header_rows!(table, block);
+                &block.transactions
+            }
+        };
+
+        table.add_row(kv_row("TRANSACTIONS", txs.prettify()));
+    }
+}
+
+/// Pretty print the block information without including its transactions.
+pub fn pretty_block_without_txs(block: &MaybePendingBlockWithTxHashes) -> String {
+    let mut table = Table::new();
+    table.set_format(<MaybePendingBlockWithTxHashes as Tabular>::format());
+
+    match block {
+        MaybePendingBlockWithTxHashes::Block(block) => add_block_header_rows!(table, block),
+        MaybePendingBlockWithTxHashes::PendingBlock(block) => {
+            add_pending_block_header_rows!(table, block)
+        }
+    }
+
+    table.to_string()
+}
+
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map_or_else(|| timestamp.to_string(), |t| t.to_string())
+}
+
+impl Tabular for SyncStatusType {
+    fn build_table(&self, table: &mut Table) {
+        match self {
+            Self::NotSyncing => {
+                table.add_row(kv_row("SYNCING", "false"));
+            }
+
+            Self::Syncing(status) => {
+                table.add_row(kv_row("SYNCING", "true"));
+                table.add_row(kv_row("STARTING BLOCK HASH", status.starting_block_hash.prettify()));
+                table.add_row(kv_row(
+                    "STARTING BLOCK NUMBER",
+                    status.starting_block_num.to_string(),
+                ));
+                table.add_row(kv_row("CURRENT BLOCK HASH", status.current_block_hash.prettify()));
+                table.add_row(kv_row("CURRENT BLOCK NUMBER", status.current_block_num.to_string()));
+                table.add_row(kv_row("HIGHEST BLOCK HASH", status.highest_block_hash.prettify()));
+                table.add_row(kv_row("HIGHEST BLOCK NUMBER", status.highest_block_num.to_string()));
+            }
+        }
+    }
+}
+
+impl Tabular for ContractClass {
+    fn build_table(&self, table: &mut Table) {
+        match self {
+            Self::Sierra(class) => {
+                let entry_points = &class.entry_points_by_type;
+                let selectors = |eps: &[starknet::core::types::SierraEntryPoint]| {
+                    eps.iter().map(|ep| ep.selector).collect::<Vec<_>>().prettify()
+                };
+
+                table.add_row(kv_row("TYPE", "SIERRA"));
+                table.add_row(kv_row(
+                    "CONTRACT CLASS VERSION",
+                    class.contract_class_version.clone(),
+                ));
+                table.add_row(kv_row(
+                    "PROGRAM SIZE",
+                    format!("{} felts", class.sierra_program.len()),
+                ));
+                table.add_row(kv_row("CONSTRUCTOR", selectors(&entry_points.constructor)));
+                table.add_row(kv_row("EXTERNAL", selectors(&entry_points.external)));
+                table.add_row(kv_row("L1 HANDLER", selectors(&entry_points.l1_handler)));
+            }
+
+            Self::Legacy(class) => {
+                let entry_points = &class.entry_points_by_type;
+                let selectors = |eps: &[starknet::core::types::LegacyContractEntryPoint]| {
+                    eps.iter().map(|ep| ep.selector).collect::<Vec<_>>().prettify()
+                };
+
+                table.add_row(kv_row("TYPE", "LEGACY"));
+                table.add_row(kv_row("PROGRAM SIZE", format!("{} bytes", class.program.len())));
+                table.add_row(kv_row("CONSTRUCTOR", selectors(&entry_points.constructor)));
+                table.add_row(kv_row("EXTERNAL", selectors(&entry_points.external)));
+                table.add_row(kv_row("L1 HANDLER", selectors(&entry_points.l1_handler)));
+            }
+        }
+    }
+}
+
+impl Tabular for MaybePendingStateUpdate {
+    fn build_table(&self, table: &mut Table) {
+        match self {
+            Self::Update(update) => {
+                table.add_row(kv_row("BLOCK HASH", update.block_hash.prettify()));
+                table.add_row(kv_row("OLD ROOT", update.old_root.prettify()));
+                table.add_row(kv_row("NEW ROOT", update.new_root.prettify()));
+                update.state_diff.build_table(table);
+            }
+
+            Self::PendingUpdate(update) => {
+                table.add_row(kv_row("OLD ROOT", update.old_root.prettify()));
+                update.state_diff.build_table(table);
+            }
+        }
+    }
+}
+
+impl Tabular for StateDiff {
+    fn build_table(&self, table: &mut Table) {
+        let storage_diffs = self
+            .storage_diffs
+            .iter()
+            .flat_map(|diff| {
+                diff.storage_entries.iter().map(move |entry| {
+                    format!("{:#x}: {:#x} = {:#x}", diff.address, entry.key, entry.value)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let deployed = self
+            .deployed_contracts
+            .iter()
+            .map(|c| format!("{:#x}: {:#x}", c.address, c.class_hash))
+            .collect::<Vec<_>>();
+
+        let declared = self
+            .declared_classes
+            .iter()
+            .map(|c| format!("{:#x} (compiled: {:#x})", c.class_hash, c.compiled_class_hash))
+            .collect::<Vec<_>>();
+
+        let replaced = self
+            .replaced_classes
+            .iter()
+            .map(|c| format!("{:#x}: {:#x}", c.contract_address, c.class_hash))
+            .collect::<Vec<_>>();
+
+        let nonces = self
+            .nonces
+            .iter()
+            .map(|n| format!("{:#x}: {:#x}", n.contract_address, n.nonce))
+            .collect::<Vec<_>>();
+
+        table.add_row(kv_row("STORAGE DIFFS", storage_diffs.join("\n")));
+        table.add_row(kv_row("DEPLOYED CONTRACTS", deployed.join("\n")));
+        table.add_row(kv_row("DECLARED CLASSES", declared.join("\n")));
+        table.add_row(kv_row(
+            "DEPRECATED DECLARED CLASSES",
+            self.deprecated_declared_classes.prettify(),
+        ));
+        table.add_row(kv_row("REPLACED CLASSES", replaced.join("\n")));
+        table.add_row(kv_row("NONCES", nonces.join("\n")));
+    }
+}
+
+fn add_emitted_event_rows(
+    event: &EmittedEvent,
+    table: &mut Table,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    table.add_row(kv_row("Tx hash", event.transaction_hash.prettify()));
+    if let Some(number) = event.block_number {
+        table.add_row(kv_row("Block number", number.to_string()));
+    }
+    table.add_row(kv_row("From", pretty_address(event.from_address, names)));
+    let (keys, data) = (&event.keys, &event.data);
+    add_event_rows(table, event.from_address, event.block_number, keys, data, decoder, names);
+}
+
+fn add_events_page_rows(
+    page: &EventsPage,
+    table: &mut Table,
+    decoder: &dyn EventDecoder,
+    names: &dyn Names,
+) {
+    table.add_row(kv_row(
+        "EVENTS",
+        page.events
+            .iter()
+            .map(|e| {
+                let mut table = Table::new();
+                table.set_format(EmittedEvent::format());
+                add_emitted_event_rows(e, &mut table, decoder, names);
+                table.to_string()
+            })
+            .collect::<String>(),
+    ));
+
+    if let Some(token) = &page.continuation_token {
+        table.add_row(kv_row("CONTINUATION TOKEN", token.clone()));
+    }
+}
+
+impl Tabular for EmittedEvent {
+    fn build_table(&self, table: &mut Table) {
+        add_emitted_event_rows(self, table, &RawEvents, &NoNames);
+    }
+}
+
+impl Tabular for EventsPage {
+    fn build_table(&self, table: &mut Table) {
+        add_events_page_rows(self, table, &RawEvents, &NoNames);
+    }
+}
+
+impl<D: EventDecoder, N: Names> Tabular for WithEvents<'_, EventsPage, D, N> {
+    fn build_table(&self, table: &mut Table) {
+        add_events_page_rows(self.value, table, self.decoder, self.names);
+    }
+}
+
+impl Tabular for FeeEstimate {
+    fn build_table(&self, table: &mut Table) {
+        let (unit, token) = match self.unit {
+            PriceUnit::Wei => ("WEI", "ETH"),
+            PriceUnit::Fri => ("FRI", "STRK"),
+        };
+
+        let overall_fee = U256::from_be_bytes(self.overall_fee.to_bytes_be());
+        let overall_fee = format!(
+            "{} ({} {unit})",
+            utils::format_erc20_amount(overall_fee, token, 18, 8),
+            self.overall_fee
+        );
+
+        table.add_row(kv_row("GAS CONSUMED", self.gas_consumed.to_string()));
+        table.add_row(kv_row("GAS PRICE", format!("{} {unit}", self.gas_price)));
+        table.add_row(kv_row("OVERALL FEE", overall_fee));
     }
 }
 
@@ -124,6 +841,59 @@ impl Pretty for FeePayment {
     }
 }
 
+impl Pretty for ResourceBoundsMapping {
+    fn prettify(&self) -> String {
+        format!(
+            "L1 gas: max amount {:#x}, max price per unit {:#x}\nL2 gas: max amount {:#x}, max \
+             price per unit {:#x}",
+            self.l1_gas.max_amount,
+            self.l1_gas.max_price_per_unit,
+            self.l2_gas.max_amount,
+            self.l2_gas.max_price_per_unit
+        )
+    }
+}
+
+impl Pretty for DataAvailabilityMode {
+    fn prettify(&self) -> String {
+        match self {
+            Self::L1 => "L1".into(),
+            Self::L2 => "L2".into(),
+        }
+    }
+}
+
+impl Pretty for BlockStatus {
+    fn prettify(&self) -> String {
+        match self {
+            Self::Pending => "Pending".into(),
+            Self::AcceptedOnL2 => "Accepted on L2".into(),
+            Self::AcceptedOnL1 => "Accepted on L1".into(),
+            Self::Rejected => "Rejected".into(),
+        }
+    }
+}
+
+impl Pretty for TransactionStatus {
+    fn prettify(&self) -> String {
+        match self {
+            Self::Received => "Received".into(),
+            Self::Rejected => "Rejected".into(),
+            Self::AcceptedOnL2(status) => format!("Accepted on L2 ({})", status.prettify()),
+            Self::AcceptedOnL1(status) => format!("Accepted on L1 ({})", status.prettify()),
+        }
+    }
+}
+
+impl Pretty for TransactionExecutionStatus {
+    fn prettify(&self) -> String {
+        match self {
+            Self::Succeeded => "Succeeded".into(),
+            Self::Reverted => "Reverted".into(),
+        }
+    }
+}
+
 impl Pretty for TransactionFinalityStatus {
     fn prettify(&self) -> String {
         match self {
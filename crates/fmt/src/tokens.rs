@@ -0,0 +1,59 @@
+//! ERC20 tokens and their metadata.
+
+use alloy_primitives::U256;
+use prettytable::format::Alignment;
+use prettytable::{Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+
+use crate::utils::format_erc20_balance;
+use crate::Tabular;
+
+/// An ERC20 token and its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub address: FieldElement,
+    pub symbol: String,
+    pub decimals: u8,
+    /// The id of the chain the token is on, or `None` if it's at the same address on every chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<FieldElement>,
+}
+
+impl TokenInfo {
+    /// Whether the token is on `chain`.
+    pub fn is_on(&self, chain: FieldElement) -> bool {
+        self.chain.map_or(true, |token| token == chain)
+    }
+}
+
+/// The balances of an address in the known tokens.
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    /// The balance in each token, or the error that kept it from being read.
+    pub balances: Vec<(TokenInfo, Result<U256, String>)>,
+}
+
+impl Tabular for Portfolio {
+    fn build_table(&self, table: &mut Table) {
+        table.set_titles(Row::from(["TOKEN", "ADDRESS", "RAW", "BALANCE"]));
+
+        for (token, balance) in &self.balances {
+            let mut cells =
+                vec![Cell::new(&token.symbol), Cell::new(&format!("{:#x}", token.address))];
+
+            match balance {
+                Ok(balance) => cells.extend([
+                    Cell::new_align(&format!("{balance:#x}"), Alignment::RIGHT),
+                    Cell::new_align(
+                        &format_erc20_balance(*balance, &token.symbol, token.decimals),
+                        Alignment::RIGHT,
+                    ),
+                ]),
+                Err(error) => cells.push(Cell::new(&format!("error: {error}")).with_hspan(2)),
+            }
+
+            table.add_row(Row::new(cells));
+        }
+    }
+}
@@ -14,12 +14,17 @@ use alloy_primitives::U256;
 /// println!("{formatted}")); // 1.23 ETH
 /// ```
 pub fn format_erc20_balance(balance: U256, symbol: &str, decimals: u8) -> String {
+    format_erc20_amount(balance, symbol, decimals, 2)
+}
+
+/// Same as [`format_erc20_balance`] but rounded to `precision` decimal places, for amounts that
+/// are usually only a small fraction of a token (e.g. transaction fees).
+pub fn format_erc20_amount(amount: U256, symbol: &str, decimals: u8, precision: i64) -> String {
     use bigdecimal::num_bigint::{BigInt, Sign};
     use bigdecimal::BigDecimal;
 
-    // Round the balance to 2 decimal places
-    let value = BigInt::from_bytes_be(Sign::Plus, &balance.to_be_bytes::<{ U256::BYTES }>());
-    let decimal = BigDecimal::new(value, decimals as i64).round(2);
+    let value = BigInt::from_bytes_be(Sign::Plus, &amount.to_be_bytes::<{ U256::BYTES }>());
+    let decimal = BigDecimal::new(value, decimals as i64).round(precision);
 
     format!("{decimal} {symbol}")
 }
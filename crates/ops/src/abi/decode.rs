@@ -0,0 +1,343 @@
+use std::fmt;
+use std::slice::Iter;
+
+use alloy_primitives::U256;
+use color_eyre::eyre::{bail, eyre};
+use color_eyre::Result;
+use serde_json::{json, Map};
+use starknet::core::types::contract::AbiFunction;
+use starknet::core::types::FieldElement;
+
+use super::types::{self, Primitive, BOOL, BYTES_PER_WORD, BYTE_ARRAY, U256 as U256_TYPE, UNIT};
+use super::Abi;
+
+/// A value decoded from felts according to its Cairo type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decoded {
+    /// `felt252` and the felt-based types (e.g. `ContractAddress`).
+    Felt(FieldElement),
+    Unsigned(U256),
+    Signed(i128),
+    Bool(bool),
+    /// A `ByteArray`.
+    String(String),
+    Array(Vec<Decoded>),
+    Tuple(Vec<Decoded>),
+    Struct {
+        name: String,
+        members: Vec<(String, Decoded)>,
+    },
+    /// An enum variant, without a value if it's of the unit type.
+    Enum {
+        variant: String,
+        value: Option<Box<Decoded>>,
+    },
+}
+
+impl Decoded {
+    /// Converts the value to JSON. Integers are given as decimal strings, so that they don't lose
+    /// precision when read back.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Felt(value) => json!(format!("{value:#x}")),
+            Self::Unsigned(value) => json!(value.to_string()),
+            Self::Signed(value) => json!(value.to_string()),
+            Self::Bool(value) => json!(value),
+            Self::String(value) => json!(value),
+            Self::Array(values) | Self::Tuple(values) => {
+                values.iter().map(Self::to_json).collect::<Vec<_>>().into()
+            }
+            Self::Struct { members, .. } => members
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_json()))
+                .collect::<Map<_, _>>()
+                .into(),
+            Self::Enum { variant, value: Some(value) } => json!({ variant: value.to_json() }),
+            Self::Enum { variant, value: None } => json!(variant),
+        }
+    }
+}
+
+impl fmt::Display for Decoded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, values: &[Decoded]| {
+            values.iter().enumerate().try_for_each(|(idx, value)| {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{value}")
+            })
+        };
+
+        match self {
+            Self::Felt(value) => write!(f, "{value:#x}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Signed(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value:?}"),
+
+            Self::Array(values) => {
+                write!(f, "[")?;
+                list(f, values)?;
+                write!(f, "]")
+            }
+
+            Self::Tuple(values) => {
+                write!(f, "(")?;
+                list(f, values)?;
+                write!(f, ")")
+            }
+
+            Self::Struct { name, members } => {
+                write!(f, "{name} {{ ")?;
+                for (idx, (member, value)) in members.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{member}: {value}")?;
+                }
+                write!(f, " }}")
+            }
+
+            Self::Enum { variant, value: Some(value) } => write!(f, "{variant}({value})"),
+            Self::Enum { variant, value: None } => write!(f, "{variant}"),
+        }
+    }
+}
+
+impl Abi {
+    /// Decodes the return data of `function` according to its outputs.
+    pub fn decode_outputs(
+        &self,
+        function: &AbiFunction,
+        data: &[FieldElement],
+    ) -> Result<Vec<Decoded>> {
+        let mut data = data.iter();
+
+        let values = function
+            .outputs
+            .iter()
+            .map(|output| self.decode(&output.r#type, &mut data))
+            .collect::<Result<Vec<_>>>()?;
+
+        if data.len() > 0 {
+            bail!("{} unexpected felt(s) left after decoding the outputs", data.len());
+        }
+
+        Ok(values)
+    }
+
+    pub(crate) fn decode(&self, ty: &str, data: &mut Iter<'_, FieldElement>) -> Result<Decoded> {
+        if let Some(members) = types::tuple_members(ty) {
+            let values = members
+                .into_iter()
+                .map(|member| self.decode(member, data))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Decoded::Tuple(values));
+        }
+
+        if let Some(item) = types::array_item(ty) {
+            let len = to_usize(next(data, ty)?)?;
+            let values = (0..len).map(|_| self.decode(item, data)).collect::<Result<Vec<_>>>()?;
+            return Ok(Decoded::Array(values));
+        }
+
+        match ty {
+            U256_TYPE => {
+                let low = u128::try_from(next(data, ty)?).map_err(|_| eyre!("invalid u256"))?;
+                let high = u128::try_from(next(data, ty)?).map_err(|_| eyre!("invalid u256"))?;
+                return Ok(Decoded::Unsigned(U256::from(high) << 128 | U256::from(low)));
+            }
+
+            BYTE_ARRAY => return Ok(Decoded::String(decode_byte_array(data)?)),
+
+            BOOL => {
+                let value = next(data, ty)?;
+                return if value == FieldElement::ZERO {
+                    Ok(Decoded::Bool(false))
+                } else if value == FieldElement::ONE {
+                    Ok(Decoded::Bool(true))
+                } else {
+                    bail!("invalid bool {value:#x}")
+                };
+            }
+
+            _ => {}
+        }
+
+        if let Some(kind) = types::primitive(ty) {
+            let value = next(data, ty)?;
+            return match kind {
+                Primitive::Felt => Ok(Decoded::Felt(value)),
+                Primitive::Unsigned(_) => {
+                    let value = u128::try_from(value)
+                        .map_err(|_| eyre!("{value:#x} is out of range for `{ty}`"))?;
+                    Ok(Decoded::Unsigned(U256::from(value)))
+                }
+                Primitive::Signed(_) => Ok(Decoded::Signed(signed_value(value)?)),
+            };
+        }
+
+        if let Some(item) = self.structs.get(ty) {
+            let members = item
+                .members
+                .iter()
+                .map(|member| Ok((member.name.clone(), self.decode(&member.r#type, data)?)))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Decoded::Struct { name: types::short_type_name(ty), members });
+        }
+
+        if let Some(item) = self.enums.get(ty) {
+            let index = next(data, ty)?;
+            let variant = item
+                .variants
+                .get(to_usize(index)?)
+                .ok_or_else(|| eyre!("invalid variant index {index:#x} for enum `{ty}`"))?;
+
+            let value = match variant.r#type.as_str() {
+                UNIT => None,
+                ty => Some(Box::new(self.decode(ty, data)?)),
+            };
+            return Ok(Decoded::Enum { variant: variant.name.clone(), value });
+        }
+
+        bail!("unsupported type `{ty}`")
+    }
+}
+
+/// Decodes a Cairo `ByteArray`, replacing invalid UTF-8 sequences.
+pub fn decode_byte_array(data: &mut Iter<'_, FieldElement>) -> Result<String> {
+    let words = to_usize(next(data, BYTE_ARRAY)?)?;
+
+    let mut bytes = Vec::new();
+    for _ in 0..words {
+        let word = next(data, BYTE_ARRAY)?.to_bytes_be();
+        bytes.extend_from_slice(&word[32 - BYTES_PER_WORD..]);
+    }
+
+    let pending_word = next(data, BYTE_ARRAY)?.to_bytes_be();
+    let pending_len = to_usize(next(data, BYTE_ARRAY)?)?;
+    if pending_len >= BYTES_PER_WORD {
+        bail!("invalid ByteArray pending word length {pending_len}");
+    }
+    bytes.extend_from_slice(&pending_word[32 - pending_len..]);
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn next(data: &mut Iter<'_, FieldElement>, ty: &str) -> Result<FieldElement> {
+    data.next().copied().ok_or_else(|| eyre!("not enough data to decode `{ty}`"))
+}
+
+fn to_usize(value: FieldElement) -> Result<usize> {
+    u64::try_from(value)
+        .ok()
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or_else(|| eyre!("invalid length {value:#x}"))
+}
+
+/// Interprets a felt as a signed integer, where the upper half of the field is negative.
+fn signed_value(value: FieldElement) -> Result<i128> {
+    let out_of_range = || eyre!("{value:#x} is out of range for a signed integer");
+
+    match u128::try_from(value) {
+        Ok(value) => i128::try_from(value).map_err(|_| out_of_range()),
+        Err(_) => {
+            let abs = u128::try_from(FieldElement::ZERO - value).map_err(|_| out_of_range())?;
+            0i128.checked_sub_unsigned(abs).ok_or_else(out_of_range)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::core::utils::get_selector_from_name;
+    use starknet::macros::felt;
+
+    use super::*;
+    use crate::abi::encode_byte_array;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "example::Point",
+            "members": [
+                { "name": "x", "type": "core::integer::i32" },
+                { "name": "y", "type": "core::integer::i32" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<example::Point>",
+            "variants": [
+                { "name": "Some", "type": "example::Point" },
+                { "name": "None", "type": "()" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "balance_of",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u256" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "everything",
+            "inputs": [],
+            "outputs": [{
+                "type": "(core::byte_array::ByteArray, core::array::Span::<core::option::Option::<example::Point>>, core::bool)"
+            }],
+            "state_mutability": "view"
+        }
+    ]"#;
+
+    fn decode(function: &str, data: &[FieldElement]) -> Result<Vec<Decoded>> {
+        let abi = Abi::from_json(ABI).unwrap();
+        let function = abi.function(get_selector_from_name(function).unwrap()).unwrap();
+        abi.decode_outputs(function, data)
+    }
+
+    #[test]
+    fn decode_u256() {
+        let values = decode("balance_of", &[felt!("1"), felt!("1")]).unwrap();
+        assert_eq!(values[0].to_string(), "340282366920938463463374607431768211457");
+        assert_eq!(values[0].to_json(), json!("340282366920938463463374607431768211457"));
+
+        assert!(decode("balance_of", &[felt!("1")]).is_err());
+        assert!(decode("balance_of", &[felt!("1"), felt!("0"), felt!("0")]).is_err());
+    }
+
+    #[test]
+    fn decode_compound_types() {
+        let minus_one = FieldElement::ZERO - FieldElement::ONE;
+
+        let mut data = encode_byte_array("hello");
+        data.extend([felt!("2"), felt!("0"), felt!("1"), minus_one, felt!("1"), felt!("1")]);
+
+        let values = decode("everything", &data).unwrap();
+        assert_eq!(
+            values[0].to_string(),
+            r#"("hello", [Some(Point { x: 1, y: -1 }), None], true)"#
+        );
+        assert_eq!(
+            values[0].to_json(),
+            json!(["hello", [{ "Some": { "x": "1", "y": "-1" } }, "None"], true])
+        );
+    }
+
+    #[test]
+    fn decode_long_byte_array() {
+        let value = "abcdefghijklmnopqrstuvwxyz0123456789";
+        let data = encode_byte_array(value);
+        assert_eq!(decode_byte_array(&mut data.iter()).unwrap(), value);
+    }
+}
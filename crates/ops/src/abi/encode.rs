@@ -0,0 +1,391 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use starknet::core::types::contract::AbiFunction;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::cairo_short_string_to_felt;
+
+use super::types::{self, Primitive, BOOL, BYTES_PER_WORD, BYTE_ARRAY, U256, UNIT};
+use super::value::{parse_value, Value};
+use super::Abi;
+use crate::utility::split_u256;
+
+impl Abi {
+    /// Encodes the arguments of `function` into calldata, following Cairo's `Serde` layout.
+    ///
+    /// Each argument is a single value, given in the syntax of [`parse_value`].
+    pub fn encode_inputs(
+        &self,
+        function: &AbiFunction,
+        args: &[String],
+    ) -> Result<Vec<FieldElement>> {
+        let values = args.iter().map(|arg| parse_value(arg)).collect::<Result<Vec<_>>>()?;
+
+        if values.len() != function.inputs.len() {
+            let inputs = function
+                .inputs
+                .iter()
+                .map(|input| format!("{}: {}", input.name, types::short_type_name(&input.r#type)))
+                .collect::<Vec<_>>();
+
+            bail!(
+                "expected {} argument(s) for `{}({})` but got {}",
+                function.inputs.len(),
+                function.name,
+                inputs.join(", "),
+                values.len()
+            );
+        }
+
+        let mut calldata = Vec::new();
+        for (input, value) in function.inputs.iter().zip(&values) {
+            self.encode(&input.r#type, value, &mut calldata)
+                .with_context(|| format!("invalid value for argument `{}`", input.name))?;
+        }
+
+        Ok(calldata)
+    }
+
+    pub(crate) fn encode(
+        &self,
+        ty: &str,
+        value: &Value,
+        calldata: &mut Vec<FieldElement>,
+    ) -> Result<()> {
+        if let Some(members) = types::tuple_members(ty) {
+            let Value::Array(values) = value else { bail!("expected a tuple for `{ty}`") };
+            if values.len() != members.len() {
+                bail!("expected {} element(s) for `{ty}` but got {}", members.len(), values.len());
+            }
+
+            for (member, value) in members.iter().zip(values) {
+                self.encode(member, value, calldata)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(item) = types::array_item(ty) {
+            let Value::Array(values) = value else { bail!("expected an array for `{ty}`") };
+
+            calldata.push(values.len().into());
+            for value in values {
+                self.encode(item, value, calldata)?;
+            }
+            return Ok(());
+        }
+
+        match (ty, value.as_str()) {
+            (U256, Some(value)) => {
+                let (low, high) = split_u256(value)?;
+                calldata.extend([low, high]);
+                return Ok(());
+            }
+
+            (BYTE_ARRAY, Some(value)) => {
+                calldata.extend(encode_byte_array(value));
+                return Ok(());
+            }
+
+            (BOOL, Some(value)) => {
+                calldata.push(parse_bool(value)?);
+                return Ok(());
+            }
+
+            (_, Some(text)) => {
+                if let Some(kind) = types::primitive(ty) {
+                    calldata.push(encode_primitive(kind, ty, text, value)?);
+                    return Ok(());
+                }
+            }
+
+            _ => {}
+        }
+
+        if let Some(item) = self.structs.get(ty) {
+            let values = match value {
+                Value::Object(fields) => {
+                    if let Some((key, _)) =
+                        fields.iter().find(|(key, _)| !item.members.iter().any(|m| &m.name == key))
+                    {
+                        bail!("unknown member `{key}` for struct `{ty}`");
+                    }
+
+                    item.members
+                        .iter()
+                        .map(|member| {
+                            fields
+                                .iter()
+                                .find(|(key, _)| key == &member.name)
+                                .map(|(_, value)| value)
+                                .ok_or_else(|| eyre!("missing member `{}` for `{ty}`", member.name))
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                }
+
+                // members can also be given positionally
+                Value::Array(values) if values.len() == item.members.len() => {
+                    values.iter().collect()
+                }
+
+                _ => bail!("expected an object with the members of `{ty}`"),
+            };
+
+            for (member, value) in item.members.iter().zip(values) {
+                self.encode(&member.r#type, value, calldata)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(item) = self.enums.get(ty) {
+            let (name, value) = match value {
+                Value::Object(fields) if fields.len() == 1 => (&fields[0].0, Some(&fields[0].1)),
+                Value::Scalar(name) | Value::Str(name) => (name, None),
+                _ => bail!("expected a variant of `{ty}`, e.g. `{{\"Variant\": value}}`"),
+            };
+
+            let (index, variant) = item
+                .variants
+                .iter()
+                .enumerate()
+                .find(|(_, variant)| &variant.name == name)
+                .ok_or_else(|| eyre!("unknown variant `{name}` for enum `{ty}`"))?;
+
+            calldata.push(index.into());
+            match value {
+                Some(value) => self.encode(&variant.r#type, value, calldata)?,
+                None if variant.r#type == UNIT => {}
+                None => bail!("missing value for variant `{name}` of enum `{ty}`"),
+            }
+            return Ok(());
+        }
+
+        bail!("unsupported type `{ty}`")
+    }
+}
+
+/// Encodes a string as a Cairo `ByteArray`: the number of full 31-byte words, the words
+/// themselves, then the remaining bytes as a pending word along with their count.
+pub fn encode_byte_array(value: &str) -> Vec<FieldElement> {
+    let bytes = value.as_bytes();
+    let words = bytes.len() / BYTES_PER_WORD;
+    let (full, pending) = bytes.split_at(words * BYTES_PER_WORD);
+
+    let mut encoded = vec![words.into()];
+    encoded.extend(
+        full.chunks(BYTES_PER_WORD)
+            .map(|word| FieldElement::from_byte_slice_be(word).expect("fits in a felt")),
+    );
+    encoded.push(FieldElement::from_byte_slice_be(pending).expect("fits in a felt"));
+    encoded.push(pending.len().into());
+    encoded
+}
+
+/// Encodes the value of a single-felt type.
+fn encode_primitive(kind: Primitive, ty: &str, text: &str, value: &Value) -> Result<FieldElement> {
+    match kind {
+        Primitive::Felt => match value {
+            // quoted values are short strings for `felt252`
+            Value::Str(text) if ty == "core::felt252" => Ok(cairo_short_string_to_felt(text)?),
+            _ => parse_felt(text),
+        },
+
+        Primitive::Unsigned(bits) => {
+            let value = parse_unsigned(text)?;
+            if bits < 128 && value >> bits != 0 {
+                bail!("{text} is out of range for `u{bits}`");
+            }
+            Ok(FieldElement::from(value))
+        }
+
+        Primitive::Signed(bits) => {
+            let value = i128::from_str(text).with_context(|| format!("invalid integer {text}"))?;
+            let (min, max) = (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1);
+            if bits < 128 && (value < min || value > max) {
+                bail!("{text} is out of range for `i{bits}`");
+            }
+            Ok(signed_felt(value))
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Result<FieldElement> {
+    match value {
+        "true" | "True" | "1" => Ok(FieldElement::ONE),
+        "false" | "False" | "0" => Ok(FieldElement::ZERO),
+        _ => bail!("invalid bool {value}, expected `true` or `false`"),
+    }
+}
+
+fn parse_felt(value: &str) -> Result<FieldElement> {
+    match value.strip_prefix('-') {
+        Some(value) => Ok(FieldElement::ZERO - parse_felt(value)?),
+        None => FieldElement::from_str(value).map_err(|e| eyre!("invalid felt {value}: {e}")),
+    }
+}
+
+fn parse_unsigned(value: &str) -> Result<u128> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16),
+        None => u128::from_str(value),
+    };
+    parsed.with_context(|| format!("invalid unsigned integer {value}"))
+}
+
+fn signed_felt(value: i128) -> FieldElement {
+    let abs = FieldElement::from(value.unsigned_abs());
+    if value < 0 {
+        FieldElement::ZERO - abs
+    } else {
+        abs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::core::utils::get_selector_from_name;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "example::Point",
+            "members": [
+                { "name": "x", "type": "core::integer::i32" },
+                { "name": "y", "type": "core::integer::i32" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<core::felt252>",
+            "variants": [
+                { "name": "Some", "type": "core::felt252" },
+                { "name": "None", "type": "()" }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "example::IExample",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "transfer",
+                    "inputs": [
+                        { "name": "recipient", "type": "core::starknet::contract_address::ContractAddress" },
+                        { "name": "amount", "type": "core::integer::u256" }
+                    ],
+                    "outputs": [{ "type": "core::bool" }],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "everything",
+                    "inputs": [
+                        { "name": "points", "type": "core::array::Span::<example::Point>" },
+                        { "name": "name", "type": "core::byte_array::ByteArray" },
+                        { "name": "pair", "type": "(core::felt252, core::bool)" },
+                        { "name": "maybe", "type": "core::option::Option::<core::felt252>" },
+                        { "name": "nothing", "type": "core::option::Option::<core::felt252>" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }
+            ]
+        }
+    ]"#;
+
+    fn encode(function: &str, args: &[&str]) -> Result<Vec<FieldElement>> {
+        let abi = Abi::from_json(ABI).unwrap();
+        let function = abi.function(get_selector_from_name(function).unwrap()).unwrap();
+        abi.encode_inputs(function, &args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn encode_u256_as_a_single_value() {
+        let calldata = encode("transfer", &["0x123", "340282366920938463463374607431768211457"]);
+        assert_eq!(calldata.unwrap(), vec![felt!("0x123"), felt!("1"), felt!("1")]);
+
+        // structs given by members
+        let calldata = encode("transfer", &["0x123", "{low: 5, high: 0}"]);
+        assert_eq!(calldata.unwrap(), vec![felt!("0x123"), felt!("5"), felt!("0")]);
+
+        // an argument is a single value, even with commas in it
+        assert!(encode("transfer", &["0x123,5"]).is_err());
+    }
+
+    #[test]
+    fn encode_compound_types() {
+        let calldata = encode(
+            "everything",
+            &["[{x: 1, y: -1}, (2, 3)]", "hello", r#"("abc", true)"#, "{Some: 7}", "None"],
+        )
+        .unwrap();
+
+        let minus_one = FieldElement::ZERO - FieldElement::ONE;
+        assert_eq!(
+            calldata,
+            vec![
+                // points
+                felt!("2"),
+                felt!("1"),
+                minus_one,
+                felt!("2"),
+                felt!("3"),
+                // name
+                felt!("0"),
+                felt!("0x68656c6c6f"),
+                felt!("5"),
+                // pair
+                felt!("0x616263"),
+                felt!("1"),
+                // maybe
+                felt!("0"),
+                felt!("7"),
+                // nothing
+                felt!("1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        let err = encode("transfer", &["0x123"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 2 argument(s) for `transfer(recipient: ContractAddress, amount: u256)` but \
+             got 1"
+        );
+
+        assert!(encode("transfer", &["0x123", "-1"]).is_err());
+        assert!(encode("everything", &["[{x: 1}]", "a", "(1, true)", "None", "None"]).is_err());
+        assert!(encode("everything", &["[]", "a", "(1, true)", "Maybe", "None"]).is_err());
+    }
+
+    #[test]
+    fn encode_long_byte_array() {
+        let value = "abcdefghijklmnopqrstuvwxyz01234567";
+        let encoded = encode_byte_array(value);
+
+        assert_eq!(
+            encoded,
+            vec![
+                felt!("1"),
+                FieldElement::from_byte_slice_be(&value.as_bytes()[..31]).unwrap(),
+                FieldElement::from_byte_slice_be(&value.as_bytes()[31..]).unwrap(),
+                felt!("3"),
+            ]
+        );
+
+        assert_eq!(encode_byte_array(""), vec![felt!("0"), felt!("0"), felt!("0")]);
+    }
+}
@@ -0,0 +1,225 @@
+use probe_fmt::{DecodedEvent, DecodedEventMember};
+use starknet::core::types::contract::{AbiEventEnum, AbiEventStruct, EventFieldKind};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::starknet_keccak;
+
+use super::{types, Abi};
+
+impl Abi {
+    /// Decodes an event emitted by a contract with this ABI.
+    ///
+    /// The first key of an event is the selector of its variant in the contract's `Event` enum.
+    /// Variants that are themselves enums (e.g. the events of a component) add the selector of
+    /// their own variant to the keys, unless they're marked as `#[flat]`.
+    pub fn decode_event(
+        &self,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Option<DecodedEvent> {
+        let typed =
+            self.root_event_enums().find_map(|item| self.decode_event_enum(item, keys, data));
+        if typed.is_some() {
+            return typed;
+        }
+
+        // events of the contracts compiled before Cairo 2.0
+        let (selector, keys) = keys.split_first()?;
+        let event = self.untyped_events.get(selector)?;
+        let mut data = data.iter();
+        let members = event
+            .inputs
+            .iter()
+            .map(|input| member(self, &input.name, &input.r#type, false, &mut data))
+            .collect::<Option<Vec<_>>>()?;
+
+        (keys.is_empty() && data.len() == 0)
+            .then(|| DecodedEvent { name: event.name.clone(), members })
+    }
+
+    /// The event enums that aren't a variant of another event, i.e. the `Event` enum of the
+    /// contract.
+    fn root_event_enums(&self) -> impl Iterator<Item = &AbiEventEnum> {
+        self.event_enums.values().filter(|item| {
+            !self
+                .event_enums
+                .values()
+                .any(|other| other.variants.iter().any(|variant| variant.r#type == item.name))
+        })
+    }
+
+    fn decode_event_enum(
+        &self,
+        item: &AbiEventEnum,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Option<DecodedEvent> {
+        let (selector, rest) = keys.split_first()?;
+
+        item.variants.iter().find_map(|variant| {
+            // flat variants don't add their own selector to the keys
+            if !matches!(variant.kind, EventFieldKind::Nested) {
+                let inner = self.event_enums.get(&variant.r#type)?;
+                return self.decode_event_enum(inner, keys, data);
+            }
+
+            if starknet_keccak(variant.name.as_bytes()) != *selector {
+                return None;
+            }
+
+            if let Some(inner) = self.event_enums.get(&variant.r#type) {
+                let event = self.decode_event_enum(inner, rest, data)?;
+                let name = format!("{}::{}", variant.name, event.name);
+                return Some(DecodedEvent { name, ..event });
+            }
+
+            let item = self.event_structs.get(&variant.r#type)?;
+            let members = self.decode_event_struct(item, rest, data)?;
+            Some(DecodedEvent { name: variant.name.clone(), members })
+        })
+    }
+
+    fn decode_event_struct(
+        &self,
+        item: &AbiEventStruct,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Option<Vec<DecodedEventMember>> {
+        let (mut keys, mut data) = (keys.iter(), data.iter());
+
+        let members = item
+            .members
+            .iter()
+            .map(|field| {
+                let key = matches!(field.kind, EventFieldKind::Key);
+                let values = if key { &mut keys } else { &mut data };
+                member(self, &field.name, &field.r#type, key, values)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        (keys.len() == 0 && data.len() == 0).then_some(members)
+    }
+}
+
+fn member(
+    abi: &Abi,
+    name: &str,
+    ty: &str,
+    key: bool,
+    values: &mut std::slice::Iter<'_, FieldElement>,
+) -> Option<DecodedEventMember> {
+    let value = abi.decode(ty, values).ok()?;
+    Some(DecodedEventMember {
+        name: name.to_string(),
+        ty: types::short_type_name(ty),
+        key,
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::core::utils::get_selector_from_name;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "example::erc20::Transfer",
+            "kind": "struct",
+            "members": [
+                { "name": "from", "type": "core::starknet::contract_address::ContractAddress", "kind": "key" },
+                { "name": "to", "type": "core::starknet::contract_address::ContractAddress", "kind": "key" },
+                { "name": "value", "type": "core::integer::u256", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "example::erc20::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "Transfer", "type": "example::erc20::Transfer", "kind": "nested" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "example::contract::Upgraded",
+            "kind": "struct",
+            "members": [
+                { "name": "class_hash", "type": "core::starknet::class_hash::ClassHash", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "example::contract::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "ERC20Event", "type": "example::erc20::Event", "kind": "nested" },
+                { "name": "Upgraded", "type": "example::contract::Upgraded", "kind": "nested" }
+            ]
+        }
+    ]"#;
+
+    fn selector(name: &str) -> FieldElement {
+        get_selector_from_name(name).unwrap()
+    }
+
+    #[test]
+    fn decode_nested_event() {
+        let abi = Abi::from_json(ABI).unwrap();
+
+        let keys = [selector("ERC20Event"), selector("Transfer"), felt!("0x1"), felt!("0x2")];
+        let event = abi.decode_event(&keys, &[felt!("100"), felt!("0")]).unwrap();
+
+        assert_eq!(event.name, "ERC20Event::Transfer");
+        assert_eq!(
+            event.members,
+            vec![
+                DecodedEventMember {
+                    name: "from".to_string(),
+                    ty: "ContractAddress".to_string(),
+                    key: true,
+                    value: "0x1".to_string(),
+                },
+                DecodedEventMember {
+                    name: "to".to_string(),
+                    ty: "ContractAddress".to_string(),
+                    key: true,
+                    value: "0x2".to_string(),
+                },
+                DecodedEventMember {
+                    name: "value".to_string(),
+                    ty: "u256".to_string(),
+                    key: false,
+                    value: "100".to_string(),
+                },
+            ]
+        );
+
+        let event = abi.decode_event(&[selector("Upgraded")], &[felt!("0x1234")]).unwrap();
+        assert_eq!(event.name, "Upgraded");
+        assert_eq!(event.members[0].value, "0x1234");
+    }
+
+    #[test]
+    fn reject_mismatched_events() {
+        let abi = Abi::from_json(ABI).unwrap();
+
+        // unknown selector
+        assert!(abi.decode_event(&[selector("Approval")], &[]).is_none());
+        // missing key
+        let keys = [selector("ERC20Event"), selector("Transfer"), felt!("0x1")];
+        assert!(abi.decode_event(&keys, &[felt!("100"), felt!("0")]).is_none());
+        // leftover data
+        assert!(abi.decode_event(&[selector("Upgraded")], &[felt!("1"), felt!("2")]).is_none());
+    }
+}
@@ -0,0 +1,209 @@
+//! Encoding and decoding of typed values against a contract's Cairo 1 ABI.
+
+mod decode;
+mod encode;
+mod events;
+mod storage;
+mod types;
+pub mod value;
+
+use std::collections::{HashMap, HashSet};
+
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+use futures::{future, stream, StreamExt};
+use probe_args::commands::rpc::{parse_raw_calldata, RawCall};
+use probe_fmt::{DecodedEvent, EventDecoder};
+use starknet::accounts::Call;
+use starknet::core::types::contract::{
+    AbiEntry, AbiEnum, AbiEvent, AbiEventEnum, AbiEventStruct, AbiFunction, AbiStruct,
+    TypedAbiEvent, UntypedAbiEvent,
+};
+use starknet::core::types::{BlockId, BlockTag, ContractClass, FieldElement, StarknetError};
+use starknet::core::utils::{get_selector_from_name, starknet_keccak};
+use starknet::providers::{Provider, ProviderError};
+
+pub use self::decode::{decode_byte_array, Decoded};
+pub use self::encode::encode_byte_array;
+pub use self::storage::StorageLayout;
+use crate::utils::CONCURRENCY;
+
+/// The functions, types and events declared in a Sierra class ABI.
+#[derive(Debug, Clone, Default)]
+pub struct Abi {
+    functions: HashMap<FieldElement, AbiFunction>,
+    structs: HashMap<String, AbiStruct>,
+    enums: HashMap<String, AbiEnum>,
+    event_structs: HashMap<String, AbiEventStruct>,
+    event_enums: HashMap<String, AbiEventEnum>,
+    /// The events of the contracts compiled before Cairo 2.0, by selector.
+    untyped_events: HashMap<FieldElement, UntypedAbiEvent>,
+}
+
+impl Abi {
+    pub fn new(entries: Vec<AbiEntry>) -> Self {
+        let mut abi = Self::default();
+        abi.extend(entries);
+        abi
+    }
+
+    /// Parses the ABI of a Sierra class, as found in its `abi` field.
+    pub fn from_json(abi: &str) -> Result<Self> {
+        let entries: Vec<AbiEntry> = serde_json::from_str(abi).context("invalid contract ABI")?;
+        Ok(Self::new(entries))
+    }
+
+    /// Returns the function with the given selector.
+    pub fn function(&self, selector: FieldElement) -> Option<&AbiFunction> {
+        self.functions.get(&selector)
+    }
+
+    fn extend(&mut self, entries: Vec<AbiEntry>) {
+        for entry in entries {
+            match entry {
+                AbiEntry::Function(function) => {
+                    // skip the names that aren't valid entrypoint names
+                    if let Ok(selector) = get_selector_from_name(&function.name) {
+                        self.functions.insert(selector, function);
+                    }
+                }
+                AbiEntry::Struct(item) => {
+                    self.structs.insert(item.name.clone(), item);
+                }
+                AbiEntry::Enum(item) => {
+                    self.enums.insert(item.name.clone(), item);
+                }
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(item))) => {
+                    self.event_structs.insert(item.name.clone(), item);
+                }
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(item))) => {
+                    self.event_enums.insert(item.name.clone(), item);
+                }
+                AbiEntry::Event(AbiEvent::Untyped(event)) => {
+                    self.untyped_events.insert(starknet_keccak(event.name.as_bytes()), event);
+                }
+                AbiEntry::Interface(interface) => self.extend(interface.items),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Fetches the ABI of the contract deployed at `address`.
+///
+/// Returns `None` if the contract doesn't exist or is a legacy (Cairo 0) contract.
+pub async fn fetch_abi<P: Provider>(
+    provider: &P,
+    block_id: BlockId,
+    address: FieldElement,
+) -> Result<Option<Abi>> {
+    match provider.get_class_at(block_id, address).await {
+        Ok(ContractClass::Sierra(class)) => Ok(Some(Abi::from_json(&class.abi)?)),
+        Ok(ContractClass::Legacy(_)) => Ok(None),
+        Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Encodes the arguments of the function with the given selector against the ABI, or parses them
+/// as raw felts if the function isn't found in it (or there's no ABI at all).
+pub fn encode_calldata(
+    abi: Option<&Abi>,
+    selector: FieldElement,
+    args: &[String],
+) -> Result<Vec<FieldElement>> {
+    match abi.and_then(|abi| abi.function(selector).map(|function| (abi, function))) {
+        Some((abi, function)) => abi.encode_inputs(function, args),
+        None => parse_raw_calldata(args),
+    }
+}
+
+/// Encodes the calldata of the calls against the ABIs of their target contracts.
+pub async fn encode_calls<P: Provider>(provider: &P, calls: Vec<RawCall>) -> Result<Vec<Call>> {
+    let mut abis: HashMap<FieldElement, Option<Abi>> = HashMap::new();
+    let mut encoded = Vec::with_capacity(calls.len());
+
+    for (idx, RawCall { to, selector, calldata }) in calls.into_iter().enumerate() {
+        if !abis.contains_key(&to) {
+            let abi = fetch_abi(provider, BlockId::Tag(BlockTag::Pending), to).await?;
+            abis.insert(to, abi);
+        }
+
+        let calldata = encode_calldata(abis[&to].as_ref(), selector, &calldata)
+            .with_context(|| format!("failed to encode the calldata of call {}", idx + 1))?;
+        encoded.push(Call { to, selector, calldata });
+    }
+
+    Ok(encoded)
+}
+
+/// The ABIs of the contracts that emitted some events, cached by class hash so that contracts
+/// sharing a class (e.g. accounts) are only fetched once.
+#[derive(Debug, Default)]
+pub struct EventAbis {
+    /// The class of each contract in the blocks it emitted events in, `None` being pending, so
+    /// that the events emitted before a class upgrade are decoded against the previous class.
+    class_hashes: HashMap<(FieldElement, Option<u64>), FieldElement>,
+    abis: HashMap<FieldElement, Option<Abi>>,
+}
+
+impl EventAbis {
+    /// Fetches the ABIs of the contracts that emitted events, given as the address of the
+    /// contract and the block of the event.
+    ///
+    /// Failing to fetch an ABI isn't an error: the events of that contract just aren't decoded.
+    pub async fn fetch<P: Provider>(
+        provider: &P,
+        emitters: impl IntoIterator<Item = (FieldElement, Option<u64>)>,
+    ) -> Self {
+        let emitters = emitters.into_iter().collect::<HashSet<_>>();
+
+        let class_hashes: HashMap<_, _> = stream::iter(emitters)
+            .map(|(address, block)| async move {
+                let class_hash = provider.get_class_hash_at(block_id(block), address).await.ok()?;
+                Some(((address, block), class_hash))
+            })
+            .buffered(CONCURRENCY)
+            .filter_map(future::ready)
+            .collect()
+            .await;
+
+        // a class can be fetched at any block where a contract has it
+        let classes = class_hashes
+            .iter()
+            .map(|(&(_, block), &class_hash)| (class_hash, block))
+            .collect::<HashMap<_, _>>();
+
+        let abis = stream::iter(classes)
+            .map(|(class_hash, block)| async move {
+                let abi = match provider.get_class(block_id(block), class_hash).await {
+                    Ok(ContractClass::Sierra(class)) => Abi::from_json(&class.abi).ok(),
+                    _ => None,
+                };
+                (class_hash, abi)
+            })
+            .buffered(CONCURRENCY)
+            .collect()
+            .await;
+
+        Self { class_hashes, abis }
+    }
+}
+
+impl EventDecoder for EventAbis {
+    fn decode_event(
+        &self,
+        from_address: FieldElement,
+        block_number: Option<u64>,
+        keys: &[FieldElement],
+        data: &[FieldElement],
+    ) -> Option<DecodedEvent> {
+        let class_hash = self.class_hashes.get(&(from_address, block_number))?;
+        self.abis.get(class_hash)?.as_ref()?.decode_event(keys, data)
+    }
+}
+
+/// The id of a block given by number, or the pending block.
+fn block_id(block_number: Option<u64>) -> BlockId {
+    block_number.map_or(BlockId::Tag(BlockTag::Pending), BlockId::Number)
+}
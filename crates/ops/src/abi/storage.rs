@@ -0,0 +1,571 @@
+//! Reading typed values from the storage of Cairo 1 contracts.
+
+use std::fs;
+use std::path::Path;
+
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use serde::Deserialize;
+use serde_json::{Map, Value as Json};
+use starknet::core::crypto::pedersen_hash;
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{normalize_address, starknet_keccak};
+use starknet::macros::felt;
+use starknet_crypto::poseidon_permute_comp;
+
+use super::decode::Decoded;
+use super::types::{self, BOOL, BYTES_PER_WORD, BYTE_ARRAY, U256, UNIT};
+use super::value::{parse_value, parse_values};
+use super::Abi;
+
+/// The corelib's `BYTE_ARRAY_MAGIC`, which the storage address of the words of a `ByteArray` is
+/// derived from.
+const BYTE_ARRAY_MAGIC: FieldElement =
+    felt!("0x46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3");
+
+/// The storage variables of a contract, along with the ABI declaring their types.
+///
+/// It's read from a JSON file with a `storage` object, which maps the name of each variable to
+/// its Cairo type (e.g. `"balances": "Map<ContractAddress, u256>"`), or to a nested object for the
+/// storage nodes and `#[substorage(v1)]` components. The file can be a Sierra class artifact, as
+/// built by Scarb, in which case the ABI of the class resolves the struct and enum types.
+#[derive(Debug, Clone)]
+pub struct StorageLayout {
+    pub abi: Abi,
+    variables: Map<String, Json>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LayoutFile {
+    #[serde(default)]
+    abi: Option<Json>,
+    #[serde(default)]
+    storage: Map<String, Json>,
+}
+
+/// A segment of the path of a storage variable, e.g. `[0x123]` or `.owner`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    Key(&'a str),
+    Member(&'a str),
+}
+
+/// What a storage path points to while it's being resolved.
+enum Node<'a> {
+    /// A storage node (or component), whose members are variables.
+    Storage(&'a Map<String, Json>),
+    /// A value of the given type.
+    Type(String),
+}
+
+impl StorageLayout {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_json(&content).with_context(|| format!("invalid layout {}", path.display()))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        let file: LayoutFile = serde_json::from_str(content)?;
+
+        let entries: Vec<AbiEntry> = match file.abi {
+            // the ABI of the classes returned by the node is itself a JSON string
+            Some(Json::String(abi)) => serde_json::from_str(&abi)?,
+            Some(abi) => serde_json::from_value(abi)?,
+            None => Vec::new(),
+        };
+
+        if file.storage.is_empty() {
+            bail!("no variables in the `storage` of the layout");
+        }
+
+        Ok(Self { abi: Abi::new(entries), variables: file.storage })
+    }
+
+    /// Resolves the path of a storage variable, e.g. `balances[0x123]`, `config.owner` or
+    /// `erc20.allowances[(0x1, 0x2)]`, into its address and type.
+    pub fn resolve(&self, path: &str) -> Result<(FieldElement, String)> {
+        let (name, segments) = parse_path(path)?;
+
+        let mut node = self.node(&self.variables, name)?;
+        let mut hash = starknet_keccak(name.as_bytes());
+        let mut offset = 0;
+
+        for segment in segments {
+            node = match (node, segment) {
+                // the members of a storage node are at `pedersen(node, sn_keccak(member))`
+                (Node::Storage(members), Segment::Member(member)) => {
+                    hash = pedersen_hash(&hash, &starknet_keccak(member.as_bytes()));
+                    self.node(members, member)?
+                }
+
+                (Node::Storage(_), Segment::Key(_)) => {
+                    bail!("a storage node can't be indexed, select one of its members in `{path}`")
+                }
+
+                (Node::Type(ty), Segment::Key(key)) => {
+                    if offset > 0 {
+                        bail!("`{ty}` is stored in a struct and can't be indexed in `{path}`");
+                    }
+                    Node::Type(self.abi.index_storage(&ty, key, &mut hash)?)
+                }
+
+                (Node::Type(ty), Segment::Member(member)) => {
+                    Node::Type(self.abi.member_offset(&ty, member, &mut offset)?)
+                }
+            };
+        }
+
+        match node {
+            Node::Type(ty) => Ok((normalize_address(hash) + FieldElement::from(offset), ty)),
+            Node::Storage(_) => bail!("`{path}` is a storage node, select one of its members"),
+        }
+    }
+
+    fn node<'a>(&self, members: &'a Map<String, Json>, name: &str) -> Result<Node<'a>> {
+        match members.get(name) {
+            Some(Json::Object(members)) => Ok(Node::Storage(members)),
+            Some(Json::String(ty)) => Ok(Node::Type(self.abi.resolve_type(ty))),
+            Some(_) => bail!("invalid layout of `{name}`, expected a type or an object"),
+            None => {
+                let names = members.keys().cloned().collect::<Vec<_>>();
+                bail!("unknown storage variable `{name}`, expected one of: {}", names.join(", "))
+            }
+        }
+    }
+}
+
+/// Splits a storage path into the name of the variable and the keys and members that follow it.
+fn parse_path(path: &str) -> Result<(&str, Vec<Segment<'_>>)> {
+    let path = path.trim();
+    let end = path.find(['[', '.']).unwrap_or(path.len());
+    let (name, mut rest) = path.split_at(end);
+    if name.is_empty() {
+        bail!("missing variable name in `{path}`");
+    }
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(member) = rest.strip_prefix('.') {
+            let end = member.find(['[', '.']).unwrap_or(member.len());
+            if end == 0 {
+                bail!("missing member name in `{path}`");
+            }
+            segments.push(Segment::Member(&member[..end]));
+            rest = &member[end..];
+        } else if rest.starts_with('[') {
+            // keys can contain brackets themselves, e.g. `[[1, 2]]`
+            let mut depth = 0;
+            let end = rest
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .map(|(idx, _)| idx)
+                .ok_or_else(|| eyre!("unclosed `[` in `{path}`"))?;
+
+            segments.push(Segment::Key(&rest[1..end]));
+            rest = &rest[end + 1..];
+        } else {
+            bail!("unexpected `{rest}` in `{path}`, expected `[key]` or `.member`");
+        }
+    }
+
+    Ok((name, segments))
+}
+
+impl Abi {
+    /// Resolves the short names that can be used for types, e.g. `u256`, `ContractAddress`,
+    /// `Map<felt252, u8>`, or the name of a struct or an enum of the ABI without its module path.
+    pub(crate) fn resolve_type(&self, ty: &str) -> String {
+        let ty = ty.trim();
+
+        if let Some(members) = types::tuple_members(ty) {
+            let members = members.iter().map(|member| self.resolve_type(member));
+            return format!("({})", members.collect::<Vec<_>>().join(", "));
+        }
+
+        if let Some((name, args)) = types::generic_args(ty) {
+            let args = args.iter().map(|arg| self.resolve_type(arg)).collect::<Vec<_>>();
+            let name = match types::last_segment(name) {
+                "Array" => "core::array::Array".to_string(),
+                "Span" => "core::array::Span".to_string(),
+                "Map" | "LegacyMap" => "core::starknet::storage::Map".to_string(),
+                "Vec" => "core::starknet::storage::Vec".to_string(),
+                _ => self.resolve_name(name, true),
+            };
+            return format!("{name}::<{}>", args.join(", "));
+        }
+
+        match ty {
+            "felt252" => "core::felt252".to_string(),
+            "bool" => BOOL.to_string(),
+            "usize" => "core::integer::u32".to_string(),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "u256" | "i8" | "i16" | "i32" | "i64"
+            | "i128" => format!("core::integer::{ty}"),
+            "ContractAddress" => "core::starknet::contract_address::ContractAddress".to_string(),
+            "ClassHash" => "core::starknet::class_hash::ClassHash".to_string(),
+            "EthAddress" => "core::starknet::eth_address::EthAddress".to_string(),
+            "ByteArray" => BYTE_ARRAY.to_string(),
+            _ => self.resolve_name(ty, false),
+        }
+    }
+
+    /// Finds the struct or enum of the ABI whose name ends with `name`, if there's a single one.
+    fn resolve_name(&self, name: &str, generic: bool) -> String {
+        if name.contains("::") {
+            return name.to_string();
+        }
+
+        let mut candidates = self
+            .structs
+            .keys()
+            .chain(self.enums.keys())
+            // the generic types are declared once for each of their instantiations
+            .map(|full| match full.split_once("::<") {
+                Some((base, _)) if generic => base,
+                _ => full.as_str(),
+            })
+            .filter(|full| types::last_segment(full) == name)
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        match candidates.as_slice() {
+            [full] => full.to_string(),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Serializes a storage key of type `ty`, resolved as in [`Abi::resolve_type`], the way `Serde`
+    /// does. The key is a single value in the syntax of [`parse_value`].
+    pub fn encode_key(&self, ty: &str, key: &str) -> Result<Vec<FieldElement>> {
+        let ty = self.resolve_type(ty);
+
+        let mut felts = Vec::new();
+        self.encode(&ty, &parse_value(key)?, &mut felts).with_context(|| {
+            format!("invalid key `{key}` for `{}`", types::short_type_name(&ty))
+        })?;
+
+        Ok(felts)
+    }
+
+    /// Hashes a key into the address of a `Map` (or an index into the one of a `Vec`), returning
+    /// the type of the values.
+    fn index_storage(&self, ty: &str, key: &str, hash: &mut FieldElement) -> Result<String> {
+        let (name, args) = types::generic_args(ty)
+            .ok_or_else(|| eyre!("`{ty}` is neither a `Map` nor a `Vec`, it can't be indexed"))?;
+
+        let (key_ty, value_ty) = match (types::last_segment(name), args.as_slice()) {
+            ("Map", [key_ty, value_ty]) => (*key_ty, *value_ty),
+            ("Vec", [item_ty]) => ("core::integer::u64", *item_ty),
+            _ => bail!("`{ty}` is neither a `Map` nor a `Vec`, it can't be indexed"),
+        };
+
+        let values = parse_values(key)?;
+        let [value] = values.as_slice() else { bail!("expected a single key in `[{key}]`") };
+
+        let mut felts = Vec::new();
+        self.encode(key_ty, value, &mut felts)
+            .with_context(|| format!("invalid key `{key}` for `{}`", types::short_type_name(ty)))?;
+
+        *hash = felts.iter().fold(*hash, |hash, felt| pedersen_hash(&hash, felt));
+        Ok(value_ty.to_string())
+    }
+
+    /// Adds the offset of a struct member, whose members are stored in consecutive slots, returning
+    /// the type of the member.
+    fn member_offset(&self, ty: &str, member: &str, offset: &mut u64) -> Result<String> {
+        let item = self.structs.get(ty).ok_or_else(|| {
+            eyre!("`{ty}` isn't a struct of the ABI, it has no member `{member}`")
+        })?;
+
+        for field in &item.members {
+            if field.name == member {
+                return Ok(field.r#type.clone());
+            }
+            *offset += self.storage_size(&field.r#type)?;
+        }
+
+        bail!("unknown member `{member}` for struct `{ty}`")
+    }
+
+    /// Returns the number of consecutive storage slots taken by a value of the given type.
+    pub fn storage_size(&self, ty: &str) -> Result<u64> {
+        if let Some(members) = types::tuple_members(ty) {
+            return members.iter().map(|member| self.storage_size(member)).sum();
+        }
+
+        match ty {
+            U256 => return Ok(2),
+            // the bytes of a `ByteArray` are stored away from its length
+            BYTE_ARRAY | BOOL => return Ok(1),
+            _ if types::primitive(ty).is_some() => return Ok(1),
+            _ => {}
+        }
+
+        if let Some(item) = self.structs.get(ty) {
+            return item.members.iter().map(|member| self.storage_size(&member.r#type)).sum();
+        }
+
+        if let Some(item) = self.enums.get(ty) {
+            let mut size = 0;
+            for variant in &item.variants {
+                size = size.max(self.storage_size(&variant.r#type)?);
+            }
+            // the index of the variant comes first
+            return Ok(1 + size);
+        }
+
+        bail!("`{}` can't be read from storage", types::short_type_name(ty))
+    }
+
+    /// Reads a value of the given type stored at `address`, with `read` returning the value of a
+    /// single storage slot.
+    pub fn read_storage(
+        &self,
+        ty: &str,
+        address: FieldElement,
+        read: &mut dyn FnMut(FieldElement) -> Result<FieldElement>,
+    ) -> Result<Decoded> {
+        let ty = self.resolve_type(ty);
+
+        if let Some((name, _)) = types::generic_args(&ty) {
+            match types::last_segment(name) {
+                "Map" => bail!("a `Map` has no value of its own, index it with `[key]`"),
+                "Vec" => {
+                    bail!("a `Vec` only stores its length as a `u64`, index it with `[index]`")
+                }
+                _ => {}
+            }
+        }
+
+        // the slots are read into the `Serde` layout of the value, which is then decoded
+        let mut felts = Vec::new();
+        self.load(&ty, address, &mut 0, read, &mut felts)?;
+        self.decode(&ty, &mut felts.iter())
+    }
+
+    fn load(
+        &self,
+        ty: &str,
+        base: FieldElement,
+        offset: &mut u64,
+        read: &mut dyn FnMut(FieldElement) -> Result<FieldElement>,
+        felts: &mut Vec<FieldElement>,
+    ) -> Result<()> {
+        if let Some(members) = types::tuple_members(ty) {
+            for member in members {
+                self.load(member, base, offset, read, felts)?;
+            }
+            return Ok(());
+        }
+
+        match ty {
+            U256 => {
+                felts.push(read_slot(base, offset, read)?);
+                felts.push(read_slot(base, offset, read)?);
+                return Ok(());
+            }
+
+            BYTE_ARRAY => {
+                let address = base + FieldElement::from(*offset);
+                *offset += 1;
+                return load_byte_array(address, read, felts);
+            }
+
+            _ if ty == BOOL || types::primitive(ty).is_some() => {
+                felts.push(read_slot(base, offset, read)?);
+                return Ok(());
+            }
+
+            _ => {}
+        }
+
+        if let Some(item) = self.structs.get(ty) {
+            for member in &item.members {
+                self.load(&member.r#type, base, offset, read, felts)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(item) = self.enums.get(ty) {
+            let start = *offset;
+            let index = read_slot(base, offset, read)?;
+            felts.push(index);
+
+            let variant = usize::try_from(u64::try_from(index).unwrap_or(u64::MAX))
+                .ok()
+                .and_then(|index| item.variants.get(index))
+                .ok_or_else(|| eyre!("invalid variant index {index:#x} for enum `{ty}`"))?;
+            if variant.r#type != UNIT {
+                self.load(&variant.r#type, base, offset, read, felts)?;
+            }
+
+            // the enum takes the size of its largest variant
+            *offset = start + self.storage_size(ty)?;
+            return Ok(());
+        }
+
+        bail!("`{}` can't be read from storage", types::short_type_name(ty))
+    }
+}
+
+/// Reads the slot at `base + offset`, moving the offset to the next slot.
+fn read_slot(
+    base: FieldElement,
+    offset: &mut u64,
+    read: &mut dyn FnMut(FieldElement) -> Result<FieldElement>,
+) -> Result<FieldElement> {
+    let value = read(base + FieldElement::from(*offset))?;
+    *offset += 1;
+    Ok(value)
+}
+
+/// Reads a `ByteArray` whose length is stored at `address`, into its `Serde` layout.
+///
+/// The 31-byte words are stored in chunks of 256 slots, each at `hades(address, chunk,
+/// BYTE_ARRAY_MAGIC)`.
+fn load_byte_array(
+    address: FieldElement,
+    read: &mut dyn FnMut(FieldElement) -> Result<FieldElement>,
+    felts: &mut Vec<FieldElement>,
+) -> Result<()> {
+    let len = read(address)?;
+    let len = u32::try_from(len).map_err(|_| eyre!("invalid ByteArray length {len:#x}"))?;
+    let (full_words, pending_len) = (len / BYTES_PER_WORD as u32, len % BYTES_PER_WORD as u32);
+
+    let mut word = |index: u32| {
+        let mut state = [address, (index / 256).into(), BYTE_ARRAY_MAGIC];
+        poseidon_permute_comp(&mut state);
+        read(normalize_address(state[0]) + FieldElement::from(index % 256))
+    };
+
+    felts.push(full_words.into());
+    for index in 0..full_words {
+        felts.push(word(index)?);
+    }
+
+    let pending_word = if pending_len > 0 { word(full_words)? } else { FieldElement::ZERO };
+    felts.extend([pending_word, pending_len.into()]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy_primitives::U256;
+    use starknet::core::utils::get_storage_var_address;
+    use starknet::macros::felt;
+
+    use super::*;
+    use crate::abi::encode_byte_array;
+
+    const LAYOUT: &str = r#"{
+        "abi": [
+            {
+                "type": "struct",
+                "name": "example::Config",
+                "members": [
+                    { "name": "fee", "type": "core::integer::u256" },
+                    { "name": "owner", "type": "core::starknet::contract_address::ContractAddress" }
+                ]
+            },
+            {
+                "type": "enum",
+                "name": "example::Status",
+                "variants": [
+                    { "name": "Active", "type": "core::integer::u256" },
+                    { "name": "Paused", "type": "()" }
+                ]
+            }
+        ],
+        "storage": {
+            "config": "Config",
+            "balances": "Map<ContractAddress, u256>",
+            "allowances": "LegacyMap<(ContractAddress, ContractAddress), u256>",
+            "holders": "Vec<ContractAddress>",
+            "name": "ByteArray",
+            "status": "Map<felt252, Status>",
+            "erc20": {
+                "supply": "u256"
+            }
+        }
+    }"#;
+
+    #[test]
+    fn resolve_storage_paths() {
+        let layout = StorageLayout::from_json(LAYOUT).unwrap();
+
+        let (address, ty) = layout.resolve("balances[0x123]").unwrap();
+        assert_eq!(address, get_storage_var_address("balances", &[felt!("0x123")]).unwrap());
+        assert_eq!(ty, "core::integer::u256");
+
+        let (address, _) = layout.resolve("allowances[(0x1, 0x2)]").unwrap();
+        let expected = get_storage_var_address("allowances", &[felt!("0x1"), felt!("0x2")]);
+        assert_eq!(address, expected.unwrap());
+
+        let (address, ty) = layout.resolve("config.owner").unwrap();
+        assert_eq!(address, get_storage_var_address("config", &[]).unwrap() + felt!("2"));
+        assert_eq!(ty, "core::starknet::contract_address::ContractAddress");
+
+        let (address, _) = layout.resolve("holders[3]").unwrap();
+        assert_eq!(address, get_storage_var_address("holders", &[felt!("3")]).unwrap());
+
+        let (address, _) = layout.resolve("erc20.supply").unwrap();
+        let node = pedersen_hash(&starknet_keccak(b"erc20"), &starknet_keccak(b"supply"));
+        assert_eq!(address, normalize_address(node));
+
+        assert!(layout.resolve("unknown").is_err());
+        assert!(layout.resolve("erc20").is_err());
+        assert!(layout.resolve("config[1]").is_err());
+        assert!(layout.resolve("balances[0x1").is_err());
+        assert!(layout.resolve("balances[0x1]x").is_err());
+    }
+
+    #[test]
+    fn read_storage_values() {
+        let layout = StorageLayout::from_json(LAYOUT).unwrap();
+        let abi = &layout.abi;
+        assert_eq!(abi.storage_size("example::Config").unwrap(), 3);
+        assert_eq!(abi.storage_size("example::Status").unwrap(), 3);
+
+        let base = felt!("0x1000");
+        let mut storage = HashMap::from([
+            (base, felt!("5")),
+            (base + felt!("1"), felt!("1")),
+            (base + felt!("2"), felt!("0xabc")),
+        ]);
+
+        let mut read = |key| Ok(storage.get(&key).copied().unwrap_or_default());
+        let value = abi.read_storage("Config", base, &mut read).unwrap();
+        let Decoded::Struct { members, .. } = value else { panic!("expected a struct") };
+        assert_eq!(members[0].1, Decoded::Unsigned(U256::from(1) << 128 | U256::from(5)));
+        assert_eq!(members[1].1, Decoded::Felt(felt!("0xabc")));
+
+        // a string spanning a full word and a pending one
+        let string = "a string that takes more than one word";
+        let serialized = encode_byte_array(string);
+        storage.insert(base, felt!("38"));
+        // the first chunk of the words of a `ByteArray` at 0x1000, from a reference implementation
+        // of the corelib's `inner_byte_array_pointer`
+        let chunk = felt!("0x54fdb7448ad996ca72e553d34751fddffc72e420201873e8da987f63995d7c");
+        storage.insert(chunk, serialized[1]);
+        storage.insert(chunk + felt!("1"), serialized[2]);
+
+        let mut read = |key| Ok(storage.get(&key).copied().unwrap_or_default());
+        let value = abi.read_storage("ByteArray", base, &mut read).unwrap();
+        assert_eq!(value, Decoded::String(string.to_string()));
+
+        assert!(abi.read_storage("Map<felt252, u8>", base, &mut read).is_err());
+    }
+}
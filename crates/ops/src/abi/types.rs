@@ -0,0 +1,155 @@
+//! Helpers to inspect the type names found in Cairo 1 ABIs.
+
+pub const U256: &str = "core::integer::u256";
+pub const BOOL: &str = "core::bool";
+pub const BYTE_ARRAY: &str = "core::byte_array::ByteArray";
+pub const UNIT: &str = "()";
+
+const ARRAY: &str = "core::array::Array::<";
+const SPAN: &str = "core::array::Span::<";
+
+/// The number of bytes stored in each full word of a `ByteArray`.
+pub const BYTES_PER_WORD: usize = 31;
+
+/// The types that are serialized as a single felt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    /// `felt252` and the felt-based types (e.g. `ContractAddress`).
+    Felt,
+    /// An unsigned integer with the given number of bits, up to 128.
+    Unsigned(u32),
+    /// A signed integer with the given number of bits, up to 128.
+    Signed(u32),
+}
+
+/// Returns the kind of single-felt type that `ty` is, if any.
+pub fn primitive(ty: &str) -> Option<Primitive> {
+    if ty.contains('<') {
+        return None;
+    }
+
+    let name = ty.rsplit("::").next().unwrap_or(ty);
+    match name {
+        "felt252" | "ContractAddress" | "ClassHash" | "EthAddress" | "StorageAddress" => {
+            Some(Primitive::Felt)
+        }
+        "usize" => Some(Primitive::Unsigned(32)),
+        "u8" | "u16" | "u32" | "u64" | "u128" => name[1..].parse().ok().map(Primitive::Unsigned),
+        "i8" | "i16" | "i32" | "i64" | "i128" => name[1..].parse().ok().map(Primitive::Signed),
+        _ => None,
+    }
+}
+
+/// Returns the item type of `Array` and `Span` types, e.g. `core::felt252` for
+/// `core::array::Array::<core::felt252>`.
+pub fn array_item(ty: &str) -> Option<&str> {
+    ty.strip_prefix(ARRAY).or_else(|| ty.strip_prefix(SPAN))?.strip_suffix('>')
+}
+
+/// Returns the member types of a tuple type, e.g. `(core::felt252, core::bool)`.
+pub fn tuple_members(ty: &str) -> Option<Vec<&str>> {
+    let inner = ty.strip_prefix('(')?.strip_suffix(')')?;
+    Some(split_types(inner))
+}
+
+/// Splits a generic type into its name and type arguments, e.g. `core::starknet::storage::Map`
+/// and `[K, V]` for `core::starknet::storage::Map::<K, V>`. The `::` before the arguments is
+/// optional, so that the types can be written as in Cairo code.
+pub fn generic_args(ty: &str) -> Option<(&str, Vec<&str>)> {
+    if ty.starts_with('(') {
+        return None;
+    }
+
+    let (name, args) = ty.split_once('<')?;
+    let args = args.strip_suffix('>')?;
+    Some((name.trim().trim_end_matches("::"), split_types(args)))
+}
+
+/// Returns the last segment of a type path, e.g. `Map` for `core::starknet::storage::Map`.
+pub fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Splits a comma separated list of types, ignoring the commas nested in other types.
+fn split_types(list: &str) -> Vec<&str> {
+    if list.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut types = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (idx, c) in list.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                types.push(list[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(list[start..].trim());
+
+    types
+}
+
+/// Strips the module paths of a type, e.g. `Array<felt252>` for
+/// `core::array::Array::<core::felt252>`.
+pub fn short_type_name(ty: &str) -> String {
+    let mut name = String::new();
+    let mut segment = String::new();
+
+    let last_segment = |path: &str| {
+        path.trim_end_matches("::").rsplit("::").next().unwrap_or_default().to_string()
+    };
+
+    for c in ty.chars() {
+        match c {
+            '<' | '>' | '(' | ')' | ',' | ' ' => {
+                name.push_str(&last_segment(&segment));
+                segment.clear();
+                name.push(c);
+            }
+            _ => segment.push(c),
+        }
+    }
+    name.push_str(&last_segment(&segment));
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspect_types() {
+        assert_eq!(primitive("core::integer::u64"), Some(Primitive::Unsigned(64)));
+        assert_eq!(primitive("core::integer::i8"), Some(Primitive::Signed(8)));
+        assert_eq!(
+            primitive("core::starknet::contract_address::ContractAddress"),
+            Some(Primitive::Felt)
+        );
+        assert_eq!(primitive("core::array::Array::<core::felt252>"), None);
+
+        assert_eq!(array_item("core::array::Span::<core::felt252>"), Some("core::felt252"));
+        assert_eq!(
+            tuple_members("(core::felt252, (core::bool, core::integer::u8))"),
+            Some(vec!["core::felt252", "(core::bool, core::integer::u8)"])
+        );
+        assert_eq!(tuple_members("()"), Some(vec![]));
+
+        assert_eq!(
+            generic_args("core::starknet::storage::Map::<core::felt252, (u8, u16)>"),
+            Some(("core::starknet::storage::Map", vec!["core::felt252", "(u8, u16)"]))
+        );
+        assert_eq!(generic_args("Vec<u64>"), Some(("Vec", vec!["u64"])));
+        assert_eq!(generic_args("(Array<u8>, u8)"), None);
+
+        assert_eq!(
+            short_type_name("core::array::Array::<(core::felt252, core::integer::u256)>"),
+            "Array<(felt252, u256)>"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! A lenient, JSON-like syntax for typed values given on the command line.
+//!
+//! Unlike JSON, numbers are kept as text (so that e.g. `u256` values don't lose precision), keys
+//! and strings don't need to be quoted, and tuples can be written with parentheses:
+//!
+//! ```text
+//! 100, 0x1234, hello, "a, quoted string", [1, 2, 3], (1, true), {low: 1, high: 0}, {Some: 5}
+//! ```
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+
+/// Characters that delimit an unquoted value.
+const DELIMITERS: &[char] = &[',', '[', ']', '(', ')', '{', '}', ':', '"'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// An unquoted value, e.g. a number, a boolean or an enum variant.
+    Scalar(String),
+    /// A quoted string.
+    Str(String),
+    /// An array or a tuple.
+    Array(Vec<Value>),
+    /// A struct, or an enum variant with its value, with the fields in the given order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the text of a scalar or string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Scalar(value) | Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a comma separated list of values.
+pub fn parse_values(input: &str) -> Result<Vec<Value>> {
+    let mut parser = Parser { input, pos: 0 };
+    let mut values = Vec::new();
+
+    parser.skip_whitespace();
+    if parser.peek().is_none() {
+        return Ok(values);
+    }
+
+    loop {
+        values.push(parser.value()?);
+        parser.skip_whitespace();
+
+        match parser.peek() {
+            None => return Ok(values),
+            Some(',') => parser.bump(),
+            Some(c) => bail!("unexpected `{c}` at position {}", parser.pos),
+        }
+    }
+}
+
+/// Parses a single value, e.g. one command line argument. A value that isn't compound or quoted is
+/// taken whole, so that `hello, world` is a single string.
+pub fn parse_value(input: &str) -> Result<Value> {
+    let mut parser = Parser { input, pos: 0 };
+
+    parser.skip_whitespace();
+    if !matches!(parser.peek(), Some('[' | '(' | '{' | '"')) {
+        return Ok(Value::Scalar(input.trim().to_string()));
+    }
+
+    let value = parser.value()?;
+    parser.skip_whitespace();
+
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => bail!("unexpected `{c}` at position {}", parser.pos),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('[') => {
+                self.bump();
+                Ok(Value::Array(self.sequence(']')?))
+            }
+            Some('(') => {
+                self.bump();
+                Ok(Value::Array(self.sequence(')')?))
+            }
+            Some('{') => {
+                self.bump();
+                self.object()
+            }
+            Some('"') => Ok(Value::Str(self.string()?)),
+            Some(c) if DELIMITERS.contains(&c) => {
+                bail!("unexpected `{c}` at position {}", self.pos)
+            }
+            Some(_) => Ok(Value::Scalar(self.token())),
+            None => bail!("unexpected end of input"),
+        }
+    }
+
+    fn sequence(&mut self, close: char) -> Result<Vec<Value>> {
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat(close) {
+            return Ok(values);
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            if self.eat(',') {
+                continue;
+            } else if self.eat(close) {
+                return Ok(values);
+            }
+
+            bail!("expected `,` or `{close}` at position {}", self.pos);
+        }
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = match self.peek() {
+                Some('"') => self.string()?,
+                _ => self.token(),
+            };
+
+            if key.is_empty() {
+                bail!("expected a key at position {}", self.pos);
+            }
+
+            self.skip_whitespace();
+            if !self.eat(':') {
+                bail!("expected `:` after key `{key}`");
+            }
+
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+
+            if self.eat(',') {
+                continue;
+            } else if self.eat('}') {
+                return Ok(Value::Object(fields));
+            }
+
+            bail!("expected `,` or `}}` at position {}", self.pos);
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.bump(); // opening quote
+
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(value);
+                }
+                Some('\\') => {
+                    self.bump();
+                    match self.peek() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(c @ ('"' | '\\')) => value.push(c),
+                        Some(c) => bail!("invalid escape sequence `\\{c}`"),
+                        None => break,
+                    }
+                    self.bump();
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.bump();
+                }
+                None => break,
+            }
+        }
+
+        bail!("unterminated string starting at position {start}")
+    }
+
+    fn token(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !DELIMITERS.contains(&c)) {
+            self.bump();
+        }
+        self.input[start..self.pos].trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(value: &str) -> Value {
+        Value::Scalar(value.to_string())
+    }
+
+    #[test]
+    fn parse_nested_values() {
+        let values =
+            parse_values(r#"100, hello world, "a, b", [1, [2]], (1, true), {low: 1, "high": 0}"#)
+                .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                scalar("100"),
+                scalar("hello world"),
+                Value::Str("a, b".to_string()),
+                Value::Array(vec![scalar("1"), Value::Array(vec![scalar("2")])]),
+                Value::Array(vec![scalar("1"), scalar("true")]),
+                Value::Object(vec![
+                    ("low".to_string(), scalar("1")),
+                    ("high".to_string(), scalar("0"))
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty_values() {
+        assert!(parse_values("").unwrap().is_empty());
+        assert_eq!(parse_values("[]").unwrap(), vec![Value::Array(vec![])]);
+    }
+
+    #[test]
+    fn parse_single_value() {
+        assert_eq!(parse_value(" hello, world ").unwrap(), scalar("hello, world"));
+        assert_eq!(parse_value(r#""a, b""#).unwrap(), Value::Str("a, b".to_string()));
+        assert_eq!(parse_value("[1, 2]").unwrap(), Value::Array(vec![scalar("1"), scalar("2")]));
+        assert!(parse_value("[1], [2]").is_err());
+    }
+
+    #[test]
+    fn parse_invalid_values() {
+        assert!(parse_values("[1, 2").is_err());
+        assert!(parse_values("{a 1}").is_err());
+        assert!(parse_values(r#""unterminated"#).is_err());
+        assert!(parse_values("1,").is_err());
+    }
+}
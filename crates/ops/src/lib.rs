@@ -1,5 +1,6 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod abi;
 pub mod rpc;
 pub mod utility;
 mod utils;
@@ -0,0 +1,459 @@
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use alloy_primitives::U256;
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use inquire::{CustomType, Password, Select, Text};
+use probe_args::account::deployment::{AccountDeployment, AccountFlavour};
+use probe_args::account::simple_account::SimpleWallet;
+use probe_args::account::WalletCommands;
+use probe_args::commands::rpc::AccountArgs;
+use probe_args::config;
+use probe_args::opts::account::utils::{get_main_keystore_dir, keystore_chains};
+use probe_args::opts::account::WalletOptions;
+use probe_args::opts::starknet::{ChainId, StarknetOptions};
+use probe_args::opts::transaction::{TransactionOptions, WaitOptions};
+use probe_args::utils::parse_hex_or_str_as_felt;
+use probe_fmt::Pretty;
+use starknet::core::types::{
+    BlockId, BlockTag, BroadcastedDeployAccountTransaction, BroadcastedDeployAccountTransactionV1,
+    BroadcastedDeployAccountTransactionV3, BroadcastedTransaction, FieldElement,
+    ResourceBoundsMapping, StarknetError,
+};
+use starknet::providers::{Provider, ProviderError};
+use starknet::signers::SigningKey;
+use starknet_crypto::poseidon_hash_many;
+
+use super::balance::get_balance;
+use super::send::{self, wait_for_tx};
+use crate::utility::tx_hash;
+use crate::utils;
+
+pub fn run(args: AccountArgs) -> Result<()> {
+    match args.commands {
+        WalletCommands::New { path, account, privatekey, chain, name, password, starknet } => {
+            new(path, account, privatekey, chain, name, password, starknet)
+        }
+        WalletCommands::Sign { keystore, password, message } => sign(keystore, password, message),
+        WalletCommands::Create { flavour, class_hash, salt, chain, name, password, starknet } => {
+            create(flavour, class_hash, salt, chain, name, password, starknet)
+        }
+        WalletCommands::Deploy { keystore, password, starknet, transaction, wait } => {
+            deploy(keystore, password, starknet, transaction, wait)
+        }
+    }
+}
+
+fn new(
+    path: Option<PathBuf>,
+    account: Option<FieldElement>,
+    privatekey: Option<FieldElement>,
+    chain: Option<ChainId>,
+    name: Option<String>,
+    password: Option<String>,
+    starknet: StarknetOptions,
+) -> Result<()> {
+    let (path, account_address, chain) = if let Some(path) = path {
+        if !path.is_dir() {
+            // we require path to be an existing directory
+            bail!("'{}' is not a directory.", path.display())
+        }
+
+        let chain = resolve_chain(chain, &starknet)?;
+        let wallet = SimpleWallet::new(account.unwrap(), privatekey.unwrap(), chain);
+        let path = wallet.encrypt_keystore(&path, password.unwrap(), name)?;
+
+        (
+            path.display().to_string(),
+            wallet.account,
+            wallet.chain.map_or_else(|| "other".to_string(), |c| c.to_string()),
+        )
+    } else {
+        let wallet = WalletOptions { interactive: true, ..Default::default() };
+
+        let mut wallet = wallet.interactive()?.unwrap();
+        wallet.chain = resolve_chain(None, &starknet)?;
+
+        let name = Text::new("Enter account name : ").prompt()?;
+        let password = Password::new("Enter keystore password : ").prompt()?;
+
+        let path = wallet.encrypt_keystore(
+            get_main_keystore_dir(),
+            password,
+            if name.is_empty() { None } else { Some(name) },
+        )?;
+
+        (
+            path.display().to_string(),
+            wallet.account,
+            wallet.chain.map_or_else(|| "other".to_string(), |c| c.to_string()),
+        )
+    };
+
+    println!(
+        "🎉 Successfully created new encrypted keystore at {path}\n\nAccount address: \
+         {account_address:#x}\nChain: {chain}",
+    );
+
+    Ok(())
+}
+
+fn sign(
+    keystore: Option<PathBuf>,
+    password: Option<String>,
+    message: Option<String>,
+) -> Result<()> {
+    // construct a SimpleAccount from the keystore
+    // `path` must be the encrypted keystore json file
+    if let Some(path) = keystore {
+        let wallet = SimpleWallet::decrypt_keystore(path, password.unwrap())?;
+        let hash = parse_hex_or_str_as_felt(message.as_ref().unwrap())?;
+        let sig = wallet.signing_key.sign(&hash)?;
+        println!("{:#x} {:#x}", sig.r, sig.s);
+    } else {
+        let wallet = WalletOptions::default()
+            .interactive()
+            .wrap_err_with(|| "Failed to open keystore".to_string())?
+            .expect("Must create wallet from keystore");
+
+        let message = Text::new("Enter message to sign : ")
+            .with_help_message(
+                "Message with 0x prefix is treated as hex value otherwise literal string",
+            )
+            .prompt()?;
+        let hash = parse_hex_or_str_as_felt(&message)?;
+        let sig = wallet.signing_key.sign(&hash)?;
+
+        println!("\n{:#x} {:#x}", sig.r, sig.s);
+    }
+
+    Ok(())
+}
+
+fn create(
+    flavour: AccountFlavour,
+    class_hash: Option<FieldElement>,
+    salt: Option<FieldElement>,
+    chain: Option<ChainId>,
+    name: Option<String>,
+    password: Option<String>,
+    starknet: StarknetOptions,
+) -> Result<()> {
+    let signing_key = SigningKey::from_random();
+
+    let deployment = AccountDeployment {
+        flavour,
+        class_hash: class_hash.unwrap_or_else(|| flavour.default_class_hash()),
+        salt: salt.unwrap_or_else(|| SigningKey::from_random().secret_scalar()),
+        public_key: signing_key.verifying_key().scalar(),
+    };
+
+    let chain = resolve_chain(chain, &starknet)?;
+
+    let password = match password {
+        Some(password) => password,
+        None => Password::new("Enter keystore password : ").prompt()?,
+    };
+
+    let address = deployment.address();
+    let wallet = SimpleWallet::new(address, signing_key.secret_scalar(), chain);
+    let path = wallet.encrypt_undeployed_keystore(get_main_keystore_dir(), password, name)?;
+    deployment.write(&path)?;
+
+    println!(
+        "🎉 Successfully created new {flavour} account at {}\n\nAccount address: \
+         {address:#x}\n\nFund the address, then deploy it with `probe account deploy {}`.",
+        path.display(),
+        path.display(),
+    );
+
+    Ok(())
+}
+
+fn deploy(
+    keystore: PathBuf,
+    password: Option<String>,
+    starknet: StarknetOptions,
+    transaction: TransactionOptions,
+    wait: WaitOptions,
+) -> Result<()> {
+    send::check_tx_options(&transaction)?;
+
+    let deployment = AccountDeployment::read(&keystore)
+        .with_context(|| format!("{} is not an undeployed account keystore", keystore.display()))?;
+
+    let password = match password {
+        Some(password) => password,
+        None => Password::new("Enter keystore password : ").prompt()?,
+    };
+
+    let wallet = SimpleWallet::decrypt_keystore(&keystore, password)?;
+    let address = deployment.address();
+
+    if wallet.account != address {
+        bail!("keystore address {:#x} doesn't match the deployment parameters", wallet.account);
+    }
+
+    utils::block_on(async {
+        let provider = starknet.provider();
+
+        // the account may have been deployed by an earlier run that didn't wait for the transaction
+        if is_deployed(&provider, address).await? {
+            let path = mark_deployed(&keystore)?;
+            println!(
+                "Account {address:#x} is already deployed. Keystore moved to {}",
+                path.display()
+            );
+            return Ok(());
+        }
+
+        let chain_id = match &wallet.chain {
+            Some(chain) => chain.id(),
+            None => provider.chain_id().await?,
+        };
+
+        let deployer =
+            Deployer { wallet: &wallet, deployment: &deployment, chain_id, options: &transaction };
+        let nonce = transaction.nonce.unwrap_or(FieldElement::ZERO);
+        let is_v3 = transaction.is_v3();
+
+        let needs_estimate = if is_v3 {
+            send::needs_estimate(&transaction)
+        } else {
+            transaction.estimate || transaction.max_fee.is_none()
+        };
+
+        let estimate = if needs_estimate {
+            let query = if is_v3 {
+                deployer.transaction_v3(&send::query_resource_bounds(), nonce, true)?
+            } else {
+                deployer.transaction_v1(FieldElement::ZERO, nonce, true)?
+            };
+
+            let estimate =
+                send::estimate_fee(&provider, BroadcastedTransaction::DeployAccount(query)).await?;
+
+            if transaction.estimate {
+                println!("{}", estimate.prettify());
+                return Ok(());
+            }
+
+            Some(estimate)
+        } else {
+            None
+        };
+
+        let (tx, max_fee) = if is_v3 {
+            let resource_bounds = send::resource_bounds(&transaction, estimate.as_ref())?;
+            let l1_gas = &resource_bounds.l1_gas;
+            let max_fee = (l1_gas.max_amount as u128).saturating_mul(l1_gas.max_price_per_unit);
+            (deployer.transaction_v3(&resource_bounds, nonce, false)?, FieldElement::from(max_fee))
+        } else {
+            let max_fee = match (transaction.max_fee, estimate) {
+                (Some(max_fee), _) => max_fee,
+                (None, estimate) => {
+                    let estimate = estimate.ok_or_else(|| eyre!("missing fee estimate"))?;
+                    let fee: u128 = estimate.overall_fee.try_into()?;
+                    FieldElement::from(send::scale_fee(fee, transaction.fee_multiplier()))
+                }
+            };
+            (deployer.transaction_v1(max_fee, nonce, false)?, max_fee)
+        };
+
+        let token = transaction.fee_token;
+        let unit = if is_v3 { "fri" } else { "wei" };
+        let balance =
+            get_balance(&provider, BlockId::Tag(BlockTag::Pending), token.address(), address)
+                .await?;
+        if balance < U256::from_be_bytes(max_fee.to_bytes_be()) {
+            bail!(
+                "Insufficient {token} balance to deploy account {address:#x}: the max fee is \
+                 {max_fee} {unit} but the balance is only {balance} {unit}"
+            );
+        }
+
+        let res = provider.add_deploy_account_transaction(tx).await?;
+        println!("Account address: {:#x}", res.contract_address);
+        println!("Transaction hash: {:#x}", res.transaction_hash);
+
+        if wait.wait {
+            wait_for_tx(&provider, res.transaction_hash, wait.receipt).await?;
+            let path = mark_deployed(&keystore)?;
+            println!("Account deployed. Keystore moved to {}", path.display());
+        }
+
+        Ok(())
+    })
+}
+
+/// Builds and signs the `DEPLOY_ACCOUNT` transaction of an account deployment.
+struct Deployer<'a> {
+    wallet: &'a SimpleWallet,
+    deployment: &'a AccountDeployment,
+    chain_id: FieldElement,
+    options: &'a TransactionOptions,
+}
+
+impl Deployer<'_> {
+    fn transaction_v1(
+        &self,
+        max_fee: FieldElement,
+        nonce: FieldElement,
+        is_query: bool,
+    ) -> Result<BroadcastedDeployAccountTransaction> {
+        let class_hash = self.deployment.deployed_class_hash();
+        let constructor_calldata = self.deployment.constructor_calldata();
+        let salt = self.deployment.salt;
+
+        let hash = tx_hash::deploy_account_v1(
+            class_hash,
+            salt,
+            &constructor_calldata,
+            max_fee,
+            nonce,
+            self.chain_id,
+            is_query,
+        );
+
+        Ok(BroadcastedDeployAccountTransaction::V1(BroadcastedDeployAccountTransactionV1 {
+            max_fee,
+            signature: self.sign(hash)?,
+            nonce,
+            contract_address_salt: salt,
+            constructor_calldata,
+            class_hash,
+            is_query,
+        }))
+    }
+
+    fn transaction_v3(
+        &self,
+        resource_bounds: &ResourceBoundsMapping,
+        nonce: FieldElement,
+        is_query: bool,
+    ) -> Result<BroadcastedDeployAccountTransaction> {
+        let class_hash = self.deployment.deployed_class_hash();
+        let constructor_calldata = self.deployment.constructor_calldata();
+        let salt = self.deployment.salt;
+
+        let fields = send::v3_fields(
+            self.options,
+            self.deployment.address(),
+            nonce,
+            resource_bounds,
+            self.chain_id,
+            is_query,
+        );
+        let hash = tx_hash::deploy_account_v3(&fields, class_hash, salt, &constructor_calldata);
+
+        Ok(BroadcastedDeployAccountTransaction::V3(BroadcastedDeployAccountTransactionV3 {
+            signature: self.sign(hash)?,
+            nonce,
+            contract_address_salt: salt,
+            constructor_calldata,
+            class_hash,
+            resource_bounds: resource_bounds.clone(),
+            tip: fields.tip,
+            paymaster_data: vec![],
+            nonce_data_availability_mode: fields.nonce_data_availability_mode,
+            fee_data_availability_mode: fields.fee_data_availability_mode,
+            is_query,
+        }))
+    }
+
+    fn sign(&self, hash: FieldElement) -> Result<Vec<FieldElement>> {
+        let signature = self.wallet.signing_key.sign(&hash)?;
+
+        match self.deployment.flavour {
+            AccountFlavour::Oz | AccountFlavour::Argent => Ok(vec![signature.r, signature.s]),
+
+            // Braavos accounts are deployed from a base class and are initialized with the
+            // (separately signed) auxiliary data that is appended to the transaction signature.
+            AccountFlavour::Braavos => {
+                let mut aux_data = vec![
+                    self.deployment.class_hash, // account implementation
+                    FieldElement::ZERO,         // signer type
+                    FieldElement::ZERO,         // secp256r1 signer x (low)
+                    FieldElement::ZERO,         // secp256r1 signer x (high)
+                    FieldElement::ZERO,         // secp256r1 signer y (low)
+                    FieldElement::ZERO,         // secp256r1 signer y (high)
+                    FieldElement::ZERO,         // multisig threshold
+                    FieldElement::ZERO,         // withdrawal limit (low)
+                    FieldElement::ZERO,         // fee rate
+                    FieldElement::ZERO,         // stark fee rate
+                    self.chain_id,
+                ];
+
+                let aux_signature = self.wallet.signing_key.sign(&poseidon_hash_many(&aux_data))?;
+
+                let mut full_signature = vec![signature.r, signature.s];
+                full_signature.append(&mut aux_data);
+                full_signature.extend([aux_signature.r, aux_signature.s]);
+                Ok(full_signature)
+            }
+        }
+    }
+}
+
+async fn is_deployed<P: Provider>(provider: &P, address: FieldElement) -> Result<bool> {
+    match provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), address).await {
+        Ok(_) => Ok(true),
+        Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Moves the keystore of a deployed account out of the undeployed directory and removes its
+/// deployment file. Returns the new path of the keystore.
+fn mark_deployed(keystore: &Path) -> Result<PathBuf> {
+    let dir = keystore.parent().and_then(Path::parent);
+    let (Some(dir), Some(filename)) = (dir, keystore.file_name()) else {
+        bail!("invalid keystore path {}", keystore.display())
+    };
+
+    let path = dir.join(filename);
+    std::fs::rename(keystore, &path)?;
+    std::fs::remove_file(AccountDeployment::path_for(keystore))?;
+
+    Ok(path)
+}
+
+/// Returns the chain of a new keystore, which is otherwise taken from the profile, detected from
+/// the node, or finally selected by the user if there's one to ask.
+fn resolve_chain(chain: Option<ChainId>, starknet: &StarknetOptions) -> Result<Option<ChainId>> {
+    if let Some(chain) = chain.or_else(|| config::profile().chain.clone()) {
+        return Ok(Some(chain));
+    }
+
+    match utils::block_on(starknet.provider().chain_id()) {
+        Ok(id) => Ok(Some(ChainId::from(id))),
+        Err(e) if std::io::stdin().is_terminal() => {
+            eprintln!("Failed to fetch the chain id from {}: {e}", starknet.rpc_url());
+            prompt_chain()
+        }
+        Err(e) => Err(eyre!("failed to fetch the chain id from {}: {e}", starknet.rpc_url())
+            .wrap_err("unknown chain, pass --chain")),
+    }
+}
+
+fn prompt_chain() -> Result<Option<ChainId>> {
+    let chains = keystore_chains(get_main_keystore_dir());
+    let mut options = chains.iter().map(ToString::to_string).collect::<Vec<_>>();
+    options.extend(["custom".to_string(), "other".to_string()]);
+
+    let chain = Select::new("Please select the chain for this account.", options).prompt()?;
+
+    match chain.as_str() {
+        "custom" => {
+            let chain = CustomType::<ChainId>::new("Enter the chain id : ")
+                .with_error_message("Invalid chain id.")
+                .with_help_message("A hex value or a Cairo short string, e.g. KATANA.")
+                .prompt()?;
+            Ok(Some(chain))
+        }
+        "other" => Ok(None),
+        chain => Ok(Some(ChainId::from_str(chain)?)),
+    }
+}
@@ -1,40 +1,261 @@
+use std::future::Future;
+
 use alloy_primitives::U256;
-use color_eyre::eyre::{eyre, ContextCompat};
+use color_eyre::eyre::{bail, ensure, eyre, ContextCompat, WrapErr};
 use color_eyre::{Report, Result};
-use probe_args::commands::rpc::BalanceArgs;
-use starknet::core::types::{BlockId, FieldElement, StarknetError};
+use futures::future::join_all;
+use futures::{stream, StreamExt, TryStreamExt};
+use probe_args::commands::rpc::{BalanceArgs, PortfolioArgs, SeriesFormat};
+use probe_args::tokens::TokenRegistry;
+use probe_fmt::{BalanceSample, BalanceSeries, Portfolio, Pretty, TokenInfo};
+use starknet::core::types::{BlockId, FieldElement, MaybePendingBlockWithTxHashes, StarknetError};
 use starknet::core::utils::parse_cairo_short_string;
 use starknet::macros::selector;
 use starknet::providers::{Provider, ProviderError};
 
 use super::call::contract_call;
-use crate::utils::{self};
+use crate::abi::decode_byte_array;
+use crate::utils::{self, CONCURRENCY};
+
+/// The most samples taken over a range, to keep from flooding the node.
+const MAX_SAMPLES: u64 = 10_000;
 
 pub fn get(args: BalanceArgs) -> Result<()> {
-    let BalanceArgs { address, token, raw, block_id, starknet } = args;
+    if let Some(from_block) = args.from_block {
+        return history(args, from_block);
+    }
+
+    let BalanceArgs { address, token, raw, cache, block_id, starknet, .. } = args;
+    let provider = starknet.provider();
 
-    let (metadata, balance) = utils::block_on(async move {
-        let provider = starknet.provider();
+    if raw {
+        let balance = utils::block_on(get_balance(&provider, block_id, token, address))?;
+        println!("{balance:#x}");
+        return Ok(());
+    }
+
+    let registry = TokenRegistry::open(TokenRegistry::default_path())?;
+    let (info, balance) = utils::block_on(async {
         tokio::join!(
-            get_token_metadata(&provider, block_id, token),
+            get_token_info(&provider, &registry, block_id, token),
             get_balance(&provider, block_id, token, address)
         )
     });
 
     let balance = balance?;
-    let (symbol, decimals) = metadata?;
+    let info = info?;
 
-    if raw {
-        println!("{balance:#x}");
-    } else {
-        let formatted = probe_fmt::utils::format_erc20_balance(balance, &symbol, decimals);
-        println!("{formatted}",);
+    let formatted = probe_fmt::utils::format_erc20_balance(balance, &info.symbol, info.decimals);
+    println!("{formatted}");
+
+    if cache {
+        cache_token(registry, &info)?;
+    }
+
+    Ok(())
+}
+
+/// Samples the balance over a range of blocks.
+fn history(args: BalanceArgs, from_block: u64) -> Result<()> {
+    // the token is only needed to format the balances
+    let registry = match args.raw {
+        true => None,
+        false => Some(TokenRegistry::open(TokenRegistry::default_path())?),
+    };
+
+    let BalanceArgs {
+        address, token, cache, to_block, step, changes, exact, format, starknet, ..
+    } = args;
+    let provider = starknet.provider();
+
+    let series = utils::block_on(async {
+        let to_block = match to_block {
+            Some(block) => block,
+            None => provider.block_number().await?,
+        };
+        let blocks = sample_blocks(from_block, to_block, step)?;
+
+        let info = async {
+            let Some(registry) = &registry else { return Ok(None) };
+            get_token_info(&provider, registry, BlockId::Number(to_block), token).await.map(Some)
+        };
+        let (info, samples) =
+            tokio::join!(info, sample_balances(&provider, token, address, blocks));
+
+        let mut samples = samples?;
+        if exact {
+            samples = sample_changes(&provider, token, address, samples).await?;
+        }
+
+        Ok::<_, Report>(BalanceSeries::new(samples, info?).with_changes(changes))
+    })?;
+
+    match format {
+        SeriesFormat::Table => println!("{}", series.prettify()),
+        SeriesFormat::Csv => println!("{}", series.to_csv()),
+        SeriesFormat::Json => {
+            println!("{}", colored_json::to_colored_json_auto(&series.to_json())?)
+        }
+    }
+
+    if let (Some(registry), Some(info)) = (registry, &series.token) {
+        if cache {
+            cache_token(registry, info)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The blocks sampled over a range: every `step` blocks from `from`, and `to`.
+fn sample_blocks(from: u64, to: u64, step: u64) -> Result<Vec<u64>> {
+    ensure!(from <= to, "the range starts after its last block ({to})");
+
+    let samples = (to - from) / step + 1;
+    ensure!(
+        samples <= MAX_SAMPLES,
+        "the range has {samples} samples, more than the maximum of {MAX_SAMPLES}, use a larger \
+         --step"
+    );
+
+    let mut blocks = (from..=to).step_by(step as usize).collect::<Vec<_>>();
+    if blocks.last() != Some(&to) {
+        blocks.push(to);
+    }
+
+    Ok(blocks)
+}
+
+async fn sample_balances<P>(
+    provider: &P,
+    token: FieldElement,
+    address: FieldElement,
+    blocks: Vec<u64>,
+) -> Result<Vec<BalanceSample>>
+where
+    P: Provider + Sync,
+{
+    stream::iter(blocks)
+        .map(|block| sample_balance(provider, token, address, block))
+        .buffered(CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+async fn sample_balance<P>(
+    provider: &P,
+    token: FieldElement,
+    address: FieldElement,
+    block_number: u64,
+) -> Result<BalanceSample>
+where
+    P: Provider + Sync,
+{
+    let block_id = BlockId::Number(block_number);
+    let (balance, block) = tokio::join!(
+        get_balance(provider, block_id, token, address),
+        provider.get_block_with_tx_hashes(block_id)
+    );
+
+    let timestamp = match block? {
+        MaybePendingBlockWithTxHashes::Block(b) => b.timestamp,
+        MaybePendingBlockWithTxHashes::PendingBlock(b) => b.timestamp,
+    };
+
+    Ok(BalanceSample { block_number, timestamp, balance: balance?, changed: false })
+}
+
+/// Adds the samples of the blocks where the balance changed between two samples, found by
+/// bisecting the blocks between them.
+///
+/// If the balance changed several times between two samples, only one of the changes is found.
+async fn sample_changes<P>(
+    provider: &P,
+    token: FieldElement,
+    address: FieldElement,
+    mut samples: Vec<BalanceSample>,
+) -> Result<Vec<BalanceSample>>
+where
+    P: Provider + Sync,
+{
+    let gaps = samples
+        .windows(2)
+        .filter(|pair| pair[0].balance != pair[1].balance)
+        .filter(|pair| pair[1].block_number - pair[0].block_number > 1)
+        .map(|pair| (pair[0].block_number, pair[1].block_number, pair[0].balance))
+        .collect::<Vec<_>>();
+
+    let changes = stream::iter(gaps)
+        .map(|(low, high, initial)| async move {
+            let balance_at = |block| get_balance(provider, BlockId::Number(block), token, address);
+            let block = bisect_change(low, high, initial, balance_at).await?;
+            if block == high {
+                return Ok(None);
+            }
+            sample_balance(provider, token, address, block).await.map(Some)
+        })
+        .buffered(CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    samples.extend(changes.into_iter().flatten());
+    samples.sort_by_key(|sample| sample.block_number);
+
+    Ok(samples)
+}
+
+/// Returns a block in `(low, high]` where the balance changed, given that it's `initial` at `low`
+/// and different at `high`.
+async fn bisect_change<F, Fut>(
+    mut low: u64,
+    mut high: u64,
+    initial: U256,
+    balance_at: F,
+) -> Result<u64>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<U256>>,
+{
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if balance_at(middle).await? == initial {
+            low = middle;
+        } else {
+            high = middle;
+        }
     }
 
+    Ok(high)
+}
+
+pub fn portfolio(args: PortfolioArgs) -> Result<()> {
+    let PortfolioArgs { address, all, block_id, starknet } = args;
+    let provider = starknet.provider();
+
+    let balances = utils::block_on(async {
+        let chain = provider.chain_id().await?;
+        let tokens = TokenRegistry::open(TokenRegistry::default_path())?.tokens(chain);
+
+        let balances = join_all(
+            tokens.iter().map(|token| get_balance(&provider, block_id, token.address, address)),
+        )
+        .await;
+
+        // a token that can't be read, e.g. one that isn't deployed at the block, only fails its row
+        let balances = balances.into_iter().map(|balance| balance.map_err(|e| e.to_string()));
+        Ok::<_, Report>(tokens.into_iter().zip(balances).collect::<Vec<_>>())
+    })?;
+
+    let balances = balances
+        .into_iter()
+        .filter(|(_, balance)| all || balance.as_ref().map_or(true, |balance| !balance.is_zero()))
+        .collect();
+    println!("{}", Portfolio { balances }.prettify());
+
     Ok(())
 }
 
-async fn get_balance<P>(
+pub(super) async fn get_balance<P>(
     provider: P,
     block_id: BlockId,
     contract_address: FieldElement,
@@ -64,6 +285,37 @@ where
     utils::to_u256(*low, *high)
 }
 
+/// Returns the symbol and decimals of a token from the token registry, fetching them from the
+/// chain if it isn't there.
+async fn get_token_info<P>(
+    provider: P,
+    registry: &TokenRegistry,
+    block_id: BlockId,
+    contract_address: FieldElement,
+) -> Result<TokenInfo>
+where
+    P: Provider + Sync,
+{
+    let chain = provider.chain_id().await?;
+    if let Some(info) = registry.get(chain, contract_address) {
+        return Ok(info);
+    }
+
+    let (symbol, decimals) = get_token_metadata(&provider, block_id, contract_address).await?;
+    Ok(TokenInfo { address: contract_address, symbol, decimals, chain: Some(chain) })
+}
+
+/// Adds a token to the token registry file, if it isn't already in the registry.
+fn cache_token(mut registry: TokenRegistry, info: &TokenInfo) -> Result<()> {
+    let Some(chain) = info.chain else { return Ok(()) };
+    if registry.get(chain, info.address).as_ref() == Some(info) {
+        return Ok(());
+    }
+
+    registry.add(info.clone());
+    registry.save(TokenRegistry::default_path()).wrap_err("failed to cache the token")
+}
+
 async fn get_token_metadata<P>(
     provider: P,
     block_id: BlockId,
@@ -100,5 +352,45 @@ async fn get_symbol(
         contract_call(provider, contract_address, selector!("symbol"), Vec::new(), block_id)
             .await?;
     let symbol = retdata.first().context("missing value in call retdata")?;
-    Ok(parse_cairo_short_string(symbol)?)
+
+    // older tokens return a short string, while the newer ones return a `ByteArray`
+    if retdata.len() == 1 {
+        if let Ok(symbol) = parse_cairo_short_string(symbol) {
+            return Ok(symbol);
+        }
+    }
+
+    let mut data = retdata.iter();
+    let symbol = decode_byte_array(&mut data).map_err(|e| eyre!("invalid token symbol: {e}"))?;
+    if data.len() > 0 {
+        bail!("invalid token symbol: unexpected data after the ByteArray");
+    }
+
+    Ok(symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_the_last_block_of_the_range() {
+        assert_eq!(sample_blocks(10, 20, 5).unwrap(), [10, 15, 20]);
+        assert_eq!(sample_blocks(10, 22, 5).unwrap(), [10, 15, 20, 22]);
+        assert_eq!(sample_blocks(10, 10, 5).unwrap(), [10]);
+        assert!(sample_blocks(20, 10, 1).is_err());
+        assert!(sample_blocks(0, 1_000_000, 1).is_err());
+    }
+
+    #[test]
+    fn bisect_the_block_of_a_change() {
+        // the balance goes from 5 to 7 at block 137
+        let balance_at =
+            |block: u64| async move { Ok(U256::from(if block < 137 { 5 } else { 7 })) };
+
+        let block = utils::block_on(bisect_change(100, 200, U256::from(5), balance_at)).unwrap();
+        assert_eq!(block, 137);
+        let block = utils::block_on(bisect_change(136, 137, U256::from(5), balance_at)).unwrap();
+        assert_eq!(block, 137);
+    }
 }
@@ -1,24 +1,21 @@
 use color_eyre::Result;
-use probe_args::{
-    commands::rpc::{AgeArgs, BlockArgs, BlockNumberArgs},
-    fmt::pretty_block_without_txs,
-};
-use starknet::{
-    core::types::{BlockId, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs},
-    providers::{Provider, ProviderError},
-};
+use probe_args::commands::rpc::{AgeArgs, BlockArgs, BlockNumberArgs};
+use probe_fmt::{pretty_block_without_txs, Pretty, WithNames};
+use starknet::core::types::MaybePendingBlockWithTxHashes;
+use starknet::providers::Provider;
 
 use super::utils;
+use crate::utility;
 
 pub fn age(args: AgeArgs) -> Result<()> {
     let AgeArgs { block_id, starknet, human_readable } = args;
 
     let provider = starknet.provider();
-    let block = utils::do_call_with_mapped_rpc_err(get_block(provider, block_id))?;
+    let block = utils::do_call_with_mapped_rpc_err(provider.get_block_with_tx_hashes(block_id))?;
 
     let timestamp = match block {
-        MaybePendingBlockWithTxs::Block(b) => b.timestamp,
-        MaybePendingBlockWithTxs::PendingBlock(b) => b.timestamp,
+        MaybePendingBlockWithTxHashes::Block(b) => b.timestamp,
+        MaybePendingBlockWithTxHashes::PendingBlock(b) => b.timestamp,
     };
 
     if human_readable {
@@ -37,17 +34,28 @@ pub fn get(args: BlockArgs) -> Result<()> {
 
     let provider = starknet.provider();
 
-    if compact {
-        let block = utils::do_call_with_mapped_rpc_err(get_block_compact(provider, id))?;
-        // display.display(block)?;
-        return Ok(());
+    if full {
+        let block = utils::do_call_with_mapped_rpc_err(provider.get_block_with_txs(id))?;
+        let names = utility::names()?;
+        display.print_with(serde_json::to_value(&block)?, || {
+            WithNames::new(&block, &names).prettify()
+        })?;
     } else {
-        // let block = utils::do_call_with_mapped_rpc_err(get_block(provider, id))?;
-        // if full || display.field.is_some() {
-        //     display.display(block)?;
-        // } else {
-        //     println!("{}", pretty_block_without_txs(&block));
-        // }
+        let block = utils::do_call_with_mapped_rpc_err(provider.get_block_with_tx_hashes(id))?;
+        let mut value = serde_json::to_value(&block)?;
+
+        if compact {
+            display.print_with(value, || block.prettify())?;
+        } else {
+            // only remove the transactions if the user isn't explicitly asking for them
+            if display.raw_format.field.is_none() {
+                if let Some(obj) = value.as_object_mut() {
+                    obj.remove("transactions");
+                }
+            }
+
+            display.print_with(value, || pretty_block_without_txs(&block))?;
+        }
     }
 
     Ok(())
@@ -59,20 +67,3 @@ pub fn number(args: BlockNumberArgs) -> Result<()> {
     println!("{number:#x}");
     Ok(())
 }
-
-async fn get_block<P>(provider: P, id: BlockId) -> Result<MaybePendingBlockWithTxs, ProviderError>
-where
-    P: Provider,
-{
-    provider.get_block_with_txs(id).await
-}
-
-async fn get_block_compact<P>(
-    provider: P,
-    id: BlockId,
-) -> Result<MaybePendingBlockWithTxHashes, ProviderError>
-where
-    P: Provider,
-{
-    provider.get_block_with_tx_hashes(id).await
-}
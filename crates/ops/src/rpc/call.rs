@@ -1,21 +1,43 @@
 use color_eyre::eyre::Context;
 use color_eyre::Result;
-use probe_args::commands::rpc::CallArgs;
+use probe_args::commands::rpc::{parse_raw_calldata, CallArgs};
 use probe_fmt::Pretty;
 use starknet::core::types::{BlockId, FieldElement, FunctionCall};
 use starknet::core::utils::get_selector_from_name;
 use starknet::providers::{Provider, ProviderError};
 
 use super::utils;
+use crate::abi::{self, Decoded};
 
-// TODO: parse the return data according to the ABI?
 pub fn call(args: CallArgs) -> Result<()> {
-    let CallArgs { contract_address, function, input, block_id, starknet } = args;
+    let CallArgs {
+        contract_address,
+        function,
+        input,
+        raw_calldata,
+        block_id,
+        raw,
+        display,
+        starknet,
+    } = args;
 
     let provider = starknet.provider();
 
     let selector = get_selector_from_name(&function)
         .with_context(|| format!("invalid contract entrypoint name '{function}'"))?;
+
+    let abi = if raw_calldata && raw {
+        None
+    } else {
+        crate::utils::block_on(abi::fetch_abi(&provider, block_id, contract_address))?
+    };
+
+    let input = if raw_calldata {
+        parse_raw_calldata(&input)?
+    } else {
+        abi::encode_calldata(abi.as_ref(), selector, &input)?
+    };
+
     let retdata = utils::do_call_with_mapped_rpc_err(contract_call(
         provider,
         contract_address,
@@ -24,7 +46,30 @@ pub fn call(args: CallArgs) -> Result<()> {
         block_id,
     ))?;
 
-    println!("{}", retdata.prettify());
+    let function = abi.as_ref().and_then(|abi| abi.function(selector).map(|f| (abi, f)));
+    let decoded = function.filter(|_| !raw).and_then(|(abi, function)| {
+        // an ABI that doesn't match the deployed code shouldn't hide the result of the call
+        abi.decode_outputs(function, &retdata)
+            .map_err(|e| {
+                eprintln!("Warning: failed to decode the return data, printing it raw: {e}")
+            })
+            .ok()
+    });
+
+    match decoded {
+        Some(values) => {
+            // functions usually have a single output, which is printed as is
+            let json = match values.as_slice() {
+                [value] => value.to_json(),
+                values => values.iter().map(Decoded::to_json).collect::<Vec<_>>().into(),
+            };
+            let pretty = || values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join("\n");
+
+            display.print_with(json, pretty)?;
+        }
+
+        None => display.print_with(serde_json::to_value(&retdata)?, || retdata.prettify())?,
+    }
 
     Ok(())
 }
@@ -1,25 +1,22 @@
-use eyre::{Context, Result};
+use color_eyre::eyre::Context;
+use color_eyre::Result;
 use probe_args::commands::rpc::{ChainIdArgs, SyncingArgs};
-use starknet::{
-    core::{
-        types::{SyncStatus, SyncStatusType},
-        utils::parse_cairo_short_string,
-    },
-    providers::Provider,
-};
+use starknet::core::utils::parse_cairo_short_string;
+use starknet::providers::Provider;
 
-use crate::utils;
+use super::utils;
 
 pub fn id(args: ChainIdArgs) -> Result<()> {
     let ChainIdArgs { starknet } = args;
-    let id = utils::block_on(starknet.provider().chain_id())?;
+    let id = utils::do_call_with_mapped_rpc_err(starknet.provider().chain_id())?;
     let parsed_id = parse_cairo_short_string(&id).context("failed to parse chain id")?;
-    println!("{id:#x} ({parsed_id})",);
+    println!("{id:#x} ({parsed_id})");
     Ok(())
 }
 
 pub fn syncing(args: SyncingArgs) -> Result<()> {
-    // let SyncingArgs { display, starknet } = args;
-    // let status = utils::block_on(starknet.provider().syncing())?;
+    let SyncingArgs { display, starknet } = args;
+    let status = utils::do_call_with_mapped_rpc_err(starknet.provider().syncing())?;
+    display.print(status)?;
     Ok(())
 }
@@ -0,0 +1,26 @@
+use color_eyre::Result;
+use probe_args::commands::rpc::{ClassArgs, CodeArgs};
+use starknet::providers::Provider;
+
+use super::utils;
+
+pub fn get(args: ClassArgs) -> Result<()> {
+    let ClassArgs { hash, block_id, display, starknet } = args;
+
+    let provider = starknet.provider();
+    let class = utils::do_call_with_mapped_rpc_err(provider.get_class(block_id, hash))?;
+    display.print(class)?;
+
+    Ok(())
+}
+
+pub fn code(args: CodeArgs) -> Result<()> {
+    let CodeArgs { contract_address, block_id, display, starknet } = args;
+
+    let provider = starknet.provider();
+    let class =
+        utils::do_call_with_mapped_rpc_err(provider.get_class_at(block_id, contract_address))?;
+    display.print(class)?;
+
+    Ok(())
+}
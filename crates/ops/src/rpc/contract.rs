@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+use probe_args::commands::rpc::{ContractClassArgs, NonceArgs, StorageArgs};
+use starknet::core::types::FieldElement;
+use starknet::providers::Provider;
+
+use super::utils;
+use crate::abi::{Abi, StorageLayout};
+
+pub fn class_hash(args: ContractClassArgs) -> Result<()> {
+    let ContractClassArgs { contract_address, block_id, starknet } = args;
+
+    let provider = starknet.provider();
+    let hash =
+        utils::do_call_with_mapped_rpc_err(provider.get_class_hash_at(block_id, contract_address))?;
+    println!("{hash:#x}");
+
+    Ok(())
+}
+
+pub fn storage(args: StorageArgs) -> Result<()> {
+    let StorageArgs { contract_address, index, r#type, layout, block_id, starknet } = args;
+
+    let (abi, address, r#type) = match layout {
+        Some(path) => {
+            let layout = StorageLayout::load(path)?;
+            let (address, ty) = layout.resolve(&index)?;
+            // the type given explicitly takes precedence over the one of the layout
+            (layout.abi, address, Some(r#type.unwrap_or(ty)))
+        }
+
+        None => {
+            let address = FieldElement::from_str(&index).with_context(|| {
+                format!("invalid storage address `{index}`, use --layout to read a variable path")
+            })?;
+            (Abi::default(), address, r#type)
+        }
+    };
+
+    let provider = starknet.provider();
+    let mut read = |key: FieldElement| {
+        utils::do_call_with_mapped_rpc_err(provider.get_storage_at(contract_address, key, block_id))
+    };
+
+    match r#type {
+        Some(ty) => println!("{}", abi.read_storage(&ty, address, &mut read)?),
+        None => println!("{:#x}", read(address)?),
+    }
+
+    Ok(())
+}
+
+pub fn nonce(args: NonceArgs) -> Result<()> {
+    let NonceArgs { contract_address, block_id, starknet } = args;
+
+    let provider = starknet.provider();
+    let nonce = utils::do_call_with_mapped_rpc_err(provider.get_nonce(block_id, contract_address))?;
+    println!("{nonce:#x}");
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use color_eyre::Result;
+use probe_args::commands::rpc::EventsArgs;
+use probe_fmt::{Pretty, WithEvents};
+use starknet::core::types::EventFilter;
+use starknet::providers::Provider;
+
+use super::utils;
+use crate::abi::EventAbis;
+use crate::utility;
+
+pub fn get(args: EventsArgs) -> Result<()> {
+    let EventsArgs {
+        keys,
+        chunk_size,
+        from,
+        from_block,
+        to_block,
+        continuation_token,
+        display,
+        starknet,
+    } = args;
+
+    let provider = starknet.provider();
+    let filter = EventFilter { from_block, to_block, address: from, keys };
+    let page = utils::do_call_with_mapped_rpc_err(provider.get_events(
+        filter,
+        continuation_token,
+        chunk_size,
+    ))?;
+    let names = utility::names()?;
+    display.print_with(page.clone(), || {
+        let emitters = page.events.iter().map(|event| (event.from_address, event.block_number));
+        let abis = crate::utils::block_on(EventAbis::fetch(&provider, emitters));
+        WithEvents::new(&page, &abis, &names).prettify()
+    })?;
+
+    Ok(())
+}
@@ -1,8 +1,14 @@
+pub mod account;
 pub mod balance;
-// pub mod block;
+pub mod block;
 pub mod call;
-// pub mod chain;
+pub mod chain;
+pub mod class;
+pub mod contract;
 pub mod error;
-// pub mod raw;
+pub mod events;
+pub mod raw;
+pub mod send;
+pub mod state_update;
 pub mod transaction;
 pub mod utils;
@@ -0,0 +1,163 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use color_eyre::eyre::{bail, Context};
+use color_eyre::Result;
+use probe_args::commands::rpc::{DeclareArgs, LegacyDeclareArgs};
+use probe_fmt::Pretty;
+use serde::de::DeserializeOwned;
+use starknet::accounts::{Account, AccountError, ConnectedAccount};
+use starknet::core::types::contract::legacy::LegacyContractClass;
+use starknet::core::types::contract::SierraClass;
+use starknet::core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+use starknet::providers::{Provider, ProviderError};
+
+use super::{apply_tx_options, check_tx_options, wait_for_tx, Payload, V3Sender};
+use crate::{utility, utils};
+
+pub fn declare(args: DeclareArgs) -> Result<()> {
+    let DeclareArgs { contract, casm, starknet, wallet, transaction, wait } = args;
+    check_tx_options(&transaction)?;
+
+    let class: SierraClass = read_artifact(&contract)?;
+    let class_hash = class.class_hash()?;
+    let compiled_class_hash = match casm {
+        Some(casm) => utility::casm_class_hash(casm)?,
+        None => utility::compiled_class_hash(&contract)?,
+    };
+
+    utils::block_on(async {
+        let provider = starknet.provider();
+
+        if is_declared(&provider, class_hash).await? {
+            already_declared(class_hash);
+            return Ok(());
+        }
+
+        let Some(wallet) = wallet.build_wallet()? else { bail!("missing wallet") };
+        let signing_key = wallet.signing_key.clone();
+        let account = wallet.account(provider).await?;
+        let class = Arc::new(class.flatten()?);
+
+        let transaction_hash = if transaction.is_v3() {
+            let sender = V3Sender::new(&account, &signing_key, &transaction);
+            let payload = Payload::Declare { class, class_hash, compiled_class_hash };
+
+            if transaction.estimate {
+                let fee = sender.estimate_fee(&payload).await?;
+                println!("{}", fee.prettify());
+                return Ok(());
+            }
+
+            match sender.send(&payload).await {
+                Err(e) if e.downcast_ref().is_some_and(is_already_declared) => {
+                    already_declared(class_hash);
+                    return Ok(());
+                }
+                result => result?,
+            }
+        } else {
+            let tx = account.declare_v2(class, compiled_class_hash);
+            let tx = apply_tx_options!(tx, transaction);
+
+            if transaction.estimate {
+                let fee = tx.estimate_fee().await?;
+                println!("{}", fee.prettify());
+                return Ok(());
+            }
+
+            match tx.send().await {
+                Err(AccountError::Provider(e)) if is_already_declared(&e) => {
+                    already_declared(class_hash);
+                    return Ok(());
+                }
+                result => result?.transaction_hash,
+            }
+        };
+
+        println!("Class hash: {class_hash:#x}");
+        println!("Transaction hash: {transaction_hash:#x}");
+
+        if wait.wait {
+            wait_for_tx(account.provider(), transaction_hash, wait.receipt).await?;
+        }
+
+        Ok(())
+    })
+}
+
+pub fn legacy_declare(args: LegacyDeclareArgs) -> Result<()> {
+    let LegacyDeclareArgs { contract, starknet, wallet, transaction, wait } = args;
+
+    if transaction.is_v3() {
+        bail!("legacy classes can only be declared with `--fee-token eth`");
+    }
+    check_tx_options(&transaction)?;
+
+    let class: LegacyContractClass = read_artifact(&contract)?;
+    let class_hash = class.class_hash()?;
+
+    utils::block_on(async {
+        let provider = starknet.provider();
+
+        if is_declared(&provider, class_hash).await? {
+            already_declared(class_hash);
+            return Ok(());
+        }
+
+        let Some(wallet) = wallet.build_wallet()? else { bail!("missing wallet") };
+        let account = wallet.account(provider).await?;
+
+        let tx = account.declare_legacy(Arc::new(class));
+        let tx = apply_tx_options!(tx, transaction);
+
+        if transaction.estimate {
+            let fee = tx.estimate_fee().await?;
+            println!("{}", fee.prettify());
+            return Ok(());
+        }
+
+        let res = match tx.send().await {
+            Err(AccountError::Provider(e)) if is_already_declared(&e) => {
+                already_declared(class_hash);
+                return Ok(());
+            }
+            result => result?,
+        };
+        println!("Class hash: {:#x}", res.class_hash);
+        println!("Transaction hash: {:#x}", res.transaction_hash);
+
+        if wait.wait {
+            wait_for_tx(account.provider(), res.transaction_hash, wait.receipt).await?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Checks whether the class with the given hash has already been declared on the network.
+async fn is_declared<P: Provider>(provider: &P, class_hash: FieldElement) -> Result<bool> {
+    match provider.get_class(BlockId::Tag(BlockTag::Pending), class_hash).await {
+        Ok(_) => Ok(true),
+        Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether the node rejected a declaration because the class is already declared, e.g. by another
+/// transaction since [`is_declared`] was checked.
+fn is_already_declared(error: &ProviderError) -> bool {
+    matches!(error, ProviderError::StarknetError(StarknetError::ClassAlreadyDeclared))
+}
+
+fn already_declared(class_hash: FieldElement) {
+    println!("Class hash: {class_hash:#x}");
+    println!("Class is already declared.");
+}
+
+fn read_artifact<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| format!("invalid contract artifact {}", path.display()))
+}
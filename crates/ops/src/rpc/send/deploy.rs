@@ -0,0 +1,63 @@
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use probe_args::commands::rpc::DeployArgs;
+use starknet::accounts::{Call, ConnectedAccount};
+use starknet::core::types::FieldElement;
+use starknet::macros::selector;
+use starknet::signers::SigningKey;
+
+use super::{execute, wait_for_tx};
+use crate::utility::{self, UDC_ADDRESS};
+use crate::utils;
+
+pub fn deploy(args: DeployArgs) -> Result<()> {
+    let DeployArgs {
+        class_hash,
+        constructor_calldata,
+        salt,
+        unique,
+        starknet,
+        wallet,
+        transaction,
+        wait,
+    } = args;
+
+    let Some(wallet) = wallet.build_wallet()? else { bail!("missing wallet") };
+    let salt = salt.unwrap_or_else(|| SigningKey::from_random().secret_scalar());
+
+    let address = utility::udc_deployed_address(
+        wallet.account,
+        salt,
+        unique,
+        class_hash,
+        &constructor_calldata,
+    );
+    println!("Contract address: {address:#x}");
+
+    let mut calldata = vec![
+        class_hash,
+        salt,
+        FieldElement::from(unique as u8),
+        FieldElement::from(constructor_calldata.len()),
+    ];
+    calldata.extend(constructor_calldata);
+
+    let call = Call { to: UDC_ADDRESS, selector: selector!("deployContract"), calldata };
+
+    let signing_key = wallet.signing_key.clone();
+
+    utils::block_on(async {
+        let account = wallet.account(starknet.provider()).await?;
+
+        let Some(hash) = execute(&account, &signing_key, vec![call], &transaction).await? else {
+            return Ok(());
+        };
+        println!("Transaction hash: {hash:#x}");
+
+        if wait.wait {
+            wait_for_tx(account.provider(), hash, wait.receipt).await?;
+        }
+
+        Ok(())
+    })
+}
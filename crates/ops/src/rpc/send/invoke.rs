@@ -0,0 +1,48 @@
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use probe_args::commands::rpc::InvokeArgs;
+use starknet::accounts::ConnectedAccount;
+
+use super::{execute, wait_for_tx};
+use crate::{abi, utility, utils};
+
+pub fn invoke(args: InvokeArgs) -> Result<()> {
+    let InvokeArgs { calls, dry_run, legacy, raw_calldata, starknet, wallet, transaction, wait } =
+        args;
+
+    // a dry run only queries the node for the ABIs if there's one to query
+    let offline = dry_run && starknet.configured_rpc_url().is_none();
+
+    let calls = if raw_calldata || offline {
+        calls.to_calls()?
+    } else {
+        utils::block_on(abi::encode_calls(&starknet.provider(), calls.to_raw_calls()?))?
+    };
+
+    if dry_run {
+        let calldata = utility::execute_calldata(&calls, legacy)
+            .into_iter()
+            .map(|e| format!("{e:#x}"))
+            .collect::<Vec<_>>();
+        println!("{}", calldata.join(" "));
+        return Ok(());
+    }
+
+    let Some(wallet) = wallet.build_wallet()? else { bail!("missing wallet") };
+    let signing_key = wallet.signing_key.clone();
+
+    utils::block_on(async {
+        let account = wallet.account(starknet.provider()).await?;
+
+        let Some(hash) = execute(&account, &signing_key, calls, &transaction).await? else {
+            return Ok(());
+        };
+        println!("{hash:#x}");
+
+        if wait.wait {
+            wait_for_tx(account.provider(), hash, wait.receipt).await?;
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,110 @@
+mod declare;
+mod deploy;
+mod invoke;
+mod offline;
+mod v3;
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use probe_args::opts::transaction::TransactionOptions;
+use probe_fmt::Pretty;
+use starknet::accounts::{Account, Call, ConnectedAccount, ExecutionEncoder};
+use starknet::core::types::{FieldElement, TransactionFinalityStatus};
+use starknet::providers::Provider;
+use starknet::signers::SigningKey;
+use waiter::TransactionWaiter;
+
+pub use self::declare::*;
+pub use self::deploy::*;
+pub use self::invoke::*;
+pub use self::offline::*;
+pub(crate) use self::v3::*;
+
+/// Applies the nonce, max fee and fee multiplier of the [`TransactionOptions`] to a transaction
+/// builder (e.g. `ExecutionV1`, `DeclarationV2`). Without a fee multiplier, the builder's default
+/// one is kept.
+///
+/// [`TransactionOptions`]: probe_args::opts::transaction::TransactionOptions
+macro_rules! apply_tx_options {
+    ($tx:expr, $opts:expr) => {{
+        let mut tx = $tx;
+        if let Some(multiplier) = $opts.fee_multiplier {
+            tx = tx.fee_estimate_multiplier(multiplier);
+        }
+        if let Some(nonce) = $opts.nonce {
+            tx = tx.nonce(nonce);
+        }
+        if let Some(max_fee) = $opts.max_fee {
+            tx = tx.max_fee(max_fee);
+        }
+        tx
+    }};
+}
+
+pub(crate) use apply_tx_options;
+
+/// Makes sure the V3-only transaction options are only used with V3 transactions.
+pub(crate) fn check_tx_options(options: &TransactionOptions) -> Result<()> {
+    if !options.is_v3() {
+        if let Some(option) = options.v3_only_option() {
+            bail!("`{option}` can only be used with `--fee-token strk`");
+        }
+    } else if options.max_fee.is_some() {
+        bail!("`--max-fee` can't be used with `--fee-token strk`, use `--l1-gas` instead");
+    }
+    Ok(())
+}
+
+/// Executes the calls from the account, as a V3 transaction when the fee is paid in STRK and as
+/// a V1 transaction otherwise.
+///
+/// Returns the transaction hash, or `None` if the fee was only estimated.
+pub(super) async fn execute<A>(
+    account: &A,
+    signing_key: &SigningKey,
+    calls: Vec<Call>,
+    transaction: &TransactionOptions,
+) -> Result<Option<FieldElement>>
+where
+    A: ConnectedAccount + ExecutionEncoder + Sync,
+{
+    check_tx_options(transaction)?;
+
+    if transaction.is_v3() {
+        let sender = V3Sender::new(account, signing_key, transaction);
+        let payload = Payload::Invoke(account.encode_calls(&calls));
+
+        if transaction.estimate {
+            let fee = sender.estimate_fee(&payload).await?;
+            println!("{}", fee.prettify());
+            return Ok(None);
+        }
+
+        return Ok(Some(sender.send(&payload).await?));
+    }
+
+    let tx = apply_tx_options!(account.execute_v1(calls), transaction);
+
+    if transaction.estimate {
+        let fee = tx.estimate_fee().await?;
+        println!("{}", fee.prettify());
+        return Ok(None);
+    }
+
+    Ok(Some(tx.send().await?.transaction_hash))
+}
+
+/// Waits for the transaction to be executed and prints its receipt if `receipt` is true.
+pub(super) async fn wait_for_tx<P>(provider: &P, tx_hash: FieldElement, receipt: bool) -> Result<()>
+where
+    P: Provider + Send,
+{
+    let res = TransactionWaiter::new(tx_hash, provider)
+        .with_tx_status(TransactionFinalityStatus::AcceptedOnL2)
+        .await?;
+    if receipt {
+        let names = crate::utility::names()?;
+        println!("{}", super::utils::pretty_receipt(provider, &res, &names).await);
+    }
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::io::Read;
+
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use probe_args::commands::rpc::{BroadcastArgs, SignTxArgs};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{
+    BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1, BroadcastedInvokeTransactionV3,
+    FieldElement,
+};
+use starknet::providers::Provider;
+
+use super::{check_tx_options, resource_bounds, v3_fields, wait_for_tx};
+use crate::utility::tx_hash::{self, V3Fields};
+use crate::{utility, utils};
+
+/// A signed transaction, ready to be submitted by a machine with network access.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub chain_id: FieldElement,
+    pub transaction_hash: FieldElement,
+    pub transaction: BroadcastedInvokeTransaction,
+}
+
+impl SignedTransaction {
+    /// Recomputes the hash of the transaction for the envelope's chain.
+    pub fn compute_hash(&self) -> FieldElement {
+        match &self.transaction {
+            BroadcastedInvokeTransaction::V1(tx) => tx_hash::invoke_v1(
+                tx.sender_address,
+                &tx.calldata,
+                tx.max_fee,
+                tx.nonce,
+                self.chain_id,
+                tx.is_query,
+            ),
+
+            BroadcastedInvokeTransaction::V3(tx) => {
+                let fields = V3Fields {
+                    sender_address: tx.sender_address,
+                    nonce: tx.nonce,
+                    resource_bounds: &tx.resource_bounds,
+                    tip: tx.tip,
+                    paymaster_data: &tx.paymaster_data,
+                    nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                    fee_data_availability_mode: tx.fee_data_availability_mode,
+                    chain_id: self.chain_id,
+                    is_query: tx.is_query,
+                };
+                tx_hash::invoke_v3(&fields, &tx.account_deployment_data, &tx.calldata)
+            }
+        }
+    }
+}
+
+pub fn sign_tx(args: SignTxArgs) -> Result<()> {
+    let SignTxArgs { calls, sender, chain, legacy, output, wallet, transaction } = args;
+
+    check_tx_options(&transaction)?;
+    if transaction.estimate {
+        bail!("`--estimate` can't be used when signing a transaction offline");
+    }
+
+    let calls = calls.to_calls()?;
+    let nonce = transaction
+        .nonce
+        .ok_or_else(|| eyre!("`--nonce` is required to sign a transaction offline"))?;
+
+    // the wallet is only needed if the signature isn't already given
+    let wallet = match transaction.signature {
+        Some(_) => None,
+        None => Some(wallet.build_wallet()?.ok_or_else(|| eyre!("missing wallet"))?),
+    };
+
+    let sender = sender
+        .or(wallet.as_ref().map(|w| w.account))
+        .ok_or_else(|| eyre!("`--sender` is required when no wallet is given"))?;
+
+    let chain_id = chain
+        .or_else(|| wallet.as_ref().and_then(|w| w.chain.clone()))
+        .ok_or_else(|| eyre!("`--chain` is required when the keystore has no chain"))?
+        .id();
+
+    let calldata = utility::execute_calldata(&calls, legacy);
+
+    let tx = if transaction.is_v3() {
+        if transaction.l1_gas.is_none() || transaction.l1_gas_price.is_none() {
+            bail!("`--l1-gas` and `--l1-gas-price` are required to sign a V3 transaction offline");
+        }
+
+        let resource_bounds = resource_bounds(&transaction, None)?;
+        let fields = v3_fields(&transaction, sender, nonce, &resource_bounds, chain_id, false);
+
+        BroadcastedInvokeTransaction::V3(BroadcastedInvokeTransactionV3 {
+            sender_address: sender,
+            calldata,
+            signature: vec![],
+            nonce,
+            tip: fields.tip,
+            paymaster_data: vec![],
+            account_deployment_data: vec![],
+            nonce_data_availability_mode: fields.nonce_data_availability_mode,
+            fee_data_availability_mode: fields.fee_data_availability_mode,
+            resource_bounds,
+            is_query: false,
+        })
+    } else {
+        let max_fee = transaction
+            .max_fee
+            .ok_or_else(|| eyre!("`--max-fee` is required to sign a transaction offline"))?;
+
+        BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1 {
+            sender_address: sender,
+            calldata,
+            max_fee,
+            signature: vec![],
+            nonce,
+            is_query: false,
+        })
+    };
+
+    let mut signed =
+        SignedTransaction { chain_id, transaction_hash: FieldElement::ZERO, transaction: tx };
+    signed.transaction_hash = signed.compute_hash();
+
+    let signature = match (transaction.signature, &wallet) {
+        (Some(signature), _) => signature,
+        (None, Some(wallet)) => {
+            let signature = wallet.signing_key.sign(&signed.transaction_hash)?;
+            vec![signature.r, signature.s]
+        }
+        (None, None) => unreachable!("the wallet is required without a signature"),
+    };
+
+    match &mut signed.transaction {
+        BroadcastedInvokeTransaction::V1(tx) => tx.signature = signature,
+        BroadcastedInvokeTransaction::V3(tx) => tx.signature = signature,
+    }
+
+    let json = serde_json::to_string_pretty(&signed)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("Transaction hash: {:#x}", signed.transaction_hash);
+        }
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
+
+pub fn broadcast(args: BroadcastArgs) -> Result<()> {
+    let BroadcastArgs { signed_tx, wait, starknet } = args;
+
+    let content = match signed_tx {
+        Some(path) => std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        None => {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            content
+        }
+    };
+
+    let signed: SignedTransaction =
+        serde_json::from_str(&content).context("invalid signed transaction")?;
+
+    let hash = signed.compute_hash();
+    if hash != signed.transaction_hash {
+        bail!(
+            "transaction hash mismatch: the envelope says {:#x} but the transaction hashes to \
+             {hash:#x}",
+            signed.transaction_hash
+        );
+    }
+
+    utils::block_on(async {
+        let provider = starknet.provider();
+
+        let chain_id = provider.chain_id().await?;
+        if chain_id != signed.chain_id {
+            bail!(
+                "the transaction was signed for chain {:#x} but the node is on chain {chain_id:#x}",
+                signed.chain_id
+            );
+        }
+
+        let res = provider.add_invoke_transaction(signed.transaction).await?;
+        println!("{:#x}", res.transaction_hash);
+
+        if wait.wait {
+            wait_for_tx(&provider, res.transaction_hash, wait.receipt).await?;
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::{felt, short_string};
+
+    use super::*;
+
+    #[test]
+    fn signed_transaction_roundtrip() {
+        let signed = SignedTransaction {
+            chain_id: short_string!("SN_SEPOLIA"),
+            // the transfer of 1 STRK of the `tx_hash` tests, hashed with starknet_api
+            transaction_hash: felt!(
+                "0x5d921ef1e4aa511b954f280ed5fd54ca066c2d64eca5cd95eaffa3bcd96944e"
+            ),
+            transaction: BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1 {
+                sender_address: felt!(
+                    "0x248a5679921d798085809aaf7799000949c305015b1396e1ba6f509cbbc19d5"
+                ),
+                calldata: vec![
+                    felt!("0x1"),
+                    felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"),
+                    felt!("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"),
+                    felt!("0x3"),
+                    felt!("0x4b3f4ba8c00a02b66142a4b1dd41a4dfab4f92650922a3280977b0f03c75ee1"),
+                    felt!("0xde0b6b3a7640000"),
+                    felt!("0x0"),
+                ],
+                max_fee: felt!("0x2386f26fc10000"),
+                signature: vec![felt!("0xa"), felt!("0xb")],
+                nonce: felt!("0x1"),
+                is_query: false,
+            }),
+        };
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let signed: SignedTransaction = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(signed.compute_hash(), signed.transaction_hash);
+    }
+}
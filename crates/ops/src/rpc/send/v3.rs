@@ -0,0 +1,273 @@
+//! V3 transactions, whose fees are paid in STRK.
+//!
+//! The `starknet-accounts` V3 builders always use a zero tip and L1 data availability modes, so
+//! the transactions are built, hashed and signed here instead.
+
+use std::sync::Arc;
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use probe_args::opts::transaction::TransactionOptions;
+use starknet::accounts::ConnectedAccount;
+use starknet::core::types::{
+    BlockId, BlockTag, BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV3,
+    BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV3, BroadcastedTransaction,
+    FeeEstimate, FieldElement, FlattenedSierraClass, ResourceBounds, ResourceBoundsMapping,
+    SimulationFlagForEstimateFee,
+};
+use starknet::providers::Provider;
+use starknet::signers::SigningKey;
+
+use crate::utility::tx_hash::{self, V3Fields};
+
+/// Resource bounds that are only meant for fee estimation.
+pub(crate) fn query_resource_bounds() -> ResourceBoundsMapping {
+    ResourceBoundsMapping {
+        l1_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+        l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+    }
+}
+
+/// Whether the fee has to be estimated to fill in the resource bounds.
+pub(crate) fn needs_estimate(options: &TransactionOptions) -> bool {
+    options.estimate || options.l1_gas.is_none() || options.l1_gas_price.is_none()
+}
+
+/// Scales a fee by the fee multiplier, rounded to three decimals, with integer math so that large
+/// values don't lose precision.
+pub(crate) fn scale_fee(value: u128, multiplier: f64) -> u128 {
+    let permille = (multiplier * 1000.0).round() as u128;
+    let (thousands, rest) = (value / 1000, value % 1000);
+    thousands.saturating_mul(permille).saturating_add(rest.saturating_mul(permille) / 1000)
+}
+
+/// Returns the resource bounds set in the options, using the fee estimate for the missing ones.
+/// Only the gas amount is scaled by the fee multiplier, so that the max fee is the estimated fee
+/// times the multiplier.
+pub(crate) fn resource_bounds(
+    options: &TransactionOptions,
+    estimate: Option<&FeeEstimate>,
+) -> Result<ResourceBoundsMapping> {
+    let estimate = || estimate.ok_or_else(|| eyre!("missing fee estimate"));
+    let multiplier = options.fee_multiplier();
+
+    let max_amount = match options.l1_gas {
+        Some(max_amount) => max_amount,
+        // the gas needed to pay for the overall fee, which also includes the data gas
+        None => {
+            let estimate = estimate()?;
+            let overall_fee: u128 = estimate.overall_fee.try_into()?;
+            let gas_price: u128 = estimate.gas_price.try_into()?;
+            scale_fee(overall_fee.div_ceil(gas_price.max(1)), multiplier).try_into()?
+        }
+    };
+
+    let max_price_per_unit = match options.l1_gas_price {
+        Some(max_price_per_unit) => max_price_per_unit,
+        None => estimate()?.gas_price.try_into()?,
+    };
+
+    Ok(ResourceBoundsMapping {
+        l1_gas: ResourceBounds { max_amount, max_price_per_unit },
+        l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+    })
+}
+
+/// Returns the hash fields that are derived from the transaction options.
+pub(crate) fn v3_fields<'a>(
+    options: &TransactionOptions,
+    sender_address: FieldElement,
+    nonce: FieldElement,
+    resource_bounds: &'a ResourceBoundsMapping,
+    chain_id: FieldElement,
+    is_query: bool,
+) -> V3Fields<'a> {
+    V3Fields {
+        sender_address,
+        nonce,
+        resource_bounds,
+        tip: options.tip.unwrap_or_default(),
+        paymaster_data: &[],
+        nonce_data_availability_mode: options.nonce_da_mode.unwrap_or_default().into(),
+        fee_data_availability_mode: options.fee_da_mode.unwrap_or_default().into(),
+        chain_id,
+        is_query,
+    }
+}
+
+/// Estimates the fee of a single (query) transaction.
+pub(crate) async fn estimate_fee<P>(provider: &P, tx: BroadcastedTransaction) -> Result<FeeEstimate>
+where
+    P: Provider,
+{
+    let flags: [SimulationFlagForEstimateFee; 0] = [];
+    let estimates = provider.estimate_fee([tx], flags, BlockId::Tag(BlockTag::Pending)).await?;
+    estimates.into_iter().next().ok_or_else(|| eyre!("missing fee estimate"))
+}
+
+/// The content of a V3 transaction sent from an account.
+pub(super) enum Payload {
+    /// The already encoded `__execute__` calldata.
+    Invoke(Vec<FieldElement>),
+    Declare {
+        class: Arc<FlattenedSierraClass>,
+        class_hash: FieldElement,
+        compiled_class_hash: FieldElement,
+    },
+}
+
+/// A V3 transaction built from a [`Payload`], with one variant per payload.
+enum V3Transaction {
+    Invoke(BroadcastedInvokeTransaction),
+    Declare(BroadcastedDeclareTransaction),
+}
+
+impl From<V3Transaction> for BroadcastedTransaction {
+    fn from(tx: V3Transaction) -> Self {
+        match tx {
+            V3Transaction::Invoke(tx) => Self::Invoke(tx),
+            V3Transaction::Declare(tx) => Self::Declare(tx),
+        }
+    }
+}
+
+/// Builds, signs and sends V3 transactions from an account.
+pub(super) struct V3Sender<'a, A> {
+    account: &'a A,
+    signing_key: &'a SigningKey,
+    options: &'a TransactionOptions,
+}
+
+impl<'a, A> V3Sender<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    pub fn new(
+        account: &'a A,
+        signing_key: &'a SigningKey,
+        options: &'a TransactionOptions,
+    ) -> Self {
+        Self { account, signing_key, options }
+    }
+
+    pub async fn estimate_fee(&self, payload: &Payload) -> Result<FeeEstimate> {
+        let nonce = self.nonce().await?;
+        let query = self.build(payload, nonce, &query_resource_bounds(), true)?;
+        estimate_fee(self.account.provider(), query.into()).await
+    }
+
+    /// Sends the transaction and returns its hash.
+    pub async fn send(&self, payload: &Payload) -> Result<FieldElement> {
+        let nonce = self.nonce().await?;
+
+        let estimate = if needs_estimate(self.options) {
+            let query = self.build(payload, nonce, &query_resource_bounds(), true)?;
+            Some(estimate_fee(self.account.provider(), query.into()).await?)
+        } else {
+            None
+        };
+
+        let resource_bounds = resource_bounds(self.options, estimate.as_ref())?;
+        let provider = self.account.provider();
+
+        let hash = match self.build(payload, nonce, &resource_bounds, false)? {
+            V3Transaction::Invoke(tx) => {
+                provider.add_invoke_transaction(tx).await?.transaction_hash
+            }
+            V3Transaction::Declare(tx) => {
+                provider.add_declare_transaction(tx).await?.transaction_hash
+            }
+        };
+
+        Ok(hash)
+    }
+
+    async fn nonce(&self) -> Result<FieldElement> {
+        match self.options.nonce {
+            Some(nonce) => Ok(nonce),
+            None => Ok(self.account.get_nonce().await?),
+        }
+    }
+
+    fn build(
+        &self,
+        payload: &Payload,
+        nonce: FieldElement,
+        resource_bounds: &ResourceBoundsMapping,
+        is_query: bool,
+    ) -> Result<V3Transaction> {
+        let fields = v3_fields(
+            self.options,
+            self.account.address(),
+            nonce,
+            resource_bounds,
+            self.account.chain_id(),
+            is_query,
+        );
+
+        let tx = match payload {
+            Payload::Invoke(calldata) => {
+                let hash = tx_hash::invoke_v3(&fields, &[], calldata);
+                V3Transaction::Invoke(BroadcastedInvokeTransaction::V3(
+                    BroadcastedInvokeTransactionV3 {
+                        sender_address: fields.sender_address,
+                        calldata: calldata.clone(),
+                        signature: self.sign(hash)?,
+                        nonce,
+                        resource_bounds: resource_bounds.clone(),
+                        tip: fields.tip,
+                        paymaster_data: vec![],
+                        account_deployment_data: vec![],
+                        nonce_data_availability_mode: fields.nonce_data_availability_mode,
+                        fee_data_availability_mode: fields.fee_data_availability_mode,
+                        is_query,
+                    },
+                ))
+            }
+
+            Payload::Declare { class, class_hash, compiled_class_hash } => {
+                let hash = tx_hash::declare_v3(&fields, &[], *class_hash, *compiled_class_hash);
+                V3Transaction::Declare(BroadcastedDeclareTransaction::V3(
+                    BroadcastedDeclareTransactionV3 {
+                        sender_address: fields.sender_address,
+                        compiled_class_hash: *compiled_class_hash,
+                        signature: self.sign(hash)?,
+                        nonce,
+                        contract_class: class.clone(),
+                        resource_bounds: resource_bounds.clone(),
+                        tip: fields.tip,
+                        paymaster_data: vec![],
+                        account_deployment_data: vec![],
+                        nonce_data_availability_mode: fields.nonce_data_availability_mode,
+                        fee_data_availability_mode: fields.fee_data_availability_mode,
+                        is_query,
+                    },
+                ))
+            }
+        };
+
+        Ok(tx)
+    }
+
+    fn sign(&self, hash: FieldElement) -> Result<Vec<FieldElement>> {
+        let signature = self.signing_key.sign(&hash)?;
+        Ok(vec![signature.r, signature.s])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_fees_without_losing_precision() {
+        assert_eq!(scale_fee(10, 1.5), 15);
+        assert_eq!(scale_fee(999, 1.0), 999);
+        assert_eq!(scale_fee(1001, 2.5), 2502);
+
+        // well above the 53 bits of precision of a f64
+        let fee = (1u128 << 100) + 1;
+        assert_eq!(scale_fee(fee, 1.5), fee * 3 / 2);
+        assert_eq!(scale_fee(u128::MAX, 2.0), u128::MAX);
+    }
+}
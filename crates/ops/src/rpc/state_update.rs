@@ -0,0 +1,15 @@
+use color_eyre::Result;
+use probe_args::commands::rpc::StateUpdateArgs;
+use starknet::providers::Provider;
+
+use super::utils;
+
+pub fn get(args: StateUpdateArgs) -> Result<()> {
+    let StateUpdateArgs { block_id, display, starknet } = args;
+
+    let provider = starknet.provider();
+    let update = utils::do_call_with_mapped_rpc_err(provider.get_state_update(block_id))?;
+    display.print(update)?;
+
+    Ok(())
+}
@@ -1,56 +1,51 @@
 use color_eyre::Result;
 use probe_args::commands::rpc::{ReceiptArgs, TxArgs, TxCountArgs, TxStatusArgs};
+use probe_fmt::{Pretty, WithNames};
 use starknet::providers::Provider;
 
 use super::utils;
+use crate::utility;
 
-// pub fn get(args: TxArgs) -> Result<()> {
-//     let TxArgs {
-//         hash,
-//         display,
-//         starknet,
-//     } = args;
+pub fn get(args: TxArgs) -> Result<()> {
+    let TxArgs { hash, display, starknet } = args;
 
-//     let provider = starknet.provider();
-//     let result = utils::do_call_with_mapped_rpc_err(provider.get_transaction_by_hash(hash))?;
-//     display.print(result)?;
+    let provider = starknet.provider();
+    let tx = utils::do_call_with_mapped_rpc_err(provider.get_transaction_by_hash(hash))?;
+    let names = utility::names()?;
+    display.print_with(tx.clone(), || WithNames::new(&tx, &names).prettify())?;
 
-//     Ok(())
-// }
+    Ok(())
+}
 
-// pub fn count(args: TxCountArgs) -> Result<()> {
-//     let TxCountArgs {
-//         block_id, starknet, ..
-//     } = args;
+pub fn count(args: TxCountArgs) -> Result<()> {
+    let TxCountArgs { block_id, display, starknet } = args;
 
-//     let provider = starknet.provider();
-//     let count =
-// utils::do_call_with_mapped_rpc_err(provider.get_block_transaction_count(block_id))?;     println!
-// ("{count}");
+    let provider = starknet.provider();
+    let count = utils::do_call_with_mapped_rpc_err(provider.get_block_transaction_count(block_id))?;
+    display.print(count)?;
 
-//     Ok(())
-// }
+    Ok(())
+}
 
-// pub fn status(args: TxStatusArgs) -> Result<()> {
-//     let TxStatusArgs {
-//         hash,
-//         display,
-//         starknet,
-//     } = args;
+pub fn status(args: TxStatusArgs) -> Result<()> {
+    let TxStatusArgs { hash, display, starknet } = args;
 
-//     let provider = starknet.provider();
-//     let status = utils::do_call_with_mapped_rpc_err(provider.get_transaction_status(hash))?;
-//     display.print(status)?;
+    let provider = starknet.provider();
+    let status = utils::do_call_with_mapped_rpc_err(provider.get_transaction_status(hash))?;
+    display.print(status)?;
 
-//     Ok(())
-// }
+    Ok(())
+}
 
 pub fn receipt(args: ReceiptArgs) -> Result<()> {
     let ReceiptArgs { hash, display, starknet } = args;
 
     let provider = starknet.provider();
     let receipt = utils::do_call_with_mapped_rpc_err(provider.get_transaction_receipt(hash))?;
-    display.print(receipt)?;
+    let names = utility::names()?;
+    display.print_with(receipt.clone(), || {
+        crate::utils::block_on(utils::pretty_receipt(&provider, &receipt, &names))
+    })?;
 
     Ok(())
 }
@@ -2,8 +2,13 @@ use std::future::Future;
 
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
-use starknet::providers::ProviderError;
+use probe_fmt::{Names, Pretty, WithEvents};
+use starknet::core::types::{
+    Event, MaybePendingTransactionReceipt, PendingTransactionReceipt, TransactionReceipt,
+};
+use starknet::providers::{Provider, ProviderError};
 
+use crate::abi::EventAbis;
 use crate::rpc::error::StarknetRpcError;
 use crate::utils;
 
@@ -17,3 +22,50 @@ where
         Err(e) => Err(eyre!(e)),
     }
 }
+
+/// Returns the events emitted by the transaction of the receipt.
+pub(super) fn receipt_events(receipt: &MaybePendingTransactionReceipt) -> &[Event] {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => match receipt {
+            TransactionReceipt::Invoke(receipt) => &receipt.events,
+            TransactionReceipt::L1Handler(receipt) => &receipt.events,
+            TransactionReceipt::Declare(receipt) => &receipt.events,
+            TransactionReceipt::Deploy(receipt) => &receipt.events,
+            TransactionReceipt::DeployAccount(receipt) => &receipt.events,
+        },
+
+        MaybePendingTransactionReceipt::PendingReceipt(receipt) => match receipt {
+            PendingTransactionReceipt::Invoke(receipt) => &receipt.events,
+            PendingTransactionReceipt::L1Handler(receipt) => &receipt.events,
+            PendingTransactionReceipt::Declare(receipt) => &receipt.events,
+            PendingTransactionReceipt::DeployAccount(receipt) => &receipt.events,
+        },
+    }
+}
+
+/// Returns the number of the block of the receipt, or `None` if it's pending.
+fn receipt_block_number(receipt: &MaybePendingTransactionReceipt) -> Option<u64> {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => Some(match receipt {
+            TransactionReceipt::Invoke(receipt) => receipt.block_number,
+            TransactionReceipt::L1Handler(receipt) => receipt.block_number,
+            TransactionReceipt::Declare(receipt) => receipt.block_number,
+            TransactionReceipt::Deploy(receipt) => receipt.block_number,
+            TransactionReceipt::DeployAccount(receipt) => receipt.block_number,
+        }),
+        MaybePendingTransactionReceipt::PendingReceipt(_) => None,
+    }
+}
+
+/// Pretty prints a transaction receipt, with its events decoded against the ABIs of the contracts
+/// that emitted them, and its addresses and selectors along with their `names`.
+pub(super) async fn pretty_receipt<P: Provider, N: Names>(
+    provider: &P,
+    receipt: &MaybePendingTransactionReceipt,
+    names: &N,
+) -> String {
+    let block_number = receipt_block_number(receipt);
+    let emitters = receipt_events(receipt).iter().map(|event| (event.from_address, block_number));
+    let abis = EventAbis::fetch(provider, emitters).await;
+    WithEvents::new(receipt, &abis, names).prettify()
+}
@@ -0,0 +1,46 @@
+//! Edition of the address book.
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use probe_args::addresses::{AddressBook, AddressEntry};
+use probe_args::commands::utility::AliasCommand;
+use probe_args::opts::starknet::ChainId;
+
+/// Runs an `alias` subcommand, returning the lines of its output.
+pub fn alias(command: AliasCommand) -> Result<Vec<String>> {
+    let path = AddressBook::default_path();
+    let mut book = AddressBook::open(&path)?;
+
+    match command {
+        AliasCommand::Add { name, address, chain, tags } => {
+            book.add(AddressEntry { name, address, chain, tags })?;
+        }
+
+        AliasCommand::Rm { name, chain } => {
+            if book.remove(&name, chain) == 0 {
+                bail!("`{name}` not found in {}", path.display());
+            }
+        }
+
+        AliasCommand::Ls { chain, tag } => {
+            let entries = book
+                .entries()
+                .filter(|e| e.is_on(chain))
+                .filter(|e| tag.map_or(true, |tag| e.tags.contains(&tag)))
+                .collect::<Vec<_>>();
+
+            let width = entries.iter().map(|e| e.name.len()).max().unwrap_or_default();
+            let lines = entries.into_iter().map(|e| {
+                let chain = e.chain.map_or("all".to_string(), |c| ChainId::from(c).to_string());
+                let tags = e.tags.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+                let line = format!("{:<width$}  {:#066x}  {chain:<10}  {tags}", e.name, e.address);
+                line.trim_end().to_string()
+            });
+
+            return Ok(lines.collect());
+        }
+    }
+
+    book.save(&path)?;
+    Ok(Vec::new())
+}
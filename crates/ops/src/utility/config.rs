@@ -0,0 +1,47 @@
+//! Edition of the profiles of the configuration file.
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use probe_args::commands::utility::ConfigCommand;
+use probe_args::config::{self, Config, Profile};
+
+/// Runs a `config` subcommand on the active profile, returning its output if it has any.
+///
+/// The profiles are edited without loading the whole file, so that an invalid one can be fixed.
+pub fn config(command: ConfigCommand) -> Result<Option<String>> {
+    let path = Config::default_path();
+    let name = config::profile_name();
+    let not_found = || eyre!("profile `{name}` not found in {}", path.display());
+    let not_a_table = || eyre!("profile `{name}` in {} must be a table", path.display());
+
+    match command {
+        ConfigCommand::Show { all } => {
+            let config = Config::open(&path)?;
+            let content = if all {
+                toml::to_string_pretty(&config)?
+            } else {
+                toml::to_string_pretty(config.profiles.get(name).ok_or_else(not_found)?)?
+            };
+            return Ok(Some(content.trim_end().to_string()));
+        }
+
+        ConfigCommand::Set { key, value } => Config::edit(&path, |profiles| {
+            let profile = profiles.entry(name).or_insert_with(|| toml::Table::new().into());
+            Profile::set(profile.as_table_mut().ok_or_else(not_a_table)?, &key, &value)
+        })?,
+
+        ConfigCommand::Unset { key } => Config::edit(&path, |profiles| {
+            let profile = profiles.get_mut(name).ok_or_else(not_found)?;
+            Profile::unset(profile.as_table_mut().ok_or_else(not_a_table)?, &key)
+        })?,
+
+        ConfigCommand::Remove => Config::edit(&path, |profiles| {
+            profiles.remove(name).ok_or_else(not_found)?;
+            Ok(())
+        })?,
+
+        ConfigCommand::Path => return Ok(Some(path.display().to_string())),
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,253 @@
+//! Conversions between integers and the ways Cairo encodes them in felts.
+
+use alloy_primitives::U256;
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use probe_args::commands::utility::{ConvertCommand, NumberFormat};
+use serde_json::{Map, Value};
+use starknet::core::types::FieldElement;
+
+use crate::utils::{felt_to_u256, to_u256, u256_to_felt};
+
+/// The order of the Stark field, `2^251 + 17 * 2^192 + 1`.
+const PRIME: U256 = U256::from_limbs([1, 0, 0, 0x0800000000000011]);
+
+/// A value output by a conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Number {
+    Integer {
+        negative: bool,
+        abs: U256,
+    },
+    /// A fixed-point number scaled back to a decimal string, e.g. `1.5`.
+    Decimal(String),
+}
+
+impl Number {
+    fn unsigned(value: U256) -> Self {
+        Self::Integer { negative: false, abs: value }
+    }
+
+    fn felt(felt: FieldElement) -> Self {
+        Self::unsigned(felt_to_u256(felt))
+    }
+
+    fn display(&self, format: NumberFormat) -> String {
+        match self {
+            Self::Integer { negative, abs } => {
+                let sign = if *negative { "-" } else { "" };
+                match format {
+                    NumberFormat::Hex => format!("{sign}{abs:#x}"),
+                    NumberFormat::Dec | NumberFormat::Json => format!("{sign}{abs}"),
+                }
+            }
+
+            // there's no hex notation for the fractional part
+            Self::Decimal(value) => value.clone(),
+        }
+    }
+}
+
+/// The named values resulting from a conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Converted {
+    values: Vec<(&'static str, Number)>,
+}
+
+impl Converted {
+    fn new(values: impl IntoIterator<Item = (&'static str, Number)>) -> Self {
+        Self { values: values.into_iter().collect() }
+    }
+
+    /// Displays the values separated by spaces, or as a JSON object of decimal strings.
+    pub fn display(&self, format: NumberFormat) -> Result<String> {
+        if format == NumberFormat::Json {
+            let object = self
+                .values
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::String(value.display(format))))
+                .collect::<Map<_, _>>();
+            return Ok(serde_json::to_string_pretty(&object)?);
+        }
+
+        let values = self.values.iter().map(|(_, value)| value.display(format));
+        Ok(values.collect::<Vec<_>>().join(" "))
+    }
+}
+
+pub fn convert(command: ConvertCommand) -> Result<Converted> {
+    let converted = match command {
+        ConvertCommand::JoinU256 { low, high } => {
+            Converted::new([("value", Number::unsigned(to_u256(low, high)?))])
+        }
+
+        ConvertCommand::ToSigned { felt } => {
+            let (negative, abs) = felt_to_signed(felt);
+            Converted::new([("value", Number::Integer { negative, abs })])
+        }
+
+        ConvertCommand::FromSigned { value, bits } => {
+            let felt = signed_to_felt(&value, bits)?;
+            Converted::new([("felt", Number::felt(felt))])
+        }
+
+        ConvertCommand::ToFixed { value, decimals } => {
+            let (negative, abs) = to_fixed(&value, decimals)?;
+            Converted::new([("value", Number::Integer { negative, abs })])
+        }
+
+        ConvertCommand::FromFixed { value, decimals } => {
+            Converted::new([("value", Number::Decimal(from_fixed(&value, decimals)?))])
+        }
+    };
+
+    Ok(converted)
+}
+
+/// Interprets a felt as a signed integer, returning its sign and absolute value. The felts above
+/// `P / 2` are the negative values, i.e. `-x` is encoded as `P - x`.
+fn felt_to_signed(felt: FieldElement) -> (bool, U256) {
+    let value = felt_to_u256(felt);
+    if value > PRIME >> 1 {
+        (true, PRIME - value)
+    } else {
+        (false, value)
+    }
+}
+
+/// Encodes a signed integer as a felt. If `bits` is set, the value must fit in the Cairo signed
+/// integer type of that size (e.g. `i32`), otherwise it must be within `[-P/2, P/2]`.
+fn signed_to_felt(value: &str, bits: Option<u32>) -> Result<FieldElement> {
+    let (negative, abs) = parse_signed(value)?;
+
+    let in_range = match bits {
+        Some(bits @ (8 | 16 | 32 | 64 | 128)) => {
+            let bound = U256::from(1) << (bits as usize - 1);
+            if negative {
+                abs <= bound
+            } else {
+                abs < bound
+            }
+        }
+        Some(bits) => bail!("invalid signed integer size {bits}, expected 8, 16, 32, 64 or 128"),
+        None => abs <= PRIME >> 1,
+    };
+
+    if !in_range {
+        match bits {
+            Some(bits) => bail!("{value} is out of range for i{bits}"),
+            None => bail!("{value} is out of range for a signed felt"),
+        }
+    }
+
+    let value = if negative && !abs.is_zero() { PRIME - abs } else { abs };
+    u256_to_felt(value)
+}
+
+/// Scales a decimal number by `10^decimals`, e.g. `1.5` with 18 decimals is `1.5 * 10^18`.
+fn to_fixed(value: &str, decimals: u8) -> Result<(bool, U256)> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if fraction.len() > decimals as usize {
+        bail!("{value} has more than {decimals} decimals");
+    }
+
+    let padded = format!("{integer}{fraction:0<width$}", width = decimals as usize);
+    if padded.is_empty() || !padded.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("invalid decimal number: {value}");
+    }
+
+    let abs = U256::from_str_radix(&padded, 10).with_context(|| format!("{value} is too large"))?;
+    Ok((negative && !abs.is_zero(), abs))
+}
+
+/// Scales a fixed-point integer back by `10^decimals` into a decimal number.
+fn from_fixed(value: &str, decimals: u8) -> Result<String> {
+    let (negative, abs) = parse_signed(value)?;
+    let scale = U256::from(10)
+        .checked_pow(U256::from(decimals))
+        .ok_or_else(|| eyre!("too many decimals: {decimals}"))?;
+
+    let (integer, fraction) = abs.div_rem(scale);
+    let sign = if negative && !abs.is_zero() { "-" } else { "" };
+
+    if fraction.is_zero() {
+        return Ok(format!("{sign}{integer}"));
+    }
+
+    let fraction = format!("{:0>width$}", fraction.to_string(), width = decimals as usize);
+    Ok(format!("{sign}{integer}.{}", fraction.trim_end_matches('0')))
+}
+
+/// Parses an unsigned integer, in hex or decimal.
+fn parse_unsigned(value: &str) -> Result<U256> {
+    value.parse().with_context(|| format!("invalid integer: {value}"))
+}
+
+/// Parses an integer with an optional `-` sign, in hex or decimal.
+fn parse_signed(value: &str) -> Result<(bool, U256)> {
+    match value.strip_prefix('-') {
+        Some(abs) => Ok((true, parse_unsigned(abs)?)),
+        None => Ok((false, parse_unsigned(value)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    #[test]
+    fn signed_felt_round_trip() {
+        let minus_one = signed_to_felt("-1", Some(8)).unwrap();
+        assert_eq!(minus_one, FieldElement::ZERO - FieldElement::ONE);
+        assert_eq!(felt_to_signed(minus_one), (true, U256::from(1)));
+        assert_eq!(felt_to_signed(felt!("0x7f")), (false, U256::from(0x7f)));
+
+        assert!(signed_to_felt("-128", Some(8)).is_ok());
+        assert!(signed_to_felt("128", Some(8)).is_err());
+        assert!(signed_to_felt("-129", Some(8)).is_err());
+        assert!(signed_to_felt("1", Some(12)).is_err());
+
+        // the largest positive signed felt is `(P - 1) / 2`
+        let max = (PRIME >> 1).to_string();
+        assert_eq!(felt_to_signed(signed_to_felt(&max, None).unwrap()), (false, PRIME >> 1));
+        assert!(signed_to_felt(&(PRIME >> 1 | U256::from(1) << 200).to_string(), None).is_err());
+    }
+
+    #[test]
+    fn fixed_point_scaling() {
+        assert_eq!(
+            to_fixed("1.5", 18).unwrap(),
+            (false, U256::from(15) * U256::from(10).pow(U256::from(17)))
+        );
+        assert_eq!(to_fixed("-0.25", 2).unwrap(), (true, U256::from(25)));
+        assert_eq!(to_fixed("7", 0).unwrap(), (false, U256::from(7)));
+        assert_eq!(to_fixed("-0", 2).unwrap(), (false, U256::ZERO));
+        assert_eq!(to_fixed("-0.0", 2).unwrap(), (false, U256::ZERO));
+        assert!(to_fixed("0.125", 2).is_err());
+        assert!(to_fixed("1.2.3", 6).is_err());
+
+        assert_eq!(from_fixed("1500000000000000000", 18).unwrap(), "1.5");
+        assert_eq!(from_fixed("-0x19", 2).unwrap(), "-0.25");
+        assert_eq!(from_fixed("700", 2).unwrap(), "7");
+        assert_eq!(from_fixed("5", 3).unwrap(), "0.005");
+    }
+
+    #[test]
+    fn display_converted_values() {
+        let (low, high) = (felt!("2"), felt!("1"));
+        let converted = Converted::new([("low", Number::felt(low)), ("high", Number::felt(high))]);
+
+        assert_eq!(converted.display(NumberFormat::Hex).unwrap(), "0x2 0x1");
+        assert_eq!(converted.display(NumberFormat::Dec).unwrap(), "2 1");
+        let json: Value =
+            serde_json::from_str(&converted.display(NumberFormat::Json).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!({ "low": "2", "high": "1" }));
+    }
+}
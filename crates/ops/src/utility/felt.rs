@@ -0,0 +1,317 @@
+//! Evaluation of arithmetic expressions over felts.
+
+use std::str::FromStr;
+
+use alloy_primitives::U256;
+use color_eyre::eyre::{bail, eyre, Context};
+use color_eyre::Result;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::cairo_short_string_to_felt;
+
+use crate::utils::{felt_to_u256, u256_to_felt};
+
+/// Evaluates an expression modulo the Stark prime.
+///
+/// From the lowest to the highest precedence, the operators are the comparisons (`==`, `!=`, `<`,
+/// `<=`, `>`, `>=`), which evaluate to `1` or `0`, `|`, `^`, `&`, `<<` and `>>`, `+` and `-`, `*`
+/// and `/`, and the unary `-`. The comparisons, shifts and bitwise operators work on the integer
+/// value of the felts, and `/` multiplies by the modular inverse of the divisor. The `pow(x, e)`
+/// and `sqrt(x)` functions are also available.
+pub fn eval_felt(expression: &str) -> Result<FieldElement> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+
+    let value = parser.expression(0)?;
+    if let Some(token) = parser.peek() {
+        bail!("unexpected {token} in expression");
+    }
+
+    Ok(value)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(FieldElement),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "`{value:#x}`"),
+            Self::Ident(name) => write!(f, "`{name}`"),
+            Self::Op(op) => write!(f, "`{op}`"),
+            Self::LParen => write!(f, "`(`"),
+            Self::RParen => write!(f, "`)`"),
+            Self::Comma => write!(f, "`,`"),
+        }
+    }
+}
+
+/// The operators, with the two-character ones first so that they take priority.
+const OPERATORS: &[&str] =
+    &["==", "!=", "<=", ">=", "<<", ">>", "<", ">", "|", "^", "&", "+", "-", "*", "/"];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_alphanumeric() || c == '_' {
+            let len =
+                rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+            let word = &rest[..len];
+
+            if c.is_ascii_digit() {
+                let value = FieldElement::from_str(word)
+                    .with_context(|| format!("invalid number `{word}`"))?;
+                tokens.push(Token::Number(value));
+            } else {
+                tokens.push(Token::Ident(word.to_string()));
+            }
+            len
+        } else if c == '\'' {
+            let end = rest[1..].find('\'').ok_or_else(|| eyre!("unterminated short string"))?;
+            let string = &rest[1..=end];
+            let value = cairo_short_string_to_felt(string)
+                .with_context(|| format!("invalid short string '{string}'"))?;
+            tokens.push(Token::Number(value));
+            end + 2
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(*op));
+            op.len()
+        } else {
+            tokens.push(match c {
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                _ => bail!("unexpected character `{c}` in expression"),
+            });
+            1
+        };
+
+        rest = rest[len..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// The precedence of the binary operators, the higher the tighter.
+fn precedence(op: &str) -> u8 {
+    match op {
+        "==" | "!=" | "<" | "<=" | ">" | ">=" => 1,
+        "|" => 2,
+        "^" => 3,
+        "&" => 4,
+        "<<" | ">>" => 5,
+        "+" | "-" => 6,
+        "*" | "/" => 7,
+        _ => unreachable!("unknown operator {op}"),
+    }
+}
+
+/// A precedence climbing parser, which evaluates the expression as it parses it.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<&Token> {
+        let token =
+            self.tokens.get(self.pos).ok_or_else(|| eyre!("unexpected end of expression"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        let token = self.next()?;
+        if *token != expected {
+            bail!("expected {expected} but found {token}");
+        }
+        Ok(())
+    }
+
+    /// Parses the binary operations whose operators have at least the given precedence.
+    fn expression(&mut self, min_precedence: u8) -> Result<FieldElement> {
+        let mut lhs = self.unary()?;
+
+        while let Some(&Token::Op(op)) = self.peek() {
+            let precedence = precedence(op);
+            if precedence < min_precedence {
+                break;
+            }
+
+            self.pos += 1;
+            // all the operators are left associative
+            let rhs = self.expression(precedence + 1)?;
+            lhs = binary(op, lhs, rhs)?;
+        }
+
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<FieldElement> {
+        if self.peek() == Some(&Token::Op("-")) {
+            self.pos += 1;
+            return Ok(-self.unary()?);
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<FieldElement> {
+        match self.next()?.clone() {
+            Token::Number(value) => Ok(value),
+
+            Token::LParen => {
+                let value = self.expression(0)?;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            }
+
+            Token::Ident(name) => {
+                self.expect(Token::LParen)?;
+                let mut args = vec![self.expression(0)?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.pos += 1;
+                    args.push(self.expression(0)?);
+                }
+                self.expect(Token::RParen)?;
+
+                function(&name, &args)
+            }
+
+            token => bail!("unexpected {token} in expression"),
+        }
+    }
+}
+
+fn binary(op: &str, lhs: FieldElement, rhs: FieldElement) -> Result<FieldElement> {
+    let value = match op {
+        "+" => lhs + rhs,
+        "-" => lhs - rhs,
+        "*" => lhs * rhs,
+        "/" => lhs * rhs.invert().ok_or_else(|| eyre!("division by zero"))?,
+
+        _ => {
+            let (lhs, rhs) = (felt_to_u256(lhs), felt_to_u256(rhs));
+            let value = match op {
+                "==" => U256::from((lhs == rhs) as u8),
+                "!=" => U256::from((lhs != rhs) as u8),
+                "<" => U256::from((lhs < rhs) as u8),
+                "<=" => U256::from((lhs <= rhs) as u8),
+                ">" => U256::from((lhs > rhs) as u8),
+                ">=" => U256::from((lhs >= rhs) as u8),
+                "|" => lhs | rhs,
+                "^" => lhs ^ rhs,
+                "&" => lhs & rhs,
+                "<<" => shl(lhs, rhs)?,
+                ">>" => lhs.checked_shr(shift_amount(rhs)).unwrap_or_default(),
+                _ => unreachable!("unknown operator {op}"),
+            };
+            u256_to_felt(value)?
+        }
+    };
+
+    Ok(value)
+}
+
+/// Shifts `value` to the left, failing if bits are shifted out of the 256 bits.
+fn shl(value: U256, amount: U256) -> Result<U256> {
+    if value.is_zero() {
+        return Ok(value);
+    }
+
+    let amount = shift_amount(amount);
+    match value.checked_shl(amount) {
+        Some(shifted) if shifted >> amount == value => Ok(shifted),
+        _ => bail!("{value:#x} << {amount} exceeds the Stark prime"),
+    }
+}
+
+/// Clamps a shift amount, as any shift of 256 bits or more shifts out all the bits.
+fn shift_amount(amount: U256) -> usize {
+    if amount >= U256::from(256) {
+        256
+    } else {
+        amount.as_limbs()[0] as usize
+    }
+}
+
+fn function(name: &str, args: &[FieldElement]) -> Result<FieldElement> {
+    match (name, args) {
+        ("pow", &[base, exponent]) => Ok(pow(base, exponent)),
+        ("sqrt", &[value]) => value.sqrt().ok_or_else(|| eyre!("{value:#x} has no square root")),
+        ("pow", _) => bail!("`pow` takes a base and an exponent"),
+        ("sqrt", _) => bail!("`sqrt` takes a single argument"),
+        _ => bail!("unknown function `{name}`"),
+    }
+}
+
+/// Raises `base` to the integer value of `exponent`, by square-and-multiply.
+fn pow(base: FieldElement, exponent: FieldElement) -> FieldElement {
+    exponent.to_bits_le().iter().rev().fold(FieldElement::ONE, |acc, bit| {
+        let acc = acc * acc;
+        if *bit {
+            acc * base
+        } else {
+            acc
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    #[test]
+    fn evaluate_arithmetic() {
+        assert_eq!(eval_felt("1 + 2 * 3").unwrap(), felt!("7"));
+        assert_eq!(eval_felt("(1 + 2) * 3").unwrap(), felt!("9"));
+        assert_eq!(eval_felt("10 - 3 - 2").unwrap(), felt!("5"));
+        assert_eq!(eval_felt("0 - 1").unwrap(), -FieldElement::ONE);
+        assert_eq!(eval_felt("-0x10 + 16").unwrap(), FieldElement::ZERO);
+        assert_eq!(eval_felt("pow(2, 10) + pow(3, 0)").unwrap(), felt!("1025"));
+
+        // division is the multiplication by the modular inverse
+        let third = eval_felt("1 / 3").unwrap();
+        assert_eq!(third * felt!("3"), FieldElement::ONE);
+        assert!(eval_felt("1 / 0").is_err());
+
+        let root = eval_felt("sqrt(16)").unwrap();
+        assert_eq!(root * root, felt!("16"));
+    }
+
+    #[test]
+    fn evaluate_integer_operators() {
+        assert_eq!(eval_felt("1 << 4 | 1").unwrap(), felt!("17"));
+        assert_eq!(eval_felt("0xff & 0x0f ^ 0x01").unwrap(), felt!("0x0e"));
+        assert_eq!(eval_felt("0x100 >> 4 == 16").unwrap(), FieldElement::ONE);
+        assert_eq!(eval_felt("0 - 1 > 1").unwrap(), FieldElement::ONE);
+        assert_eq!(eval_felt("'a' == 0x61").unwrap(), FieldElement::ONE);
+        assert_eq!(eval_felt("'hello world' != 'hello'").unwrap(), FieldElement::ONE);
+        // the result must still be a felt
+        assert!(eval_felt("1 << 252").is_err());
+    }
+
+    #[test]
+    fn reject_invalid_expressions() {
+        assert!(eval_felt("").is_err());
+        assert!(eval_felt("1 +").is_err());
+        assert!(eval_felt("(1 + 2").is_err());
+        assert!(eval_felt("1 2").is_err());
+        assert!(eval_felt("foo(1)").is_err());
+        assert!(eval_felt("'unterminated").is_err());
+    }
+}
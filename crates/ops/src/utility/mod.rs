@@ -1,13 +1,629 @@
+mod alias;
+mod config;
+mod convert;
+mod felt;
+mod selector;
+mod storage;
+pub mod tx_hash;
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use alloy_primitives::U256;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use color_eyre::eyre::{bail, eyre, Context};
 use color_eyre::Result;
-use probe_args::commands::utility::IndexArgs;
-use starknet::core::utils::get_storage_var_address;
-
-pub fn storage_address(args: IndexArgs) -> Result<()> {
-    let IndexArgs { var_name, keys } = args;
-    println!("{var_name}");
-    let a = keys.iter().map(|k| format!("{k:#x}")).collect::<Vec<String>>().join(" ");
-    println!("{a}");
-    let address = get_storage_var_address(&var_name, &keys)?;
-    println!("{address:#x}");
-    Ok(())
+use probe_args::addresses::AddressBook;
+use probe_args::commands::utility::{
+    DeclareHashArgs, DeployAccountHashArgs, InvokeHashArgs, TxHashFields,
+};
+use probe_args::selectors::SelectorDb;
+use probe_fmt::ExecuteCalldata;
+use starknet::accounts::Call;
+use starknet::core::crypto::{
+    compute_hash_on_elements, ecdsa_sign as sign, ecdsa_verify as verify, pedersen_hash,
+    ExtendedSignature, Signature,
+};
+use starknet::core::types::{
+    ContractArtifact, FieldElement, ResourceBounds, ResourceBoundsMapping, Transaction,
+};
+use starknet::core::utils::{
+    cairo_short_string_to_felt, get_contract_address, parse_cairo_short_string, starknet_keccak,
+};
+use starknet::macros::felt;
+use starknet_crypto::poseidon_hash_many;
+
+pub use self::alias::alias;
+pub use self::config::config;
+pub use self::convert::{convert, Converted};
+pub use self::felt::eval_felt;
+pub use self::selector::{import_selectors, selector};
+pub use self::storage::storage_address;
+use self::tx_hash::V3Fields;
+use crate::abi::{decode_byte_array, encode_byte_array};
+use crate::utils;
+
+/// Address of the Universal Deployer Contract (UDC), which is the same on all public networks.
+pub const UDC_ADDRESS: FieldElement =
+    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+
+pub fn to_hex(dec: &FieldElement, pad: bool) -> String {
+    if pad {
+        format!("{dec:#066x}")
+    } else {
+        format!("{dec:#x}")
+    }
+}
+
+pub fn to_dec(hex: &FieldElement) -> String {
+    hex.to_string()
+}
+
+/// Hash `data` using Starknet keccak. Data prefixed with `0x` is decoded as hex bytes, otherwise
+/// it is hashed as UTF-8 text.
+pub fn keccak(data: &str) -> Result<FieldElement> {
+    let hash = match data.strip_prefix("0x") {
+        Some(hex) => starknet_keccak(&hex::decode(hex).context("invalid hex data")?),
+        None => starknet_keccak(data.as_bytes()),
+    };
+    Ok(hash)
+}
+
+pub fn pedersen(elements: &[FieldElement]) -> FieldElement {
+    compute_hash_on_elements(elements)
+}
+
+pub fn poseidon(elements: &[FieldElement]) -> FieldElement {
+    poseidon_hash_many(elements)
+}
+
+pub fn ecdsa_sign(private_key: &FieldElement, message: &FieldElement) -> Result<ExtendedSignature> {
+    Ok(sign(private_key, message)?)
+}
+
+pub fn ecdsa_verify(
+    verifying_key: &FieldElement,
+    message: &FieldElement,
+    r: FieldElement,
+    s: FieldElement,
+) -> Result<bool> {
+    Ok(verify(verifying_key, message, &Signature { r, s })?)
+}
+
+/// Decode a felt into the Cairo short string it encodes.
+pub fn felt_to_short_string(felt: &FieldElement) -> Result<String> {
+    Ok(parse_cairo_short_string(felt)?)
+}
+
+/// Encode an ASCII string (at most 31 characters) as a Cairo short string.
+pub fn short_string_to_felt(short_str: &str) -> Result<FieldElement> {
+    Ok(cairo_short_string_to_felt(short_str)?)
+}
+
+/// Split a 256-bit unsigned integer (hex or decimal) into its `(low, high)` 128-bit limbs.
+pub fn split_u256(value: &str) -> Result<(FieldElement, FieldElement)> {
+    let value: U256 = value.parse().with_context(|| format!("invalid u256 value: {value}"))?;
+    Ok(utils::from_u256(value))
+}
+
+/// Compute the class hash of a contract artifact. Sierra, CASM and legacy artifacts are supported.
+pub fn class_hash<P: AsRef<Path>>(path: P) -> Result<FieldElement> {
+    let file = File::open(path.as_ref())
+        .with_context(|| format!("failed to open {}", path.as_ref().display()))?;
+    let artifact: ContractArtifact =
+        serde_json::from_reader(BufReader::new(file)).context("invalid contract artifact")?;
+
+    let hash = match artifact {
+        ContractArtifact::SierraClass(class) => class.class_hash()?,
+        ContractArtifact::CompiledClass(class) => class.class_hash()?,
+        ContractArtifact::LegacyClass(class) => class.class_hash()?,
+    };
+
+    Ok(hash)
+}
+
+/// Compile a Sierra contract artifact to CASM and compute its compiled class hash.
+pub fn compiled_class_hash<P: AsRef<Path>>(path: P) -> Result<FieldElement> {
+    let file = File::open(path.as_ref())
+        .with_context(|| format!("failed to open {}", path.as_ref().display()))?;
+    let class: ContractClass = serde_json::from_reader(BufReader::new(file))
+        .context("invalid Sierra contract artifact")?;
+
+    let casm = match CasmContractClass::from_contract_class(class, true, usize::MAX) {
+        Ok(casm) => casm,
+        Err(e) => bail!("failed to compile Sierra class: {e}"),
+    };
+
+    let hash = casm.compiled_class_hash().to_be_bytes();
+    Ok(FieldElement::from_bytes_be(&hash)?)
+}
+
+/// Compute the compiled class hash of a compiled (CASM) contract artifact.
+pub fn casm_class_hash<P: AsRef<Path>>(path: P) -> Result<FieldElement> {
+    let file = File::open(path.as_ref())
+        .with_context(|| format!("failed to open {}", path.as_ref().display()))?;
+    let casm: CasmContractClass = serde_json::from_reader(BufReader::new(file))
+        .context("invalid compiled contract artifact")?;
+
+    let hash = casm.compiled_class_hash().to_be_bytes();
+    Ok(FieldElement::from_bytes_be(&hash)?)
+}
+
+pub fn compute_address(
+    caller_address: FieldElement,
+    salt: FieldElement,
+    class_hash: FieldElement,
+    calldata: &[FieldElement],
+) -> FieldElement {
+    get_contract_address(salt, class_hash, calldata, caller_address)
+}
+
+/// Compute the address of a contract deployed by `deployer` through the UDC.
+///
+/// If `unique` is set, the UDC mixes the deployer address into the salt and deploys from its own
+/// address, otherwise the deployment is origin-independent (i.e. deployer address is zero).
+pub fn udc_deployed_address(
+    deployer: FieldElement,
+    salt: FieldElement,
+    unique: bool,
+    class_hash: FieldElement,
+    calldata: &[FieldElement],
+) -> FieldElement {
+    if unique {
+        let salt = pedersen_hash(&deployer, &salt);
+        compute_address(UDC_ADDRESS, salt, class_hash, calldata)
+    } else {
+        compute_address(FieldElement::ZERO, salt, class_hash, calldata)
+    }
+}
+
+pub fn invoke_tx_hash(args: InvokeHashArgs) -> FieldElement {
+    let InvokeHashArgs { sender_address, calldata, account_deployment_data, fields } = args;
+
+    match fields.max_fee {
+        Some(max_fee) => tx_hash::invoke_v1(
+            sender_address,
+            &calldata,
+            max_fee,
+            fields.nonce,
+            fields.chain,
+            fields.query,
+        ),
+
+        None => {
+            let resource_bounds = resource_bounds(&fields);
+            let fields = v3_fields(&fields, sender_address, &resource_bounds);
+            tx_hash::invoke_v3(&fields, &account_deployment_data, &calldata)
+        }
+    }
+}
+
+pub fn declare_tx_hash(args: DeclareHashArgs) -> FieldElement {
+    let DeclareHashArgs {
+        sender_address,
+        class_hash,
+        compiled_class_hash,
+        account_deployment_data,
+        fields,
+    } = args;
+
+    match fields.max_fee {
+        Some(max_fee) => tx_hash::declare_v2(
+            sender_address,
+            class_hash,
+            compiled_class_hash,
+            max_fee,
+            fields.nonce,
+            fields.chain,
+            fields.query,
+        ),
+
+        None => {
+            let resource_bounds = resource_bounds(&fields);
+            let fields = v3_fields(&fields, sender_address, &resource_bounds);
+            tx_hash::declare_v3(&fields, &account_deployment_data, class_hash, compiled_class_hash)
+        }
+    }
+}
+
+pub fn deploy_account_tx_hash(args: DeployAccountHashArgs) -> FieldElement {
+    let DeployAccountHashArgs { class_hash, salt, constructor_calldata, fields } = args;
+
+    match fields.max_fee {
+        Some(max_fee) => tx_hash::deploy_account_v1(
+            class_hash,
+            salt,
+            &constructor_calldata,
+            max_fee,
+            fields.nonce,
+            fields.chain,
+            fields.query,
+        ),
+
+        None => {
+            let address =
+                compute_address(FieldElement::ZERO, salt, class_hash, &constructor_calldata);
+            let resource_bounds = resource_bounds(&fields);
+            let fields = v3_fields(&fields, address, &resource_bounds);
+            tx_hash::deploy_account_v3(&fields, class_hash, salt, &constructor_calldata)
+        }
+    }
+}
+
+/// Recomputes the hash of a transaction JSON, as returned by the node, read from `file` or stdin.
+///
+/// Returns the hash found in the JSON and the recomputed one, in that order.
+pub fn verify_tx_hash(
+    file: Option<PathBuf>,
+    chain_id: FieldElement,
+) -> Result<(FieldElement, FieldElement)> {
+    let content = match file {
+        Some(path) => std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        None => {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            content
+        }
+    };
+
+    let tx: Transaction = serde_json::from_str(&content).context("invalid transaction JSON")?;
+    let computed = tx_hash::transaction_hash(&tx, chain_id)?;
+    Ok((*tx.transaction_hash(), computed))
+}
+
+/// The names of the address book and of the selector database, which are shown next to the
+/// addresses and selectors found in the outputs.
+pub fn names() -> Result<(&'static AddressBook, &'static SelectorDb)> {
+    Ok((AddressBook::global()?, SelectorDb::global()?))
+}
+
+/// Decodes the calldata of an account's `__execute__` into the calls it makes.
+///
+/// The calldata is given as in [`read_felts`].
+pub fn decode_calldata(calldata: Vec<String>) -> Result<ExecuteCalldata> {
+    let felts = read_felts(calldata)?;
+    ExecuteCalldata::decode(&felts)
+        .ok_or_else(|| eyre!("calldata doesn't match any known `__execute__` layout"))
+}
+
+/// Encodes a string as a Cairo `ByteArray`. The string is read from stdin if not given.
+pub fn to_byte_array(string: Option<String>) -> Result<Vec<FieldElement>> {
+    let string = match string {
+        Some(string) => string,
+        None => {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            // the trailing newline of piped input isn't part of the string
+            content.strip_suffix('\n').map(str::to_string).unwrap_or(content)
+        }
+    };
+
+    Ok(encode_byte_array(&string))
+}
+
+/// Decodes a Cairo `ByteArray` into a string. The felts are given as in [`read_felts`].
+pub fn from_byte_array(felts: Vec<String>) -> Result<String> {
+    let felts = read_felts(felts)?;
+    let mut data = felts.iter();
+    let string = decode_byte_array(&mut data)?;
+
+    if data.len() > 0 {
+        bail!("{} unexpected felt(s) after the ByteArray", data.len());
+    }
+
+    Ok(string)
+}
+
+/// Parses a list of felts that can be separated by spaces or commas, and be given as a JSON array.
+/// They're read from stdin if `values` is empty, so that the output of other commands can be
+/// piped in.
+fn read_felts(values: Vec<String>) -> Result<Vec<FieldElement>> {
+    let input = if values.is_empty() {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        values.join(" ")
+    };
+
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|value| value.trim_matches(|c| matches!(c, '[' | ']' | '"')))
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            FieldElement::from_str(value).with_context(|| format!("invalid felt `{value}`"))
+        })
+        .collect()
+}
+
+fn resource_bounds(fields: &TxHashFields) -> ResourceBoundsMapping {
+    ResourceBoundsMapping {
+        l1_gas: ResourceBounds {
+            max_amount: fields.l1_gas,
+            max_price_per_unit: fields.l1_gas_price,
+        },
+        l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+    }
+}
+
+fn v3_fields<'a>(
+    fields: &'a TxHashFields,
+    sender_address: FieldElement,
+    resource_bounds: &'a ResourceBoundsMapping,
+) -> V3Fields<'a> {
+    V3Fields {
+        sender_address,
+        nonce: fields.nonce,
+        resource_bounds,
+        tip: fields.tip,
+        paymaster_data: &fields.paymaster_data,
+        nonce_data_availability_mode: fields.nonce_da_mode.into(),
+        fee_data_availability_mode: fields.fee_da_mode.into(),
+        chain_id: fields.chain,
+        is_query: fields.query,
+    }
+}
+
+/// Encodes the calls into the calldata of an account's `__execute__` entrypoint.
+///
+/// Cairo 1 accounts take an array of `Call` structs, while legacy (Cairo 0) accounts take an
+/// array of call descriptors whose calldata are offsets into a single concatenated array.
+pub fn execute_calldata(calls: &[Call], legacy: bool) -> Vec<FieldElement> {
+    let mut execute_calldata = vec![FieldElement::from(calls.len())];
+
+    if legacy {
+        let mut concated_calldata = Vec::new();
+        for call in calls {
+            execute_calldata.push(call.to);
+            execute_calldata.push(call.selector);
+            execute_calldata.push(concated_calldata.len().into()); // data_offset
+            execute_calldata.push(call.calldata.len().into()); // data_len
+            concated_calldata.extend_from_slice(&call.calldata);
+        }
+        execute_calldata.push(concated_calldata.len().into()); // calldata_len
+        execute_calldata.extend(concated_calldata);
+    } else {
+        for call in calls {
+            execute_calldata.push(call.to);
+            execute_calldata.push(call.selector);
+            execute_calldata.push(call.calldata.len().into());
+            execute_calldata.extend_from_slice(&call.calldata);
+        }
+    }
+
+    execute_calldata
+}
+
+#[cfg(test)]
+mod tests {
+    use probe_args::commands::utility::IndexArgs;
+    use probe_args::opts::starknet::StarknetOptions;
+    use starknet::core::types::{BlockId, BlockTag};
+    use starknet::macros::selector;
+
+    use super::*;
+
+    const SIERRA_ARTIFACT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../tests/test-contracts/hello_starknet.contract_class.json"
+    );
+
+    const CASM_ARTIFACT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../tests/test-contracts/hello_starknet.compiled_contract_class.json"
+    );
+
+    #[test]
+    fn sierra_class_hash() {
+        let hash = class_hash(SIERRA_ARTIFACT).unwrap();
+        assert_eq!(
+            hash,
+            felt!("0x7a107b80f225e95a58e6ba915c89fa4676b7bc439eeac6c38d56616c7f313c4")
+        );
+    }
+
+    #[test]
+    fn sierra_compiled_class_hash() {
+        let hash = compiled_class_hash(SIERRA_ARTIFACT).unwrap();
+        assert_eq!(
+            hash,
+            felt!("0x2a22d6b5124d1f581f2361e05d9b787cccaf7371b7d5eef6ab7e6c97974a25c")
+        );
+    }
+
+    #[test]
+    fn casm_compiled_class_hash() {
+        let hash = casm_class_hash(CASM_ARTIFACT).unwrap();
+        assert_eq!(hash, compiled_class_hash(SIERRA_ARTIFACT).unwrap());
+    }
+
+    #[test]
+    fn hash_functions() {
+        let elements = [felt!("1"), felt!("2"), felt!("3")];
+
+        assert_eq!(
+            pedersen(&elements),
+            felt!("0xf9d95fbf356fbeda26538c92f7040abe51bf142350f73c9ee5ba7c660bae71")
+        );
+        assert_eq!(
+            pedersen(&[]),
+            felt!("0x49ee3eba8c1600700ee1b87eb599f16716b0b1022947733551fde4050ca6804")
+        );
+        assert_eq!(
+            poseidon(&elements),
+            felt!("0x2f0d8840bcf3bc629598d8a6cc80cb7c0d9e52d93dab244bbf9cd0dca0ad082")
+        );
+        assert_eq!(
+            keccak("transfer").unwrap(),
+            felt!("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e")
+        );
+        assert_eq!(
+            keccak("0xdeadbeef").unwrap(),
+            felt!("0xfd4e189132273036449fc9e11198c739161b4c0116a9a2dccdfa1c492006f1")
+        );
+    }
+
+    #[test]
+    fn short_string_round_trip() {
+        let felt = short_string_to_felt("hello").unwrap();
+        assert_eq!(felt, felt!("0x68656c6c6f"));
+        assert_eq!(felt_to_short_string(&felt).unwrap(), "hello");
+
+        let long = "a".repeat(32);
+        assert!(short_string_to_felt(&long).is_err());
+    }
+
+    #[test]
+    fn ecdsa_sign_and_verify() {
+        let private_key = felt!("0x1234");
+        let public_key = felt!("0x26da8d11938b76025862be14fdb8b28438827f73e75e86f7bfa38b196951fa7");
+        let message = felt!("0x5678");
+
+        let signature = ecdsa_sign(&private_key, &message).unwrap();
+        assert!(ecdsa_verify(&public_key, &message, signature.r, signature.s).unwrap());
+        assert!(!ecdsa_verify(&public_key, &felt!("0x9999"), signature.r, signature.s).unwrap());
+    }
+
+    #[test]
+    fn split_u256_limbs() {
+        let (low, high) = split_u256("0x100000000000000000000000000000002").unwrap();
+        assert_eq!(low, felt!("2"));
+        assert_eq!(high, felt!("1"));
+
+        let (low, high) = split_u256("340282366920938463463374607431768211455").unwrap();
+        assert_eq!(low, felt!("0xffffffffffffffffffffffffffffffff"));
+        assert_eq!(high, FieldElement::ZERO);
+    }
+
+    #[test]
+    fn contract_address() {
+        let address = compute_address(
+            FieldElement::ZERO,
+            felt!("0x1234"),
+            felt!("0xabcd"),
+            &[felt!("1"), felt!("2")],
+        );
+        assert_eq!(
+            address,
+            felt!("0x123d717b9f7640de2b38859b7daf743829fac31e5b66dc5b7643e083debb7a0")
+        );
+    }
+
+    #[test]
+    fn storage_var_address() {
+        let args = IndexArgs {
+            var_name: "balances".to_string(),
+            keys: vec!["0x1234".to_string()],
+            key_types: Vec::new(),
+            components: Vec::new(),
+            list_index: None,
+            item_size: 1,
+            offset: 0,
+            contract: None,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            starknet: StarknetOptions { rpc_url: None },
+        };
+        assert_eq!(
+            storage_address(&args).unwrap(),
+            felt!("0x730784555874dce9495f6892fa28aba9abd33ebaec60b2dd4f2dc7f76a05e75")
+        );
+    }
+
+    #[test]
+    fn udc_address() {
+        let class_hash = felt!("0x7a107b80f225e95a58e6ba915c89fa4676b7bc439eeac6c38d56616c7f313c4");
+        let calldata = [felt!("1"), felt!("2")];
+
+        let unique =
+            udc_deployed_address(felt!("0x1234"), felt!("0xabcd"), true, class_hash, &calldata);
+        assert_eq!(
+            unique,
+            felt!("0x5e62c7c3708e8bdbea700436a4e049baafb690a33520e0240dd96e0549e506d")
+        );
+
+        let not_unique =
+            udc_deployed_address(felt!("0x1234"), felt!("0xabcd"), false, class_hash, &calldata);
+        assert_eq!(
+            not_unique,
+            felt!("0x5864faab38336a2524941c387867a7a3a819899f435e13c1fb9cabf64b53071")
+        );
+    }
+
+    fn calls() -> Vec<Call> {
+        vec![
+            Call {
+                to: felt!("0x123456789"),
+                selector: selector!("balanceOf"),
+                calldata: vec![felt!("0x987654321")],
+            },
+            Call {
+                to: felt!("0xabc298498723"),
+                selector: selector!("get_the_owner_of_something"),
+                calldata: vec![felt!("0x1abdf988"), felt!("0x9872349"), felt!("0x19831")],
+            },
+        ]
+    }
+
+    #[test]
+    fn legacy_execute_calldata() {
+        assert_eq!(
+            execute_calldata(&calls(), true),
+            vec![
+                felt!("2"),
+                felt!("0x123456789"),
+                selector!("balanceOf"),
+                felt!("0"),
+                felt!("1"),
+                felt!("0xabc298498723"),
+                selector!("get_the_owner_of_something"),
+                felt!("1"),
+                felt!("3"),
+                felt!("4"),
+                felt!("0x987654321"),
+                felt!("0x1abdf988"),
+                felt!("0x9872349"),
+                felt!("0x19831"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cairo1_execute_calldata() {
+        assert_eq!(
+            execute_calldata(&calls(), false),
+            vec![
+                felt!("2"),
+                felt!("0x123456789"),
+                selector!("balanceOf"),
+                felt!("1"),
+                felt!("0x987654321"),
+                felt!("0xabc298498723"),
+                selector!("get_the_owner_of_something"),
+                felt!("3"),
+                felt!("0x1abdf988"),
+                felt!("0x9872349"),
+                felt!("0x19831"),
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_array_round_trip() {
+        let felts = to_byte_array(Some("Hello, Starknet! This is longer than 31 bytes.".into()));
+        let felts = felts.unwrap().iter().map(|felt| format!("{felt:#x}")).collect::<Vec<_>>();
+        assert_eq!(felts.len(), 4);
+
+        let string = from_byte_array(vec![felts.join(",")]).unwrap();
+        assert_eq!(string, "Hello, Starknet! This is longer than 31 bytes.");
+
+        // as printed by `tx --json`
+        let string = from_byte_array(vec![r#"["0x0", "0x616263", "0x3"]"#.into()]).unwrap();
+        assert_eq!(string, "abc");
+
+        assert!(from_byte_array(vec!["0x0 0x616263 0x3 0x1".into()]).is_err());
+    }
 }
@@ -0,0 +1,145 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
+
+use color_eyre::eyre::{bail, Context};
+use color_eyre::Result;
+use probe_args::commands::utility::SelectorArgs;
+use probe_args::parser::selector_parser;
+use probe_args::selectors::SelectorDb;
+use starknet::core::types::contract::legacy::RawLegacyAbiEntry;
+use starknet::core::types::contract::{AbiEntry, AbiEvent, TypedAbiEvent};
+use starknet::core::types::{
+    BlockId, BlockTag, ContractArtifact, ContractClass, FieldElement, LegacyContractAbiEntry,
+};
+use starknet::providers::Provider;
+
+use crate::utils;
+
+/// Computes the selector of a name, or looks up the names of a selector in the local database.
+pub fn selector(value: &str) -> Result<Vec<String>> {
+    let Ok(selector) = FieldElement::from_str(value) else {
+        return Ok(vec![format!("{:#x}", selector_parser(value)?)]);
+    };
+
+    let db = SelectorDb::open(SelectorDb::default_path())?;
+    let names = [("function", db.function(selector)), ("event", db.event(selector))]
+        .into_iter()
+        .filter_map(|(kind, name)| name.map(|name| format!("{kind}: {name}")))
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        bail!("unknown selector {selector:#x}");
+    }
+
+    Ok(names)
+}
+
+/// Imports the function and event names of the ABIs given by `args` into the local database.
+///
+/// Returns the number of names that weren't known yet.
+pub fn import_selectors(args: &SelectorArgs) -> Result<usize> {
+    let path = SelectorDb::default_path();
+    let mut db = SelectorDb::open(&path)?;
+    let mut added = 0;
+
+    if let Some(artifact) = &args.import {
+        added += import_artifact(&mut db, artifact)?;
+    }
+
+    let provider = args.starknet.provider();
+    let block_id = BlockId::Tag(BlockTag::Pending);
+
+    if let Some(class_hash) = args.import_class {
+        let class = utils::block_on(provider.get_class(block_id, class_hash))?;
+        added += import_class(&mut db, class)?;
+    }
+
+    if let Some(address) = args.import_contract {
+        let class = utils::block_on(provider.get_class_at(block_id, address))?;
+        added += import_class(&mut db, class)?;
+    }
+
+    db.save(&path)?;
+    Ok(added)
+}
+
+fn import_artifact(db: &mut SelectorDb, path: &Path) -> Result<usize> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let artifact: ContractArtifact =
+        serde_json::from_reader(BufReader::new(file)).context("invalid contract artifact")?;
+
+    match artifact {
+        ContractArtifact::SierraClass(class) => Ok(import_abi(db, &class.abi)),
+        ContractArtifact::LegacyClass(class) => Ok(import_raw_legacy_abi(db, &class.abi)),
+        ContractArtifact::CompiledClass(_) => {
+            bail!("compiled (CASM) classes have no ABI, use the Sierra class instead")
+        }
+    }
+}
+
+fn import_class(db: &mut SelectorDb, class: ContractClass) -> Result<usize> {
+    match class {
+        ContractClass::Sierra(class) => {
+            let abi: Vec<AbiEntry> =
+                serde_json::from_str(&class.abi).context("invalid contract ABI")?;
+            Ok(import_abi(db, &abi))
+        }
+
+        ContractClass::Legacy(class) => Ok(import_legacy_abi(db, &class.abi.unwrap_or_default())),
+    }
+}
+
+fn import_abi(db: &mut SelectorDb, abi: &[AbiEntry]) -> usize {
+    let mut added = 0;
+
+    for entry in abi {
+        added += match entry {
+            AbiEntry::Function(function) => db.add_function(&function.name) as usize,
+            AbiEntry::L1Handler(handler) => db.add_function(&handler.name) as usize,
+            AbiEntry::Constructor(constructor) => db.add_function(&constructor.name) as usize,
+            AbiEntry::Interface(interface) => import_abi(db, &interface.items),
+
+            // the selector of a typed event is the name of its variant in the `Event` enum
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(event))) => {
+                event.variants.iter().filter(|variant| db.add_event(&variant.name)).count()
+            }
+            AbiEntry::Event(AbiEvent::Untyped(event)) => db.add_event(&event.name) as usize,
+
+            _ => 0,
+        };
+    }
+
+    added
+}
+
+fn import_raw_legacy_abi(db: &mut SelectorDb, abi: &[RawLegacyAbiEntry]) -> usize {
+    let mut added = 0;
+
+    for entry in abi {
+        added += match entry {
+            RawLegacyAbiEntry::Function(function) => db.add_function(&function.name),
+            RawLegacyAbiEntry::L1Handler(handler) => db.add_function(&handler.name),
+            RawLegacyAbiEntry::Constructor(constructor) => db.add_function(&constructor.name),
+            RawLegacyAbiEntry::Event(event) => db.add_event(&event.name),
+            RawLegacyAbiEntry::Struct(_) => false,
+        } as usize;
+    }
+
+    added
+}
+
+fn import_legacy_abi(db: &mut SelectorDb, abi: &[LegacyContractAbiEntry]) -> usize {
+    let mut added = 0;
+
+    for entry in abi {
+        added += match entry {
+            LegacyContractAbiEntry::Function(function) => db.add_function(&function.name),
+            LegacyContractAbiEntry::Event(event) => db.add_event(&event.name),
+            LegacyContractAbiEntry::Struct(_) => false,
+        } as usize;
+    }
+
+    added
+}
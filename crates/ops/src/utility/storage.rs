@@ -0,0 +1,151 @@
+//! The storage layout of Cairo 1 contracts.
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use probe_args::commands::utility::IndexArgs;
+use starknet::core::crypto::pedersen_hash;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{normalize_address, starknet_keccak};
+use starknet_crypto::poseidon_hash_many;
+
+use crate::abi::Abi;
+
+/// The type of the keys whose type isn't given.
+const FELT: &str = "felt252";
+
+/// Computes the address of a storage variable from its location in the contract's storage.
+///
+/// The members of a storage node are at `pedersen(node, sn_keccak(member))`, the entries of a map
+/// (and the items of a `Vec`) at `pedersen(...pedersen(map, key_0)..., key_n)` where the keys are
+/// serialized with `Serde` according to their type, and the address is finally reduced to a valid
+/// storage address.
+pub fn storage_address(args: &IndexArgs) -> Result<FieldElement> {
+    let mut names = args.components.iter().chain([&args.var_name]);
+    let first = names.next().expect("the variable name is always present");
+
+    let mut address = starknet_keccak(first.as_bytes());
+    for name in names {
+        address = pedersen_hash(&address, &starknet_keccak(name.as_bytes()));
+    }
+
+    if args.key_types.len() > args.keys.len() {
+        bail!("got {} key types for {} keys", args.key_types.len(), args.keys.len());
+    }
+
+    // the key types are core types, which don't need the ABI of the contract to be resolved
+    let abi = Abi::default();
+    for (idx, key) in args.keys.iter().enumerate() {
+        let ty = args.key_types.get(idx).map_or(FELT, String::as_str);
+        let felts = abi.encode_key(ty, key)?;
+        address = felts.iter().fold(address, |address, felt| pedersen_hash(&address, felt));
+    }
+
+    let mut address = normalize_address(address);
+
+    if let Some(index) = args.list_index {
+        address = list_item_address(address, index, args.item_size)?;
+    }
+
+    Ok(address + FieldElement::from(args.offset))
+}
+
+/// Returns the address of an item of an alexandria `List`, whose items are stored in segments of
+/// 256 slots at `poseidon(list, segment)`.
+fn list_item_address(list: FieldElement, index: u32, item_size: u8) -> Result<FieldElement> {
+    if item_size == 0 {
+        bail!("the items of a list take at least one slot");
+    }
+
+    let items_per_segment = 256 / item_size as u32;
+    let (segment, offset) = (index / items_per_segment, index % items_per_segment);
+
+    let segment = normalize_address(poseidon_hash_many(&[list, segment.into()]));
+    Ok(segment + FieldElement::from(offset * item_size as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use probe_args::opts::starknet::StarknetOptions;
+    use starknet::core::types::{BlockId, BlockTag};
+    use starknet::core::utils::get_storage_var_address;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    fn index_args(var_name: &str, keys: &[&str]) -> IndexArgs {
+        IndexArgs {
+            var_name: var_name.to_string(),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            key_types: Vec::new(),
+            components: Vec::new(),
+            list_index: None,
+            item_size: 1,
+            offset: 0,
+            contract: None,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            starknet: StarknetOptions { rpc_url: None },
+        }
+    }
+
+    fn address(var_name: &str, keys: &[&str]) -> FieldElement {
+        storage_address(&index_args(var_name, keys)).unwrap()
+    }
+
+    #[test]
+    fn typed_map_keys() {
+        // nested maps and tuple keys are hashed the same way
+        let expected =
+            get_storage_var_address("allowances", &[felt!("0x1"), felt!("0x2")]).unwrap();
+        assert_eq!(address("allowances", &["0x1", "0x2"]), expected);
+        let args = IndexArgs {
+            key_types: vec!["(ContractAddress, ContractAddress)".to_string()],
+            ..index_args("allowances", &["(0x1, 0x2)"])
+        };
+        assert_eq!(storage_address(&args).unwrap(), expected);
+
+        let expected = get_storage_var_address("totals", &[felt!("5"), felt!("0")]).unwrap();
+        let args =
+            IndexArgs { key_types: vec!["u256".to_string()], ..index_args("totals", &["5"]) };
+        assert_eq!(storage_address(&args).unwrap(), expected);
+
+        let expected = get_storage_var_address("names", &[felt!("0x61")]).unwrap();
+        assert_eq!(address("names", &["\"a\""]), expected);
+
+        let expected = get_storage_var_address("deltas", &[-FieldElement::ONE]).unwrap();
+        assert_eq!(address("deltas", &["-1"]), expected);
+
+        // without a type, a key is a single felt
+        assert!(storage_address(&index_args("allowances", &["(0x1, 0x2)"])).is_err());
+        let args =
+            IndexArgs { key_types: vec!["u8".to_string()], ..index_args("totals", &["256"]) };
+        assert!(storage_address(&args).is_err());
+    }
+
+    #[test]
+    fn offsets_and_components() {
+        let base = get_storage_var_address("config", &[]).unwrap();
+        let args = IndexArgs { offset: 2, ..index_args("config", &[]) };
+        assert_eq!(storage_address(&args).unwrap(), base + felt!("2"));
+
+        let node = starknet_keccak(b"erc20");
+        let expected = normalize_address(pedersen_hash(
+            &pedersen_hash(&node, &starknet_keccak(b"balances")),
+            &felt!("0x1"),
+        ));
+        let args =
+            IndexArgs { components: vec!["erc20".to_string()], ..index_args("balances", &["0x1"]) };
+        assert_eq!(storage_address(&args).unwrap(), expected);
+    }
+
+    #[test]
+    fn list_items() {
+        let list = get_storage_var_address("items", &[]).unwrap();
+        let segment = |key: u32| normalize_address(poseidon_hash_many(&[list, key.into()]));
+
+        let args = IndexArgs { list_index: Some(3), ..index_args("items", &[]) };
+        assert_eq!(storage_address(&args).unwrap(), segment(0) + felt!("3"));
+
+        let args = IndexArgs { list_index: Some(130), item_size: 2, ..index_args("items", &[]) };
+        assert_eq!(storage_address(&args).unwrap(), segment(1) + felt!("4"));
+    }
+}
@@ -0,0 +1,487 @@
+//! Offline computation of transaction hashes.
+
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use starknet::core::crypto::compute_hash_on_elements;
+use starknet::core::types::{
+    DataAvailabilityMode, DeclareTransaction, DeployAccountTransaction, FieldElement,
+    InvokeTransaction, ResourceBounds, ResourceBoundsMapping, Transaction,
+};
+use starknet::core::utils::get_contract_address;
+use starknet::macros::{felt, short_string};
+use starknet_crypto::poseidon_hash_many;
+
+/// Added to the transaction version of transactions that are only meant for querying (e.g. fee
+/// estimation), making them invalid for execution.
+pub const QUERY_VERSION_OFFSET: FieldElement = felt!("0x100000000000000000000000000000000");
+
+const PREFIX_INVOKE: FieldElement = short_string!("invoke");
+const PREFIX_DECLARE: FieldElement = short_string!("declare");
+const PREFIX_DEPLOY_ACCOUNT: FieldElement = short_string!("deploy_account");
+
+const L1_GAS: FieldElement = short_string!("L1_GAS");
+const L2_GAS: FieldElement = short_string!("L2_GAS");
+
+/// The fields that are hashed the same way by all V3 transactions.
+#[derive(Debug, Clone)]
+pub struct V3Fields<'a> {
+    /// The sender address, or the address of the account being deployed for `DEPLOY_ACCOUNT`.
+    pub sender_address: FieldElement,
+    pub nonce: FieldElement,
+    pub resource_bounds: &'a ResourceBoundsMapping,
+    pub tip: u64,
+    pub paymaster_data: &'a [FieldElement],
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+    pub chain_id: FieldElement,
+    pub is_query: bool,
+}
+
+impl V3Fields<'_> {
+    fn fee_fields_hash(&self) -> FieldElement {
+        poseidon_hash_many(&[
+            self.tip.into(),
+            concat_resource(&self.resource_bounds.l1_gas, L1_GAS),
+            concat_resource(&self.resource_bounds.l2_gas, L2_GAS),
+        ])
+    }
+
+    // [0 (192 bits) | nonce mode (32 bits) | fee mode (32 bits)]
+    fn data_availability_modes(&self) -> FieldElement {
+        let index = |mode: DataAvailabilityMode| match mode {
+            DataAvailabilityMode::L1 => 0u64,
+            DataAvailabilityMode::L2 => 1u64,
+        };
+        ((index(self.nonce_data_availability_mode) << 32) + index(self.fee_data_availability_mode))
+            .into()
+    }
+}
+
+// [0 (8 bits) | resource name (56 bits) | max amount (64 bits) | max price per unit (128 bits)]
+fn concat_resource(bounds: &ResourceBounds, name: FieldElement) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    bytes[1..8].copy_from_slice(&name.to_bytes_be()[25..]);
+    bytes[8..16].copy_from_slice(&bounds.max_amount.to_be_bytes());
+    bytes[16..].copy_from_slice(&bounds.max_price_per_unit.to_be_bytes());
+    FieldElement::from_bytes_be(&bytes).expect("fits in a field element")
+}
+
+fn version(version: u8, is_query: bool) -> FieldElement {
+    let version = FieldElement::from(version);
+    if is_query {
+        version + QUERY_VERSION_OFFSET
+    } else {
+        version
+    }
+}
+
+/// Computes the hash of a `DEPLOY_ACCOUNT` v1 transaction.
+pub fn deploy_account_v1(
+    class_hash: FieldElement,
+    salt: FieldElement,
+    constructor_calldata: &[FieldElement],
+    max_fee: FieldElement,
+    nonce: FieldElement,
+    chain_id: FieldElement,
+    is_query: bool,
+) -> FieldElement {
+    let address = get_contract_address(salt, class_hash, constructor_calldata, FieldElement::ZERO);
+
+    let mut calldata = vec![class_hash, salt];
+    calldata.extend_from_slice(constructor_calldata);
+
+    compute_hash_on_elements(&[
+        PREFIX_DEPLOY_ACCOUNT,
+        version(1, is_query),
+        address,
+        FieldElement::ZERO, // entry point selector
+        compute_hash_on_elements(&calldata),
+        max_fee,
+        chain_id,
+        nonce,
+    ])
+}
+
+/// Computes the hash of an `INVOKE` v1 transaction.
+pub fn invoke_v1(
+    sender_address: FieldElement,
+    calldata: &[FieldElement],
+    max_fee: FieldElement,
+    nonce: FieldElement,
+    chain_id: FieldElement,
+    is_query: bool,
+) -> FieldElement {
+    compute_hash_on_elements(&[
+        PREFIX_INVOKE,
+        version(1, is_query),
+        sender_address,
+        FieldElement::ZERO, // entry point selector
+        compute_hash_on_elements(calldata),
+        max_fee,
+        chain_id,
+        nonce,
+    ])
+}
+
+/// Computes the hash of a `DECLARE` v2 transaction.
+pub fn declare_v2(
+    sender_address: FieldElement,
+    class_hash: FieldElement,
+    compiled_class_hash: FieldElement,
+    max_fee: FieldElement,
+    nonce: FieldElement,
+    chain_id: FieldElement,
+    is_query: bool,
+) -> FieldElement {
+    compute_hash_on_elements(&[
+        PREFIX_DECLARE,
+        version(2, is_query),
+        sender_address,
+        FieldElement::ZERO, // entry point selector
+        compute_hash_on_elements(&[class_hash]),
+        max_fee,
+        chain_id,
+        nonce,
+        compiled_class_hash,
+    ])
+}
+
+/// Computes the hash of a `DEPLOY_ACCOUNT` v3 transaction.
+pub fn deploy_account_v3(
+    fields: &V3Fields<'_>,
+    class_hash: FieldElement,
+    salt: FieldElement,
+    constructor_calldata: &[FieldElement],
+) -> FieldElement {
+    poseidon_hash_many(&[
+        PREFIX_DEPLOY_ACCOUNT,
+        version(3, fields.is_query),
+        fields.sender_address,
+        fields.fee_fields_hash(),
+        poseidon_hash_many(fields.paymaster_data),
+        fields.chain_id,
+        fields.nonce,
+        fields.data_availability_modes(),
+        poseidon_hash_many(constructor_calldata),
+        class_hash,
+        salt,
+    ])
+}
+
+/// Computes the hash of an `INVOKE` v3 transaction.
+pub fn invoke_v3(
+    fields: &V3Fields<'_>,
+    account_deployment_data: &[FieldElement],
+    calldata: &[FieldElement],
+) -> FieldElement {
+    poseidon_hash_many(&[
+        PREFIX_INVOKE,
+        version(3, fields.is_query),
+        fields.sender_address,
+        fields.fee_fields_hash(),
+        poseidon_hash_many(fields.paymaster_data),
+        fields.chain_id,
+        fields.nonce,
+        fields.data_availability_modes(),
+        poseidon_hash_many(account_deployment_data),
+        poseidon_hash_many(calldata),
+    ])
+}
+
+/// Computes the hash of a `DECLARE` v3 transaction.
+pub fn declare_v3(
+    fields: &V3Fields<'_>,
+    account_deployment_data: &[FieldElement],
+    class_hash: FieldElement,
+    compiled_class_hash: FieldElement,
+) -> FieldElement {
+    poseidon_hash_many(&[
+        PREFIX_DECLARE,
+        version(3, fields.is_query),
+        fields.sender_address,
+        fields.fee_fields_hash(),
+        poseidon_hash_many(fields.paymaster_data),
+        fields.chain_id,
+        fields.nonce,
+        fields.data_availability_modes(),
+        poseidon_hash_many(account_deployment_data),
+        class_hash,
+        compiled_class_hash,
+    ])
+}
+
+/// Recomputes the hash of a transaction as returned by the node (e.g.
+/// `starknet_getTransactionByHash`).
+///
+/// Only the transaction versions that can still be submitted to the network are supported.
+pub fn transaction_hash(tx: &Transaction, chain_id: FieldElement) -> Result<FieldElement> {
+    let hash = match tx {
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => {
+            invoke_v1(tx.sender_address, &tx.calldata, tx.max_fee, tx.nonce, chain_id, false)
+        }
+
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => {
+            let fields = V3Fields {
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                resource_bounds: &tx.resource_bounds,
+                tip: tx.tip,
+                paymaster_data: &tx.paymaster_data,
+                nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                fee_data_availability_mode: tx.fee_data_availability_mode,
+                chain_id,
+                is_query: false,
+            };
+            invoke_v3(&fields, &tx.account_deployment_data, &tx.calldata)
+        }
+
+        Transaction::Declare(DeclareTransaction::V2(tx)) => declare_v2(
+            tx.sender_address,
+            tx.class_hash,
+            tx.compiled_class_hash,
+            tx.max_fee,
+            tx.nonce,
+            chain_id,
+            false,
+        ),
+
+        Transaction::Declare(DeclareTransaction::V3(tx)) => {
+            let fields = V3Fields {
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                resource_bounds: &tx.resource_bounds,
+                tip: tx.tip,
+                paymaster_data: &tx.paymaster_data,
+                nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                fee_data_availability_mode: tx.fee_data_availability_mode,
+                chain_id,
+                is_query: false,
+            };
+            declare_v3(&fields, &tx.account_deployment_data, tx.class_hash, tx.compiled_class_hash)
+        }
+
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => deploy_account_v1(
+            tx.class_hash,
+            tx.contract_address_salt,
+            &tx.constructor_calldata,
+            tx.max_fee,
+            tx.nonce,
+            chain_id,
+            false,
+        ),
+
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => {
+            let sender_address = get_contract_address(
+                tx.contract_address_salt,
+                tx.class_hash,
+                &tx.constructor_calldata,
+                FieldElement::ZERO,
+            );
+            let fields = V3Fields {
+                sender_address,
+                nonce: tx.nonce,
+                resource_bounds: &tx.resource_bounds,
+                tip: tx.tip,
+                paymaster_data: &tx.paymaster_data,
+                nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                fee_data_availability_mode: tx.fee_data_availability_mode,
+                chain_id,
+                is_query: false,
+            };
+            deploy_account_v3(
+                &fields,
+                tx.class_hash,
+                tx.contract_address_salt,
+                &tx.constructor_calldata,
+            )
+        }
+
+        _ => bail!("unsupported transaction type or version"),
+    };
+
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The expected hashes were computed with `get_transaction_hash` from starknet_api, the
+    // sequencer's implementation, for transactions of the OZ account deployed with `PUBLIC_KEY`
+    // and a salt of 1 on sepolia: sending 1 STRK, declaring the OZ account class, and deploying
+    // the account itself.
+
+    const SEPOLIA: FieldElement = short_string!("SN_SEPOLIA");
+    const SENDER: FieldElement =
+        felt!("0x248a5679921d798085809aaf7799000949c305015b1396e1ba6f509cbbc19d5");
+    const PUBLIC_KEY: FieldElement =
+        felt!("0x26da8d11938b76025862be14fdb8b28438827f73e75e86f7bfa38b196951fa7");
+    const OZ_CLASS_HASH: FieldElement =
+        felt!("0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f");
+    const COMPILED_CLASS_HASH: FieldElement =
+        felt!("0x2a22d6b5124d1f581f2361e05d9b787cccaf7371b7d5eef6ab7e6c97974a25c");
+    const MAX_FEE: FieldElement = felt!("0x2386f26fc10000");
+
+    /// The calldata of a transfer of 1 STRK.
+    fn transfer_calldata() -> Vec<FieldElement> {
+        vec![
+            felt!("0x1"),
+            felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"),
+            felt!("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"),
+            felt!("0x3"),
+            felt!("0x4b3f4ba8c00a02b66142a4b1dd41a4dfab4f92650922a3280977b0f03c75ee1"),
+            felt!("0xde0b6b3a7640000"),
+            felt!("0x0"),
+        ]
+    }
+
+    fn resource_bounds() -> ResourceBoundsMapping {
+        ResourceBoundsMapping {
+            l1_gas: ResourceBounds { max_amount: 0x7d0, max_price_per_unit: 0x5af3107a4000 },
+            l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+        }
+    }
+
+    fn fields(resource_bounds: &ResourceBoundsMapping, nonce: FieldElement) -> V3Fields<'_> {
+        V3Fields {
+            sender_address: SENDER,
+            nonce,
+            resource_bounds,
+            tip: 0,
+            paymaster_data: &[],
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+            chain_id: SEPOLIA,
+            is_query: false,
+        }
+    }
+
+    #[test]
+    fn invoke_v1_hash() {
+        let hash = |is_query| {
+            invoke_v1(SENDER, &transfer_calldata(), MAX_FEE, felt!("0x1"), SEPOLIA, is_query)
+        };
+        assert_eq!(
+            hash(false),
+            felt!("0x5d921ef1e4aa511b954f280ed5fd54ca066c2d64eca5cd95eaffa3bcd96944e")
+        );
+        assert_eq!(
+            hash(true),
+            felt!("0x66cf4f6ca476a2da640fb9c623ffecf098432a766d6bf3c3d523f1fa191e4d8")
+        );
+    }
+
+    #[test]
+    fn invoke_v3_hash() {
+        let resource_bounds = resource_bounds();
+        let fields = fields(&resource_bounds, felt!("0x2"));
+        let hash = |fields: &V3Fields<'_>| invoke_v3(fields, &[], &transfer_calldata());
+
+        assert_eq!(
+            hash(&fields),
+            felt!("0x69008511c33e462061599f5fee23986d8b6a9eb9c01f55198c8bf55ae46aa51")
+        );
+        assert_eq!(
+            hash(&V3Fields {
+                tip: 10,
+                fee_data_availability_mode: DataAvailabilityMode::L2,
+                ..fields.clone()
+            }),
+            felt!("0x3a55763ae78f238628addc8b9c79336a5b159a00975eb5589df0ff99ec2cdde")
+        );
+        assert_eq!(
+            hash(&V3Fields { is_query: true, ..fields }),
+            felt!("0x6f4860919f0b6fd3d3ae2fb474c059711d4a3e35e5bd0aecd7aa172459612")
+        );
+    }
+
+    #[test]
+    fn declare_v2_hash() {
+        let hash = declare_v2(
+            SENDER,
+            OZ_CLASS_HASH,
+            COMPILED_CLASS_HASH,
+            MAX_FEE,
+            felt!("0x3"),
+            SEPOLIA,
+            false,
+        );
+        assert_eq!(
+            hash,
+            felt!("0x42e53a5f6405b6bf874f72ec83a9acd1a3f21e853ac08a731b5f22534d67611")
+        );
+    }
+
+    #[test]
+    fn declare_v3_hash() {
+        let resource_bounds = resource_bounds();
+        let hash = declare_v3(
+            &fields(&resource_bounds, felt!("0x4")),
+            &[],
+            OZ_CLASS_HASH,
+            COMPILED_CLASS_HASH,
+        );
+        assert_eq!(
+            hash,
+            felt!("0x4fd80b880cf5ee99f7f5b379bbc5697c1923d27146a8fd2b3e9a34c22ee9bcb")
+        );
+    }
+
+    #[test]
+    fn deploy_account_v1_hash() {
+        let hash = deploy_account_v1(
+            OZ_CLASS_HASH,
+            felt!("0x1"),
+            &[PUBLIC_KEY],
+            MAX_FEE,
+            FieldElement::ZERO,
+            SEPOLIA,
+            false,
+        );
+        assert_eq!(
+            hash,
+            felt!("0x78075a6548fa49f5098cab8665d0e8931bd61ca03c83503191f8d49043c27e8")
+        );
+    }
+
+    #[test]
+    fn deploy_account_v3_hash() {
+        let resource_bounds = resource_bounds();
+        let hash = deploy_account_v3(
+            &fields(&resource_bounds, FieldElement::ZERO),
+            OZ_CLASS_HASH,
+            felt!("0x1"),
+            &[PUBLIC_KEY],
+        );
+        assert_eq!(
+            hash,
+            felt!("0x7f95c34d28dac66f900533c21c85a8ee313028b4da49a910c0c5c247c130fce")
+        );
+    }
+
+    #[test]
+    fn transaction_json_hash() {
+        // the address of the account is derived from the deployment
+        let json = r#"{
+            "type": "DEPLOY_ACCOUNT",
+            "version": "0x3",
+            "transaction_hash": "0x7f95c34d28dac66f900533c21c85a8ee313028b4da49a910c0c5c247c130fce",
+            "signature": [],
+            "nonce": "0x0",
+            "contract_address_salt": "0x1",
+            "constructor_calldata": ["0x26da8d11938b76025862be14fdb8b28438827f73e75e86f7bfa38b196951fa7"],
+            "class_hash": "0x61dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f",
+            "resource_bounds": {
+                "l1_gas": { "max_amount": "0x7d0", "max_price_per_unit": "0x5af3107a4000" },
+                "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1"
+        }"#;
+
+        let tx: Transaction = serde_json::from_str(json).unwrap();
+        assert_eq!(transaction_hash(&tx, SEPOLIA).unwrap(), *tx.transaction_hash());
+    }
+}
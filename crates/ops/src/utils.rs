@@ -1,10 +1,14 @@
 use std::future::Future;
 
 use alloy_primitives::U256;
-use color_eyre::eyre::Context;
+use color_eyre::eyre::{eyre, Context};
 use color_eyre::Result;
 use starknet::core::types::FieldElement;
 
+/// The most requests that are sent to the node at the same time, e.g. when sampling a balance over
+/// a range of blocks.
+pub(crate) const CONCURRENCY: usize = 16;
+
 /// Blocks on a future, returning the output.
 pub fn block_on<F, T>(future: F) -> T
 where
@@ -18,8 +22,30 @@ where
         .block_on(future)
 }
 
+/// Joins the `(low, high)` 128-bit limbs of a Cairo `u256`.
 pub fn to_u256(low: FieldElement, high: FieldElement) -> Result<U256> {
-    let low: u128 = low.try_into().context("parsing low")?;
-    let high: u128 = high.try_into().context("parsing high")?;
+    let low: u128 =
+        low.try_into().with_context(|| format!("low limb {low:#x} exceeds 128 bits"))?;
+    let high: u128 =
+        high.try_into().with_context(|| format!("high limb {high:#x} exceeds 128 bits"))?;
     Ok(U256::from(high) << 128 | U256::from(low))
 }
+
+/// Splits a `U256` into the `(low, high)` 128-bit limbs of a Cairo `u256`.
+pub fn from_u256(value: U256) -> (FieldElement, FieldElement) {
+    let bytes = value.to_be_bytes::<32>();
+    let high = FieldElement::from_byte_slice_be(&bytes[..16]).expect("128 bits fit in a felt");
+    let low = FieldElement::from_byte_slice_be(&bytes[16..]).expect("128 bits fit in a felt");
+    (low, high)
+}
+
+/// Converts a felt to its integer value.
+pub fn felt_to_u256(felt: FieldElement) -> U256 {
+    U256::from_be_bytes(felt.to_bytes_be())
+}
+
+/// Converts an integer to a felt, failing if it's not smaller than the Stark prime.
+pub fn u256_to_felt(value: U256) -> Result<FieldElement> {
+    FieldElement::from_bytes_be(&value.to_be_bytes())
+        .map_err(|_| eyre!("{value:#x} exceeds the Stark prime"))
+}